@@ -1,6 +1,12 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20250514_045755_create_table_users;
+mod m20261019_000001_create_table_audit_logs;
+mod m20261019_000002_create_table_outbox;
+mod m20261019_000003_create_table_webhook_subscriptions;
+mod m20261019_000004_create_table_webhook_deliveries;
+mod m20261019_000005_create_table_rate_limits;
+mod m20261019_000006_add_client_ip_to_audit_logs;
 
 pub struct Migrator;
 
@@ -9,6 +15,12 @@ impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         vec![
             Box::new(m20250514_045755_create_table_users::Migration),
+            Box::new(m20261019_000001_create_table_audit_logs::Migration),
+            Box::new(m20261019_000002_create_table_outbox::Migration),
+            Box::new(m20261019_000003_create_table_webhook_subscriptions::Migration),
+            Box::new(m20261019_000004_create_table_webhook_deliveries::Migration),
+            Box::new(m20261019_000005_create_table_rate_limits::Migration),
+            Box::new(m20261019_000006_add_client_ip_to_audit_logs::Migration),
         ]
     }
 }
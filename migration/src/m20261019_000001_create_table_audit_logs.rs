@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLogs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditLogs::Id)
+                            .big_integer()
+                            .auto_increment()
+                            .primary_key()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::UserUid)
+                            .string()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::Actor)
+                            .string()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::RequestId)
+                            .string()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::Action)
+                            .string()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::Before)
+                            .json_binary()
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::After)
+                            .json_binary()
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp())
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // ユーザー単位で履歴を取得するためのインデックス
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_logs_user_uid")
+                    .table(AuditLogs::Table)
+                    .col(AuditLogs::UserUid)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditLogs::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditLogs {
+    Table,
+    Id,
+    UserUid,
+    Actor,
+    RequestId,
+    Action,
+    Before,
+    After,
+    CreatedAt,
+}
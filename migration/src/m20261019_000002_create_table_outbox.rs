@@ -0,0 +1,112 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Outbox::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Outbox::Id)
+                            .big_integer()
+                            .auto_increment()
+                            .primary_key()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(Outbox::EventId)
+                            .string()
+                            .not_null()
+                            .unique_key()
+                    )
+                    .col(
+                        ColumnDef::new(Outbox::EventType)
+                            .string()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(Outbox::AggregateId)
+                            .string()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(Outbox::Payload)
+                            .json_binary()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(Outbox::Status)
+                            .string()
+                            .not_null()
+                            .default("pending")
+                    )
+                    .col(
+                        ColumnDef::new(Outbox::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0)
+                    )
+                    .col(
+                        ColumnDef::new(Outbox::NextAttemptAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp())
+                    )
+                    .col(
+                        ColumnDef::new(Outbox::LastError)
+                            .text()
+                    )
+                    .col(
+                        ColumnDef::new(Outbox::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp())
+                    )
+                    .col(
+                        ColumnDef::new(Outbox::PublishedAt)
+                            .timestamp_with_time_zone()
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 未送信イベントを取得するためのインデックス
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_outbox_status_next_attempt_at")
+                    .table(Outbox::Table)
+                    .col(Outbox::Status)
+                    .col(Outbox::NextAttemptAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Outbox::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Outbox {
+    Table,
+    Id,
+    EventId,
+    EventType,
+    AggregateId,
+    Payload,
+    Status,
+    Attempts,
+    NextAttemptAt,
+    LastError,
+    CreatedAt,
+    PublishedAt,
+}
@@ -0,0 +1,76 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookSubscriptions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::Id)
+                            .big_integer()
+                            .auto_increment()
+                            .primary_key()
+                            .not_null()
+                    )
+                    .col(string(WebhookSubscriptions::Uid).not_null().unique_key())
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::Url)
+                            .text()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::EventTypes)
+                            .json_binary()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::Secret)
+                            .string()
+                            .not_null()
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp())
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp())
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::DeletedAt)
+                            .timestamp_with_time_zone()
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WebhookSubscriptions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WebhookSubscriptions {
+    Table,
+    Id,
+    Uid,
+    Url,
+    EventTypes,
+    Secret,
+    CreatedAt,
+    UpdatedAt,
+    DeletedAt,
+}
@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RateLimits::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RateLimits::Key)
+                            .string()
+                            .not_null()
+                            .primary_key()
+                    )
+                    .col(
+                        // GCRAの理論上の到着時刻（UNIX時間のミリ秒）
+                        ColumnDef::new(RateLimits::Tat)
+                            .big_integer()
+                            .not_null()
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 期限切れの状態を削除するためのインデックス
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_rate_limits_tat")
+                    .table(RateLimits::Table)
+                    .col(RateLimits::Tat)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RateLimits::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RateLimits {
+    Table,
+    Key,
+    Tat,
+}
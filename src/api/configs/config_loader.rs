@@ -0,0 +1,319 @@
+use serde::Deserialize;
+use serde::de::{self, Visitor};
+
+// 標準ライブラリ
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+// configsモジュール
+use super::config::Config;
+use super::config_validation::validate;
+
+// 設定ファイルのディレクトリ（「config/{env}.toml」を読み込む）
+pub const CONFIG_DIR: &str = "config";
+
+// 設定内容を出力して終了するコマンドライン引数
+pub const ARG_PRINT_CONFIG: &str = "--print-config";
+
+// マウントしたファイルから値を読み込む項目名の接尾辞（例：DATABASE_URL_FILE）
+const FILE_SUFFIX: &str = "_file";
+
+// 旧名の設定（名前を変更した項目）と現在の設定の対応
+const LEGACY_KEYS: [(&str, &str); 1] = [("allow_origin", "cors_allow_origins")];
+
+// 設定の読み込みエラー（全ての項目のエラーをまとめて返す）
+#[derive(Debug, thiserror::Error)]
+#[error("設定が不正なため起動できません:\n{}", format_errors(.0))]
+pub struct ConfigError(pub Vec<String>);
+
+fn format_errors(errors: &[String]) -> String {
+    errors
+        .iter()
+        .map(|err| format!("  - {}", err))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// コマンドライン引数
+#[derive(Debug, Default, PartialEq)]
+pub struct CliArgs {
+    pub print_config: bool,
+    // 設定の上書き（例：--log-format=json、--port 8081）
+    pub overrides: Vec<(String, String)>,
+}
+
+// コマンドライン引数を変換する関数
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<CliArgs, ConfigError> {
+    let mut cli = CliArgs::default();
+    let mut errors = Vec::new();
+    let mut args = args.into_iter().peekable();
+    while let Some(arg) = args.next() {
+        if arg == ARG_PRINT_CONFIG {
+            cli.print_config = true;
+            continue;
+        }
+        let Some(option) = arg.strip_prefix("--") else {
+            errors.push(format!("不正な引数です: {}", arg));
+            continue;
+        };
+        let (name, value) = match option.split_once('=') {
+            Some((name, value)) => (name.to_string(), value.to_string()),
+            // 値が無い場合は真偽値のフラグとみなす
+            None => match args.next_if(|next| !next.starts_with("--")) {
+                Some(value) => (option.to_string(), value),
+                None => (option.to_string(), "true".to_string()),
+            },
+        };
+        let key = name.replace('-', "_").to_lowercase();
+        match normalize_key(&key) {
+            Some(key) => cli.overrides.push((key, value)),
+            None if file_key(&key).is_some() => cli.overrides.push((key, value)),
+            None => errors.push(format!("不明な引数です: --{}", name)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(cli)
+    } else {
+        Err(ConfigError(errors))
+    }
+}
+
+// 設定の項目名（serdeの定義から取得）
+pub fn config_keys() -> &'static [&'static str] {
+    static KEYS: OnceLock<&'static [&'static str]> = OnceLock::new();
+    KEYS.get_or_init(|| {
+        let mut probe = FieldNamesProbe(None);
+        let _ = Config::deserialize(&mut probe);
+        probe.0.unwrap_or(&[])
+    })
+}
+
+// 項目名に変換する関数（旧名は現在の名前に変換し、不明な項目はNone）
+fn normalize_key(key: &str) -> Option<String> {
+    let key = key.to_lowercase();
+    if let Some((_, current)) = LEGACY_KEYS.iter().find(|(legacy, _)| *legacy == key) {
+        return Some(current.to_string());
+    }
+    config_keys().contains(&key.as_str()).then_some(key)
+}
+
+// 「項目名_FILE」の場合は項目名を返す関数
+fn file_key(key: &str) -> Option<String> {
+    key.to_lowercase()
+        .strip_suffix(FILE_SUFFIX)
+        .and_then(normalize_key)
+}
+
+// マウントした秘密情報のファイルを読み込む関数（末尾の改行は除く）
+fn read_secret_file(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map(|value| value.trim_end_matches(['\r', '\n']).to_string())
+        .map_err(|err| format!("ファイルの読み込みエラー（{}）: {}", path, err))
+}
+
+// 設定の層の項目名を変換する関数（不明な項目は除き、「項目名_FILE」はファイルの内容を値にする）
+// 旧名と現在の名前の両方がある場合は現在の名前を優先し、値とファイルの両方がある場合はエラー
+fn normalize_layer(
+    values: impl IntoIterator<Item = (String, String)>,
+    errors: &mut Vec<String>,
+) -> BTreeMap<String, String> {
+    let mut layer: BTreeMap<String, (u8, String)> = BTreeMap::new();
+    for (key, value) in values {
+        let name = key.to_lowercase();
+        let (priority, key, value) = if let Some(key) = normalize_key(&name) {
+            (u8::from(key == name), key, value)
+        } else if let Some(key) = file_key(&name) {
+            match read_secret_file(&value) {
+                Ok(value) => (2, key, value),
+                Err(err) => {
+                    errors.push(format!("{}: {}", name.to_uppercase(), err));
+                    continue;
+                }
+            }
+        } else {
+            continue;
+        };
+
+        match layer.get(&key) {
+            Some((current, _)) if (*current == 2) != (priority == 2) => {
+                errors.push(format!(
+                    "{}: {}と両方は指定できません",
+                    key.to_uppercase(),
+                    (key.clone() + FILE_SUFFIX).to_uppercase()
+                ));
+            }
+            Some((current, _)) if *current > priority => {}
+            _ => {
+                layer.insert(key, (priority, value));
+            }
+        }
+    }
+    layer
+        .into_iter()
+        .map(|(key, (_, value))| (key, value))
+        .collect()
+}
+
+// 設定ファイルの値を「項目名 = 文字列」に変換する関数（テーブルは「テーブル名_項目名」にする）
+fn flatten_toml(
+    prefix: &str,
+    table: &toml::Table,
+    values: &mut Vec<(String, String)>,
+    errors: &mut Vec<String>,
+) {
+    for (key, value) in table.iter() {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}_{}", prefix, key)
+        };
+        let value = match value {
+            toml::Value::Table(table) => {
+                flatten_toml(&key, table, values, errors);
+                continue;
+            }
+            toml::Value::String(value) => value.clone(),
+            toml::Value::Array(items) => items
+                .iter()
+                .map(|item| match item {
+                    toml::Value::String(item) => item.clone(),
+                    item => item.to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(","),
+            value => value.to_string(),
+        };
+        if normalize_key(&key).is_none() && file_key(&key).is_none() {
+            errors.push(format!("不明な項目です: {}", key));
+            continue;
+        }
+        values.push((key, value));
+    }
+}
+
+// 設定ファイルを読み込む関数（存在しない場合は空）
+fn read_config_file(path: &Path, errors: &mut Vec<String>) -> Vec<(String, String)> {
+    let mut values = Vec::new();
+    if !path.exists() {
+        return values;
+    }
+    let table = std::fs::read_to_string(path)
+        .map_err(|err| err.to_string())
+        .and_then(|text| text.parse::<toml::Table>().map_err(|err| err.to_string()));
+    match table {
+        Ok(table) => {
+            let mut file_errors = Vec::new();
+            flatten_toml("", &table, &mut values, &mut file_errors);
+            errors.extend(
+                file_errors
+                    .into_iter()
+                    .map(|err| format!("{}: {}", path.display(), err)),
+            );
+        }
+        Err(err) => errors.push(format!(
+            "{}: 設定ファイルの読み込みエラー: {}",
+            path.display(),
+            err
+        )),
+    }
+    values
+}
+
+// 設定を読み込む関数
+pub fn load(
+    env_vars: impl IntoIterator<Item = (String, String)>,
+    overrides: &[(String, String)],
+) -> Result<Config, ConfigError> {
+    load_from_dir(Path::new(CONFIG_DIR), env_vars, overrides)
+}
+
+// 設定を読み込む関数（デフォルト値 < 設定ファイル < 環境変数 < コマンドライン引数）
+pub fn load_from_dir(
+    dir: &Path,
+    env_vars: impl IntoIterator<Item = (String, String)>,
+    overrides: &[(String, String)],
+) -> Result<Config, ConfigError> {
+    let mut errors = Vec::new();
+    let env_layer = normalize_layer(env_vars, &mut errors);
+    let cli_layer = normalize_layer(overrides.iter().cloned(), &mut errors);
+
+    // 実行環境ごとの設定ファイル
+    let env_name = cli_layer
+        .get("env")
+        .or_else(|| env_layer.get("env"))
+        .map(|env| env.to_lowercase())
+        .unwrap_or_else(|| "local".to_string());
+    let file_values = read_config_file(&dir.join(format!("{}.toml", env_name)), &mut errors);
+    let file_layer = normalize_layer(file_values, &mut errors);
+
+    let mut values = file_layer;
+    values.extend(env_layer);
+    values.extend(cli_layer);
+
+    // 項目ごとに変換してエラーをまとめる（変換できない項目はデフォルト値で補って検証を続ける）
+    let mut invalid_keys = Vec::new();
+    values.retain(|key, value| {
+        let name = key.to_uppercase();
+        match envy::from_iter::<_, Config>([(name.clone(), value.clone())]) {
+            Ok(_) => true,
+            Err(err) => {
+                errors.push(format!("{}: {}", name, err));
+                invalid_keys.push(name);
+                false
+            }
+        }
+    });
+
+    let config = envy::from_iter::<_, Config>(
+        values
+            .into_iter()
+            .map(|(key, value)| (key.to_uppercase(), value)),
+    )
+    .map_err(|err| ConfigError(vec![err.to_string()]))?;
+
+    // 変換できなかった項目の検証エラーは重複するため除外する
+    errors.extend(validate(&config).into_iter().filter(|err| {
+        !invalid_keys
+            .iter()
+            .any(|name| err.starts_with(&format!("{}:", name)))
+    }));
+    if errors.is_empty() {
+        Ok(config)
+    } else {
+        Err(ConfigError(errors))
+    }
+}
+
+// 有効な設定をTOML形式で返す関数（秘密情報はマスクする）
+pub fn render_config(config: &Config) -> String {
+    toml::to_string_pretty(config).unwrap_or_else(|err| format!("# 設定の出力エラー: {}", err))
+}
+
+// 構造体の項目名を取得するためのDeserializer（serdeから渡される項目名の一覧を保持する）
+struct FieldNamesProbe(Option<&'static [&'static str]>);
+
+impl<'de> de::Deserializer<'de> for &mut FieldNamesProbe {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("項目名の取得用"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0 = Some(fields);
+        Err(de::Error::custom("項目名の取得用"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
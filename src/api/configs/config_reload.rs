@@ -0,0 +1,361 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+// 標準ライブラリ
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, SystemTime};
+
+// configsモジュール
+use super::config::{Config, get_config};
+use super::config_loader::{self, CONFIG_DIR, ConfigError};
+
+// ログ用のモジュール
+use crate::api::loggers::logger::reload_log_filter;
+
+// シャットダウン用のモジュール
+use crate::api::lifecycle::shutdown::ShutdownListener;
+
+// 再起動せずに反映できる設定の項目（それ以外の項目の変更は再起動が必要）
+pub const RELOADABLE_KEYS: [&str; 22] = [
+    "cors_allow_origins",
+    "cors_public_allow_origins",
+    "cors_auth_allow_origins",
+    "cors_admin_allow_origins",
+    "rust_log",
+    "ws_rate_limit_per_sec",
+    "ws_rate_limit_burst",
+    "body_log_enabled",
+    "body_log_routes",
+    "rate_limit_enabled",
+    "rate_limit_create_user",
+    "rate_limit_auth",
+    "rate_limit_admin",
+    "request_timeout",
+    "request_timeout_routes",
+    "ip_filter_public_allow",
+    "ip_filter_public_deny",
+    "ip_filter_auth_allow",
+    "ip_filter_auth_deny",
+    "ip_filter_admin_allow",
+    "ip_filter_admin_deny",
+    "feature_flags",
+];
+
+// 再読み込みできる項目を新しい設定の値に置き換える関数
+fn apply_reloadable(current: &Config, loaded: &Config) -> Config {
+    let mut config = current.clone();
+    config.cors_allow_origins = loaded.cors_allow_origins.clone();
+    config.cors_public_allow_origins = loaded.cors_public_allow_origins.clone();
+    config.cors_auth_allow_origins = loaded.cors_auth_allow_origins.clone();
+    config.cors_admin_allow_origins = loaded.cors_admin_allow_origins.clone();
+    config.rust_log = loaded.rust_log.clone();
+    config.ws_rate_limit_per_sec = loaded.ws_rate_limit_per_sec;
+    config.ws_rate_limit_burst = loaded.ws_rate_limit_burst;
+    config.body_log_enabled = loaded.body_log_enabled;
+    config.body_log_routes = loaded.body_log_routes.clone();
+    config.rate_limit_enabled = loaded.rate_limit_enabled;
+    config.rate_limit_create_user = loaded.rate_limit_create_user.clone();
+    config.rate_limit_auth = loaded.rate_limit_auth.clone();
+    config.rate_limit_admin = loaded.rate_limit_admin.clone();
+    config.request_timeout = loaded.request_timeout;
+    config.request_timeout_routes = loaded.request_timeout_routes.clone();
+    config.ip_filter_public_allow = loaded.ip_filter_public_allow.clone();
+    config.ip_filter_public_deny = loaded.ip_filter_public_deny.clone();
+    config.ip_filter_auth_allow = loaded.ip_filter_auth_allow.clone();
+    config.ip_filter_auth_deny = loaded.ip_filter_auth_deny.clone();
+    config.ip_filter_admin_allow = loaded.ip_filter_admin_allow.clone();
+    config.ip_filter_admin_deny = loaded.ip_filter_admin_deny.clone();
+    config.feature_flags = loaded.feature_flags.clone();
+    config
+}
+
+// 設定の変更内容（秘密情報はマスクした値）
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigChange {
+    pub key: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+impl std::fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} -> {}", self.key, self.old, self.new)
+    }
+}
+
+// 設定を項目ごとに比較する関数（項目名の順に返す）
+pub fn diff_config(old: &Config, new: &Config) -> Vec<ConfigChange> {
+    let (Ok(Value::Object(old)), Ok(Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return Vec::new();
+    };
+    let mut changes: Vec<ConfigChange> = new
+        .iter()
+        .filter(|(key, value)| old.get(*key) != Some(value))
+        .map(|(key, value)| ConfigChange {
+            key: key.clone(),
+            old: old.get(key).cloned().unwrap_or(Value::Null),
+            new: value.clone(),
+        })
+        .collect();
+    changes.sort_by(|a, b| a.key.cmp(&b.key));
+    changes
+}
+
+// 変更内容をログ出力用の文字列にする関数
+pub fn format_changes(changes: &[ConfigChange]) -> String {
+    changes
+        .iter()
+        .map(|change| change.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// 有効な設定（再読み込みで反映するたびに版を更新する）
+#[derive(Debug)]
+pub struct ActiveConfig {
+    pub version: u64,
+    pub loaded_at: DateTime<Utc>,
+    pub config: Arc<Config>,
+}
+
+impl ActiveConfig {
+    // 再読み込みできる項目の値（秘密情報はマスクした値）
+    pub fn reloadable_values(&self) -> serde_json::Map<String, Value> {
+        let Ok(Value::Object(values)) = serde_json::to_value(&*self.config) else {
+            return serde_json::Map::new();
+        };
+        values
+            .into_iter()
+            .filter(|(key, _)| RELOADABLE_KEYS.contains(&key.as_str()))
+            .collect()
+    }
+}
+
+// 再読み込みの結果
+#[derive(Debug)]
+pub struct ReloadOutcome {
+    pub version: u64,
+    // 反映した変更
+    pub applied: Vec<ConfigChange>,
+    // 反映していない変更（再起動が必要）
+    pub restart_required: Vec<ConfigChange>,
+}
+
+// 有効な設定の保持（再読み込み時は新しい設定に丸ごと置き換える）
+pub struct ConfigStore {
+    current: RwLock<Arc<ActiveConfig>>,
+}
+
+impl ConfigStore {
+    pub fn new(config: Config) -> Self {
+        ConfigStore {
+            current: RwLock::new(Arc::new(ActiveConfig {
+                version: 1,
+                loaded_at: Utc::now(),
+                config: Arc::new(config),
+            })),
+        }
+    }
+
+    pub fn current(&self) -> Arc<ActiveConfig> {
+        self.current.read().unwrap().clone()
+    }
+
+    // 検証済みの設定から再読み込みできる項目を反映する（変更が無い場合は版を更新しない）
+    pub fn apply(&self, loaded: &Config) -> ReloadOutcome {
+        let mut current = self.current.write().unwrap();
+        let (applied, restart_required): (Vec<ConfigChange>, Vec<ConfigChange>) =
+            diff_config(&current.config, loaded)
+                .into_iter()
+                .partition(|change| RELOADABLE_KEYS.contains(&change.key.as_str()));
+
+        if !applied.is_empty() {
+            *current = Arc::new(ActiveConfig {
+                version: current.version + 1,
+                loaded_at: Utc::now(),
+                config: Arc::new(apply_reloadable(&current.config, loaded)),
+            });
+        }
+
+        ReloadOutcome {
+            version: current.version,
+            applied,
+            restart_required,
+        }
+    }
+}
+
+// 有効な設定のインスタンス（起動時の設定から作成し、プロセス内で共有）
+static CONFIG_STORE: OnceLock<ConfigStore> = OnceLock::new();
+
+// 有効な設定を保持するインスタンスを取得する関数
+pub fn config_store() -> &'static ConfigStore {
+    CONFIG_STORE.get_or_init(|| ConfigStore::new(get_config().clone()))
+}
+
+// 有効な設定を取得する関数（再読み込みできる項目はこちらから参照する）
+pub fn active_config() -> Arc<ActiveConfig> {
+    config_store().current()
+}
+
+// 設定から作成する値のキャッシュ（設定の版が変わった場合のみ作り直す）
+pub struct ReloadableSetting<T> {
+    build: fn(&Config) -> T,
+    cache: RwLock<Option<(u64, Arc<T>)>>,
+}
+
+impl<T> ReloadableSetting<T> {
+    pub const fn new(build: fn(&Config) -> T) -> Self {
+        ReloadableSetting {
+            build,
+            cache: RwLock::new(None),
+        }
+    }
+
+    pub fn get(&self) -> Arc<T> {
+        self.get_from(config_store())
+    }
+
+    pub fn get_from(&self, store: &ConfigStore) -> Arc<T> {
+        let active = store.current();
+        if let Some((version, setting)) = &*self.cache.read().unwrap()
+            && *version == active.version
+        {
+            return setting.clone();
+        }
+        let setting = Arc::new((self.build)(&active.config));
+        *self.cache.write().unwrap() = Some((active.version, setting.clone()));
+        setting
+    }
+}
+
+// SIGHUPの受信用
+#[cfg(unix)]
+type HangupSignal = tokio::signal::unix::Signal;
+#[cfg(not(unix))]
+type HangupSignal = ();
+
+fn hangup_signal() -> Option<HangupSignal> {
+    #[cfg(unix)]
+    match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+        Ok(signal) => Some(signal),
+        Err(err) => {
+            tracing::error!("SIGHUPの受信設定エラー: {}", err);
+            None
+        }
+    }
+    #[cfg(not(unix))]
+    None
+}
+
+// SIGHUPを受信するまで待つ関数（受信できない環境では待ち続ける）
+async fn recv_hangup(signal: &mut Option<HangupSignal>) {
+    match signal {
+        #[cfg(unix)]
+        Some(signal) => {
+            signal.recv().await;
+        }
+        _ => std::future::pending::<()>().await,
+    }
+}
+
+// ファイルの更新日時（存在しない場合はNone）
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+// 設定ファイルの変更またはSIGHUPの受信時に設定を再読み込みする構造体
+pub struct ConfigReloader {
+    dir: PathBuf,
+    overrides: Vec<(String, String)>,
+}
+
+impl ConfigReloader {
+    // 起動時と同じコマンドライン引数で読み込む
+    pub fn new(overrides: Vec<(String, String)>) -> Self {
+        ConfigReloader {
+            dir: PathBuf::from(CONFIG_DIR),
+            overrides,
+        }
+    }
+
+    // 設定を読み込んで検証し、問題が無い場合のみ反映する
+    pub fn reload(&self) -> Result<ReloadOutcome, ConfigError> {
+        let loaded = config_loader::load_from_dir(&self.dir, std::env::vars(), &self.overrides)?;
+        let outcome = config_store().apply(&loaded);
+
+        // ログレベルは変更時にフィルターを置き換える
+        if outcome
+            .applied
+            .iter()
+            .any(|change| change.key == "rust_log")
+            && let Err(err) = reload_log_filter(&loaded.rust_log)
+        {
+            tracing::error!("ログレベルの変更エラー: {}", err);
+        }
+        Ok(outcome)
+    }
+
+    fn reload_and_log(&self, trigger: &str) {
+        match self.reload() {
+            Ok(outcome) => {
+                if outcome.applied.is_empty() {
+                    tracing::info!(
+                        trigger = trigger,
+                        version = outcome.version,
+                        "設定の再読み込み: 反映する変更はありません"
+                    );
+                } else {
+                    tracing::info!(
+                        trigger = trigger,
+                        version = outcome.version,
+                        changes = %format_changes(&outcome.applied),
+                        "設定を再読み込みしました"
+                    );
+                }
+                if !outcome.restart_required.is_empty() {
+                    tracing::warn!(
+                        trigger = trigger,
+                        changes = %format_changes(&outcome.restart_required),
+                        "再起動するまで反映されない設定の変更があります"
+                    );
+                }
+            }
+            // 不正な設定の場合は反映せずに現在の設定を使い続ける
+            Err(err) => tracing::error!(trigger = trigger, "設定の再読み込みエラー: {}", err),
+        }
+    }
+
+    // 設定ファイルの更新日時を定期的に確認し、SIGHUPの受信時は常に再読み込みする
+    pub async fn run(self, mut shutdown: ShutdownListener) {
+        let config = get_config();
+        let path = self.dir.join(format!("{}.toml", config.env));
+        let mut last_modified = modified_time(&path);
+        let mut hangup = hangup_signal();
+
+        // 確認の間隔が0の場合は設定ファイルを監視しない
+        let watch = !config.config_reload_interval.is_zero();
+        let mut ticker =
+            tokio::time::interval(config.config_reload_interval.max(Duration::from_millis(1)));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = shutdown.wait() => break,
+                _ = recv_hangup(&mut hangup) => self.reload_and_log("SIGHUP"),
+                _ = ticker.tick(), if watch => {
+                    let modified = modified_time(&path);
+                    if modified != last_modified {
+                        last_modified = modified;
+                        self.reload_and_log("file");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+// 設定の再読み込みのテスト
+mod config_reload_test {
+    use crate::api::configs::config::{self, Config};
+    use crate::api::configs::config_reload::{
+        ConfigStore, ReloadableSetting, diff_config, format_changes,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn base_config() -> Config {
+        let mut config = config::get_config().clone();
+        config.cors_allow_origins = "http://localhost:3000".to_string();
+        config.rust_log = "info".to_string();
+        config
+    }
+
+    #[test]
+    fn test_diff_config() {
+        let old = base_config();
+        let mut new = old.clone();
+        new.rust_log = "debug".to_string();
+        new.port = 8081;
+
+        // 変更された項目を項目名の順に返す
+        let changes = diff_config(&old, &new);
+        assert_eq!(
+            format_changes(&changes),
+            format!(
+                "port: {} -> 8081, rust_log: \"info\" -> \"debug\"",
+                old.port
+            )
+        );
+        assert!(diff_config(&old, &old.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_apply_reloadable_changes() {
+        let store = ConfigStore::new(base_config());
+        let before = store.current();
+        assert_eq!(before.version, 1);
+
+        let mut loaded = base_config();
+        loaded.cors_allow_origins = "https://app.example.com".to_string();
+        loaded.ws_rate_limit_burst = 5;
+        loaded.webhook_timeout = Duration::from_secs(99);
+
+        // 再読み込みできる項目のみ反映し、それ以外は再起動が必要な変更として返す
+        let outcome = store.apply(&loaded);
+        assert_eq!(outcome.version, 2);
+        let applied: Vec<&str> = outcome.applied.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(applied, vec!["cors_allow_origins", "ws_rate_limit_burst"]);
+        let restart: Vec<&str> = outcome
+            .restart_required
+            .iter()
+            .map(|c| c.key.as_str())
+            .collect();
+        assert_eq!(restart, vec!["webhook_timeout"]);
+
+        let after = store.current();
+        assert_eq!(after.version, 2);
+        assert_eq!(after.config.cors_allow_origins, "https://app.example.com");
+        assert_eq!(after.config.ws_rate_limit_burst, 5);
+        assert_eq!(after.config.webhook_timeout, before.config.webhook_timeout);
+        assert_eq!(
+            after.reloadable_values()["cors_allow_origins"],
+            "https://app.example.com"
+        );
+
+        // 取得済みの設定は置き換えの影響を受けない
+        assert_eq!(before.config.cors_allow_origins, "http://localhost:3000");
+
+        // 反映する変更が無い場合は版を更新しない
+        let outcome = store.apply(&loaded);
+        assert_eq!(outcome.version, 2);
+        assert!(outcome.applied.is_empty());
+    }
+
+    static BUILD_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    fn build_origin(config: &Config) -> String {
+        BUILD_COUNT.fetch_add(1, Ordering::SeqCst);
+        config.cors_allow_origins.clone()
+    }
+
+    #[test]
+    fn test_reloadable_setting_rebuilds_on_new_version() {
+        let store = ConfigStore::new(base_config());
+        let setting = ReloadableSetting::new(build_origin);
+
+        // 同じ版の間はキャッシュを返す
+        assert_eq!(*setting.get_from(&store), "http://localhost:3000");
+        assert_eq!(*setting.get_from(&store), "http://localhost:3000");
+        assert_eq!(BUILD_COUNT.load(Ordering::SeqCst), 1);
+
+        let mut loaded = base_config();
+        loaded.cors_allow_origins = "https://app.example.com".to_string();
+        store.apply(&loaded);
+        assert_eq!(*setting.get_from(&store), "https://app.example.com");
+        assert_eq!(BUILD_COUNT.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,403 @@
+// axum
+use axum::http::HeaderName;
+
+// ログレベルの形式チェック用
+use tracing_subscriber::EnvFilter;
+
+// IPアドレスの範囲
+use ipnet::IpNet;
+
+// 標準ライブラリ
+use std::net::IpAddr;
+use std::time::Duration;
+
+// configsモジュール
+use super::config::{Config, OutboxPublisher};
+
+// DB接続用のモジュール
+use crate::api::databases::database::pg_connect_options;
+
+// ログ用のモジュール
+use crate::api::loggers::logger::{parse_precision, parse_timezone};
+use crate::api::loggers::redaction::{PATTERN_EMAIL, PATTERN_PHONE, PATTERN_TOKEN};
+
+// CORS用のモジュール
+use crate::api::middleware::cors_middleware::{
+    CorsGroup, parse_allow_headers, parse_expose_headers, parse_methods, parse_origins,
+};
+
+// 圧縮用のモジュール
+use crate::api::middleware::compression_middleware::validate_algorithms;
+
+// IPアドレスの制限用のモジュール
+use crate::api::middleware::ip_filter_middleware::{IpFilterGroup, parse_cidrs};
+
+// セキュリティ関連のレスポンスヘッダー用のモジュール
+use crate::api::middleware::security_headers_middleware::SecurityHeaders;
+
+// リクエストの制限用のモジュール
+use crate::api::middleware::request_limit_middleware::parse_timeout_routes;
+
+// レート制限用のモジュール
+use crate::api::middleware::rate_limit_middleware::{RATE_LIMIT_POLICIES, quota_setting};
+use crate::api::middleware::rate_limit_store::Quota;
+
+// 認証用のモジュール
+use crate::api::contexts::principal::parse_auth_tokens;
+
+// 機能フラグ用のモジュール
+use super::feature_flags::is_valid_feature_name;
+
+// OpenTelemetry用のモジュール
+use crate::api::telemetry::otel::{OTLP_PROTOCOL_GRPC, OTLP_PROTOCOL_HTTP, build_sampler};
+
+// 設定値の候補
+const REDACT_PATTERNS: [&str; 3] = [PATTERN_EMAIL, PATTERN_PHONE, PATTERN_TOKEN];
+const OTLP_PROTOCOLS: [&str; 2] = [OTLP_PROTOCOL_GRPC, OTLP_PROTOCOL_HTTP];
+
+// 設定値を検証する関数（全ての項目のエラーを返す）
+pub fn validate(config: &Config) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // DBの接続先
+    if let Err(err) = pg_connect_options(config) {
+        errors.push(err);
+    }
+
+    // ポート
+    if config.port == 0 {
+        errors.push("PORT: 1以上を指定してください".to_string());
+    }
+    if config.metrics_enabled && config.metrics_port == config.port {
+        errors.push(format!(
+            "METRICS_PORT: PORTと異なるポートを指定してください: {}",
+            config.metrics_port
+        ));
+    }
+
+    // リクエストの制限
+    if let Err(err) = parse_timeout_routes(&config.request_timeout_routes) {
+        errors.push(format!("REQUEST_TIMEOUT_ROUTES: {}", err));
+    }
+    check_positive(
+        &mut errors,
+        "REQUEST_BODY_LIMIT",
+        config.request_body_limit as i64,
+    );
+    check_positive(
+        &mut errors,
+        "MAX_IN_FLIGHT_REQUESTS",
+        config.max_in_flight_requests as i64,
+    );
+
+    // 圧縮
+    if let Err(err) = validate_algorithms(&config.compression_algorithms) {
+        errors.push(format!("COMPRESSION_ALGORITHMS: {}", err));
+    }
+
+    // セキュリティ関連のレスポンスヘッダー
+    if let Err(errs) = SecurityHeaders::from_config(config) {
+        errors.extend(errs);
+    }
+
+    // ログ
+    if let Err(err) = EnvFilter::try_new(&config.rust_log) {
+        errors.push(format!("RUST_LOG: {}", err));
+    }
+    if let Err(err) = parse_timezone(&config.log_timezone) {
+        errors.push(format!("LOG_TIMEZONE: {}", err));
+    }
+    if let Err(err) = parse_precision(&config.log_timestamp_precision) {
+        errors.push(format!("LOG_TIMESTAMP_PRECISION: {}", err));
+    }
+    for name in split_list(&config.log_redact_patterns) {
+        check_one_of(&mut errors, "LOG_REDACT_PATTERNS", &name, &REDACT_PATTERNS);
+    }
+
+    // CORS（グループごとの上書きは指定された場合のみ）
+    let credentials = config.cors_allow_credentials;
+    check_cors_list(
+        &mut errors,
+        "CORS_ALLOW_ORIGINS",
+        &config.cors_allow_origins,
+        credentials,
+        |v| parse_origins(v).map(|_| ()),
+    );
+    check_cors_list(
+        &mut errors,
+        "CORS_ALLOW_METHODS",
+        &config.cors_allow_methods,
+        credentials,
+        |v| parse_methods(v).map(|_| ()),
+    );
+    for group in CorsGroup::ALL {
+        let prefix = format!("CORS_{}", group.name().to_uppercase());
+        if let Some(origins) = group.origins_override(config) {
+            check_cors_list(
+                &mut errors,
+                &format!("{}_ALLOW_ORIGINS", prefix),
+                origins,
+                credentials,
+                |v| parse_origins(v).map(|_| ()),
+            );
+        }
+        if let Some(methods) = group.methods_override(config) {
+            check_cors_list(
+                &mut errors,
+                &format!("{}_ALLOW_METHODS", prefix),
+                methods,
+                credentials,
+                |v| parse_methods(v).map(|_| ()),
+            );
+        }
+    }
+    check_cors_list(
+        &mut errors,
+        "CORS_ALLOW_HEADERS",
+        &config.cors_allow_headers,
+        credentials,
+        |v| parse_allow_headers(v).map(|_| ()),
+    );
+    check_cors_list(
+        &mut errors,
+        "CORS_EXPOSE_HEADERS",
+        &config.cors_expose_headers,
+        credentials,
+        |v| parse_expose_headers(v).map(|_| ()),
+    );
+
+    // アウトボックス
+    if config.outbox_publisher.contains(&OutboxPublisher::Http)
+        && config.outbox_webhook_url.is_none()
+    {
+        errors.push("OUTBOX_WEBHOOK_URL: OUTBOX_PUBLISHERがhttpの場合は必須です".to_string());
+    }
+    check_interval(
+        &mut errors,
+        "OUTBOX_POLL_INTERVAL",
+        config.outbox_poll_interval,
+    );
+    check_positive(
+        &mut errors,
+        "OUTBOX_BATCH_SIZE",
+        config.outbox_batch_size as i64,
+    );
+    check_positive(
+        &mut errors,
+        "OUTBOX_MAX_ATTEMPTS",
+        config.outbox_max_attempts as i64,
+    );
+    check_backoff(
+        &mut errors,
+        "OUTBOX_BACKOFF",
+        config.outbox_backoff_base,
+        config.outbox_backoff_max,
+    );
+    if config.outbox_publish_timeout.is_zero() {
+        errors.push("OUTBOX_PUBLISH_TIMEOUT: 0より大きい時間を指定してください".to_string());
+    }
+
+    // Webhook
+    check_interval(
+        &mut errors,
+        "WEBHOOK_POLL_INTERVAL",
+        config.webhook_poll_interval,
+    );
+    check_positive(
+        &mut errors,
+        "WEBHOOK_BATCH_SIZE",
+        config.webhook_batch_size as i64,
+    );
+    check_positive(
+        &mut errors,
+        "WEBHOOK_MAX_ATTEMPTS",
+        config.webhook_max_attempts as i64,
+    );
+    check_backoff(
+        &mut errors,
+        "WEBHOOK_BACKOFF",
+        config.webhook_backoff_base,
+        config.webhook_backoff_max,
+    );
+    if config.webhook_timeout.is_zero() {
+        errors.push("WEBHOOK_TIMEOUT: 0より大きい時間を指定してください".to_string());
+    }
+
+    // SSE・WebSocket
+    check_positive(
+        &mut errors,
+        "USER_EVENTS_CHANNEL_CAPACITY",
+        config.user_events_channel_capacity as i64,
+    );
+    check_interval(
+        &mut errors,
+        "USER_EVENTS_HEARTBEAT_INTERVAL",
+        config.user_events_heartbeat_interval,
+    );
+    check_interval(&mut errors, "WS_PING_INTERVAL", config.ws_ping_interval);
+    if config.ws_ping_interval >= config.ws_idle_timeout {
+        errors.push(format!(
+            "WS_PING_INTERVAL: WS_IDLE_TIMEOUTより短い時間を指定してください: {}",
+            humantime::format_duration(config.ws_ping_interval)
+        ));
+    }
+    check_positive(
+        &mut errors,
+        "WS_MAX_MESSAGE_BYTES",
+        config.ws_max_message_bytes as i64,
+    );
+    check_positive(
+        &mut errors,
+        "WS_RATE_LIMIT_BURST",
+        config.ws_rate_limit_burst as i64,
+    );
+
+    // 認証
+    if let Some(tokens) = &config.auth_tokens
+        && let Err(err) = parse_auth_tokens(tokens.expose())
+    {
+        errors.push(format!("AUTH_TOKENS: {}", err));
+    }
+
+    // レート制限
+    for name in RATE_LIMIT_POLICIES {
+        if let Some(value) = quota_setting(config, name)
+            && let Err(err) = Quota::parse(value)
+        {
+            errors.push(format!("RATE_LIMIT_{}: {}", name.to_uppercase(), err));
+        }
+    }
+
+    // リクエストID
+    if HeaderName::from_bytes(config.request_id_header.as_bytes()).is_err() {
+        errors.push(format!(
+            "REQUEST_ID_HEADER: 不正なヘッダー名です: {}",
+            config.request_id_header
+        ));
+    }
+    check_positive(
+        &mut errors,
+        "REQUEST_ID_MAX_LENGTH",
+        config.request_id_max_length as i64,
+    );
+
+    // 信頼するプロキシ、PROXYプロトコル
+    check_cidr_list(
+        &mut errors,
+        "PROXY_TRUSTED_CIDRS",
+        &config.proxy_trusted_cidrs,
+    );
+    if config.proxy_protocol_enabled && config.proxy_protocol_timeout.is_zero() {
+        errors.push("PROXY_PROTOCOL_TIMEOUT: 0より大きい時間を指定してください".to_string());
+    }
+
+    // IPアドレスの制限（*は指定できない）
+    for group in IpFilterGroup::ALL {
+        for (kind, value) in [("ALLOW", group.allow(config)), ("DENY", group.deny(config))] {
+            if let Err(err) = parse_cidrs(value.unwrap_or_default()) {
+                errors.push(format!(
+                    "IP_FILTER_{}_{}: {}",
+                    group.name().to_uppercase(),
+                    kind,
+                    err
+                ));
+            }
+        }
+    }
+
+    // 機能フラグ
+    for name in config.feature_flags.iter() {
+        if !is_valid_feature_name(name) {
+            errors.push(format!("FEATURE_FLAGS: 不正な機能名です: {}", name));
+        }
+    }
+
+    // OpenTelemetry
+    check_one_of(
+        &mut errors,
+        "OTEL_EXPORTER_OTLP_PROTOCOL",
+        &config.otel_exporter_otlp_protocol,
+        &OTLP_PROTOCOLS,
+    );
+    if let Err(err) = build_sampler(&config.otel_traces_sampler, config.otel_traces_sampler_arg) {
+        errors.push(format!("OTEL_TRACES_SAMPLER: {}", err));
+    }
+
+    errors
+}
+
+// カンマ区切りの設定値を小文字のリストに変換する関数
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+fn check_one_of(errors: &mut Vec<String>, name: &str, value: &str, candidates: &[&str]) {
+    if !candidates.contains(&value) {
+        errors.push(format!(
+            "{}: {} のいずれかを指定してください: {}",
+            name,
+            candidates.join(" | "),
+            value
+        ));
+    }
+}
+
+// CORSの設定値をチェックする関数（認証情報を許可する場合は*を指定できない）
+fn check_cors_list(
+    errors: &mut Vec<String>,
+    name: &str,
+    value: &str,
+    credentials: bool,
+    parse: impl Fn(&str) -> Result<(), String>,
+) {
+    if credentials && split_list(value).iter().any(|v| v == "*") {
+        errors.push(format!(
+            "{}: CORS_ALLOW_CREDENTIALSがtrueの場合は*を指定できません",
+            name
+        ));
+        return;
+    }
+    if let Err(err) = parse(value) {
+        errors.push(format!("{}: {}", name, err));
+    }
+}
+
+// CIDRまたはIPアドレスのカンマ区切りの設定値をチェックする関数（*は全て）
+fn check_cidr_list(errors: &mut Vec<String>, name: &str, value: &str) {
+    for item in split_list(value) {
+        if item != "*" && item.parse::<IpNet>().is_err() && item.parse::<IpAddr>().is_err() {
+            errors.push(format!(
+                "{}: 不正なCIDRまたはIPアドレスです: {}",
+                name, item
+            ));
+        }
+    }
+}
+
+fn check_positive(errors: &mut Vec<String>, name: &str, value: i64) {
+    if value <= 0 {
+        errors.push(format!("{}: 1以上を指定してください: {}", name, value));
+    }
+}
+
+// 一定間隔で実行する処理の間隔をチェックする関数（0の場合は実行できないためエラー）
+fn check_interval(errors: &mut Vec<String>, name: &str, value: Duration) {
+    if value.is_zero() {
+        errors.push(format!("{}: 0より大きい時間を指定してください", name));
+    }
+}
+
+fn check_backoff(errors: &mut Vec<String>, name: &str, base: Duration, max: Duration) {
+    if base > max {
+        errors.push(format!(
+            "{}_BASE: {}_MAX以下の時間を指定してください: {}",
+            name,
+            name,
+            humantime::format_duration(base)
+        ));
+    }
+}
@@ -0,0 +1,49 @@
+// 標準ライブラリ
+use std::collections::HashSet;
+use std::sync::Arc;
+
+// configsモジュール
+use super::config::Config;
+use super::config_reload::ReloadableSetting;
+
+// 機能名が正しい形式（英小文字・数字・「_」）か判定する関数
+pub fn is_valid_feature_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+// 機能フラグの設定
+#[derive(Clone, Debug, Default)]
+pub struct FeatureFlags {
+    enabled: HashSet<String>,
+}
+
+impl FeatureFlags {
+    pub fn from_config(config: &Config) -> Self {
+        FeatureFlags {
+            enabled: config.feature_flags.iter().cloned().collect(),
+        }
+    }
+
+    // 機能が有効か判定する
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.enabled.contains(name)
+    }
+}
+
+// 設定のインスタンス（再読み込みで設定の版が変わった場合に作り直す）
+static FEATURE_FLAGS: ReloadableSetting<FeatureFlags> =
+    ReloadableSetting::new(FeatureFlags::from_config);
+
+// 設定を取得する関数
+pub fn feature_flags() -> Arc<FeatureFlags> {
+    FEATURE_FLAGS.get()
+}
+
+// 機能が有効か判定する関数（機能の実装側から参照する）
+#[allow(dead_code)]
+pub fn feature_enabled(name: &str) -> bool {
+    feature_flags().is_enabled(name)
+}
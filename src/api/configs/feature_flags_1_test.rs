@@ -0,0 +1,42 @@
+#[cfg(test)]
+// 機能フラグのテスト
+mod feature_flags_test {
+    use crate::api::configs::config::{self, Config};
+    use crate::api::configs::config_reload::{ConfigStore, ReloadableSetting};
+    use crate::api::configs::feature_flags::{FeatureFlags, is_valid_feature_name};
+
+    fn base_config() -> Config {
+        let mut config = config::get_config().clone();
+        config.feature_flags = vec!["new_search".to_string()];
+        config
+    }
+
+    #[test]
+    fn test_is_valid_feature_name() {
+        assert!(is_valid_feature_name("new_search"));
+        assert!(is_valid_feature_name("v2"));
+        assert!(!is_valid_feature_name(""));
+        assert!(!is_valid_feature_name("New-Search"));
+    }
+
+    #[test]
+    fn test_reload_feature_flags() {
+        let store = ConfigStore::new(base_config());
+        let setting = ReloadableSetting::new(FeatureFlags::from_config);
+        assert!(setting.get_from(&store).is_enabled("new_search"));
+        assert!(!setting.get_from(&store).is_enabled("bulk_import"));
+
+        // 再読み込みで反映し、再起動せずに切り替わる
+        let mut loaded = base_config();
+        loaded.feature_flags = vec!["bulk_import".to_string(), "audit_export".to_string()];
+        let outcome = store.apply(&loaded);
+        let applied: Vec<&str> = outcome.applied.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(applied, vec!["feature_flags"]);
+        assert!(outcome.restart_required.is_empty());
+
+        let flags = setting.get_from(&store);
+        assert!(!flags.is_enabled("new_search"));
+        assert!(flags.is_enabled("bulk_import"));
+        assert!(flags.is_enabled("audit_export"));
+    }
+}
@@ -1 +1,12 @@
 pub mod config;
+pub mod config_loader;
+pub mod config_reload;
+pub mod config_validation;
+pub mod feature_flags;
+pub mod secret;
+
+// テストコード用のモジュール
+mod config_loader_1_test;
+mod config_reload_1_test;
+mod feature_flags_1_test;
+mod secret_1_test;
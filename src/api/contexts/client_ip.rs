@@ -0,0 +1,209 @@
+// axum
+use axum::http::HeaderMap;
+
+// 標準ライブラリ
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+
+// configsモジュール
+use crate::api::configs::config::{self, Config};
+
+// 信頼するプロキシ（リクエストIDと共通）
+use super::trusted_proxies::TrustedProxies;
+
+// 接続元の情報を受け取るヘッダー
+pub const HEADER_FORWARDED: &str = "forwarded";
+pub const HEADER_X_FORWARDED_FOR: &str = "x-forwarded-for";
+pub const HEADER_X_FORWARDED_PROTO: &str = "x-forwarded-proto";
+pub const HEADER_X_REAL_IP: &str = "x-real-ip";
+
+// スキーム（このサーバーはTLSを終端しないため、プロキシから受信しない場合はhttp）
+pub const SCHEME_HTTP: &str = "http";
+pub const SCHEME_HTTPS: &str = "https";
+
+// クライアントの情報
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientInfo {
+    // クライアントのIPアドレス（接続元が不明な場合はNone）
+    pub ip: Option<IpAddr>,
+    pub scheme: String,
+}
+
+// プロキシを経由した接続の1区間（プロキシが受信した接続元とスキーム）
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForwardedHop {
+    // 接続元のIPアドレス（unknown、難読化された識別子の場合はNone）
+    pub ip: Option<IpAddr>,
+    pub proto: Option<String>,
+}
+
+// 引用符の外側の区切り文字で分割する関数
+fn split_unquoted(value: &str, separator: char) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => {
+                items.push(&value[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    items.push(&value[start..]);
+    items
+}
+
+// ノード（例：192.0.2.1、192.0.2.1:8080、"[2001:db8::1]:8080"）からIPアドレスを取得する関数
+pub fn parse_node(value: &str) -> Option<IpAddr> {
+    let value = value.trim().trim_matches('"');
+    if let Some(rest) = value.strip_prefix('[') {
+        return rest.split_once(']')?.0.parse().ok();
+    }
+    if let Ok(ip) = value.parse::<IpAddr>() {
+        return Some(ip);
+    }
+    value.parse::<SocketAddr>().ok().map(|addr| addr.ip())
+}
+
+// スキームの形式チェック（http、httpsのみ使用する）
+fn parse_proto(value: &str) -> Option<String> {
+    let proto = value.trim().trim_matches('"').to_lowercase();
+    [SCHEME_HTTP, SCHEME_HTTPS]
+        .contains(&proto.as_str())
+        .then_some(proto)
+}
+
+// Forwardedヘッダー（RFC 7239）の値を変換する関数（for、protoのみ使用する）
+pub fn parse_forwarded(value: &str) -> Vec<ForwardedHop> {
+    split_unquoted(value, ',')
+        .into_iter()
+        .filter(|element| !element.trim().is_empty())
+        .map(|element| {
+            let mut hop = ForwardedHop {
+                ip: None,
+                proto: None,
+            };
+            for pair in split_unquoted(element, ';') {
+                let Some((key, value)) = pair.split_once('=') else {
+                    continue;
+                };
+                match key.trim().to_lowercase().as_str() {
+                    "for" => hop.ip = parse_node(value),
+                    "proto" => hop.proto = parse_proto(value),
+                    _ => {}
+                }
+            }
+            hop
+        })
+        .collect()
+}
+
+// 複数行のヘッダーをカンマ区切りで結合する関数（ヘッダーが無い場合はNone）
+fn joined_header(headers: &HeaderMap, name: &str) -> Option<String> {
+    let values: Vec<&str> = headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect();
+    (!values.is_empty()).then(|| values.join(","))
+}
+
+// X-Forwarded-ForとX-Forwarded-Protoの値を変換する関数
+// スキームは区間の数が一致する場合は区間ごと、一致しない場合は最も近いプロキシが設定した値を使用する
+pub fn parse_x_forwarded(forwarded_for: &str, forwarded_proto: Option<&str>) -> Vec<ForwardedHop> {
+    let ips: Vec<Option<IpAddr>> = forwarded_for
+        .split(',')
+        .filter(|v| !v.trim().is_empty())
+        .map(parse_node)
+        .collect();
+    let protos: Vec<Option<String>> = forwarded_proto
+        .unwrap_or_default()
+        .split(',')
+        .filter(|v| !v.trim().is_empty())
+        .map(parse_proto)
+        .collect();
+    let aligned = protos.len() == ips.len();
+    let last = protos.last().cloned().flatten();
+    ips.into_iter()
+        .enumerate()
+        .map(|(i, ip)| ForwardedHop {
+            ip,
+            proto: if aligned {
+                protos[i].clone()
+            } else {
+                last.clone()
+            },
+        })
+        .collect()
+}
+
+// クライアントのIPアドレスの設定
+#[derive(Clone, Debug)]
+pub struct ClientIpSetting {
+    pub trusted_proxies: TrustedProxies,
+}
+
+impl ClientIpSetting {
+    pub fn from_config(config: &Config) -> Self {
+        ClientIpSetting {
+            trusted_proxies: TrustedProxies::from_config(config),
+        }
+    }
+
+    // プロキシから受信した区間（クライアントに近い順）をヘッダーから取得する関数
+    // Forwarded、X-Forwarded-For、X-Real-IPの順に、最初に存在するヘッダーのみ使用する
+    fn forwarded_hops(headers: &HeaderMap) -> Option<Vec<ForwardedHop>> {
+        if let Some(value) = joined_header(headers, HEADER_FORWARDED) {
+            return Some(parse_forwarded(&value));
+        }
+        let forwarded_proto = joined_header(headers, HEADER_X_FORWARDED_PROTO);
+        if let Some(value) = joined_header(headers, HEADER_X_FORWARDED_FOR) {
+            return Some(parse_x_forwarded(&value, forwarded_proto.as_deref()));
+        }
+        if let Some(value) = joined_header(headers, HEADER_X_REAL_IP) {
+            return Some(parse_x_forwarded(&value, forwarded_proto.as_deref()));
+        }
+        None
+    }
+
+    // 接続元が信頼するプロキシの場合のみヘッダーからクライアントの情報を取得する
+    // 最も近いプロキシから順に辿り、信頼するプロキシ以外の最初の接続元をクライアントとする
+    pub fn resolve(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> ClientInfo {
+        let mut client = ClientInfo {
+            ip: peer,
+            scheme: SCHEME_HTTP.to_string(),
+        };
+        if !peer.is_some_and(|ip| self.trusted_proxies.contains(ip)) {
+            return client;
+        }
+        let Some(hops) = Self::forwarded_hops(headers) else {
+            return client;
+        };
+
+        for hop in hops.into_iter().rev() {
+            if let Some(proto) = hop.proto {
+                client.scheme = proto;
+            }
+            // 接続元が不明な場合はそれ以上辿らずに直前のプロキシをクライアントとする
+            let Some(ip) = hop.ip else {
+                break;
+            };
+            client.ip = Some(ip);
+            if !self.trusted_proxies.contains(ip) {
+                break;
+            }
+        }
+        client
+    }
+}
+
+// 設定のインスタンス（プロセス内で共有）
+static CLIENT_IP_SETTING: OnceLock<ClientIpSetting> = OnceLock::new();
+
+// 設定を取得する関数
+pub fn client_ip_setting() -> &'static ClientIpSetting {
+    CLIENT_IP_SETTING.get_or_init(|| ClientIpSetting::from_config(config::get_config()))
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+// クライアントのIPアドレスのテスト
+mod client_ip_test {
+    use crate::api::contexts::client_ip::{
+        ClientIpSetting, ForwardedHop, parse_forwarded, parse_node, parse_x_forwarded,
+    };
+    use crate::api::contexts::trusted_proxies::TrustedProxies;
+    use axum::http::HeaderMap;
+    use std::net::IpAddr;
+
+    fn new_setting(trusted_proxies: &str) -> ClientIpSetting {
+        ClientIpSetting {
+            trusted_proxies: TrustedProxies::parse(trusted_proxies),
+        }
+    }
+
+    fn header_map(values: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in values {
+            headers.append(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    #[test]
+    fn test_parse_node() {
+        assert_eq!(parse_node("192.0.2.60"), ip("192.0.2.60"));
+        assert_eq!(parse_node(" 192.0.2.60:4711 "), ip("192.0.2.60"));
+        assert_eq!(
+            parse_node("\"[2001:db8:cafe::17]:4711\""),
+            ip("2001:db8:cafe::17")
+        );
+        assert_eq!(parse_node("2001:db8::1"), ip("2001:db8::1"));
+        assert_eq!(parse_node("unknown"), None);
+        assert_eq!(parse_node("_hidden"), None);
+    }
+
+    #[test]
+    fn test_parse_forwarded() {
+        let hops = parse_forwarded(
+            "for=192.0.2.60;proto=https;by=203.0.113.43, For=\"[2001:db8:cafe::17]:4711\", for=unknown",
+        );
+        assert_eq!(
+            hops,
+            vec![
+                ForwardedHop {
+                    ip: ip("192.0.2.60"),
+                    proto: Some("https".to_string()),
+                },
+                ForwardedHop {
+                    ip: ip("2001:db8:cafe::17"),
+                    proto: None,
+                },
+                ForwardedHop {
+                    ip: None,
+                    proto: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_x_forwarded() {
+        // 区間の数が一致する場合は区間ごとのスキーム
+        let hops = parse_x_forwarded("192.0.2.1, 10.0.0.1", Some("https, http"));
+        assert_eq!(hops[0].proto.as_deref(), Some("https"));
+        assert_eq!(hops[1].proto.as_deref(), Some("http"));
+
+        // 一致しない場合は最も近いプロキシが設定した値
+        let hops = parse_x_forwarded("192.0.2.1, 10.0.0.1", Some("https"));
+        assert_eq!(hops[0].ip, ip("192.0.2.1"));
+        assert_eq!(hops[0].proto.as_deref(), Some("https"));
+        assert_eq!(hops[1].proto.as_deref(), Some("https"));
+
+        // http、https以外は使用しない
+        let hops = parse_x_forwarded("192.0.2.1", Some("ftp"));
+        assert_eq!(hops[0].proto, None);
+    }
+
+    #[test]
+    fn test_resolve_untrusted_peer() {
+        // 信頼するプロキシ以外からの接続はヘッダーを使用しない
+        let setting = new_setting("10.0.0.0/8");
+        let headers = header_map(&[
+            ("X-Forwarded-For", "192.0.2.1"),
+            ("X-Forwarded-Proto", "https"),
+        ]);
+        let client = setting.resolve(&headers, ip("198.51.100.1"));
+        assert_eq!(client.ip, ip("198.51.100.1"));
+        assert_eq!(client.scheme, "http");
+
+        // 設定が無い場合も使用しない
+        let client = new_setting("").resolve(&headers, ip("10.0.0.1"));
+        assert_eq!(client.ip, ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn test_resolve_x_forwarded_for() {
+        let setting = new_setting("10.0.0.0/8");
+
+        // 信頼するプロキシを右から辿り、最初の信頼しないアドレスをクライアントとする（偽装された左側の値は使用しない）
+        let headers = header_map(&[
+            ("X-Forwarded-For", "1.1.1.1, 192.0.2.1, 10.0.0.2"),
+            ("X-Forwarded-Proto", "https"),
+        ]);
+        let client = setting.resolve(&headers, ip("10.0.0.1"));
+        assert_eq!(client.ip, ip("192.0.2.1"));
+        assert_eq!(client.scheme, "https");
+
+        // 複数行のヘッダーは結合して使用する
+        let headers = header_map(&[
+            ("X-Forwarded-For", "192.0.2.1"),
+            ("X-Forwarded-For", "10.0.0.2"),
+        ]);
+        let client = setting.resolve(&headers, ip("10.0.0.1"));
+        assert_eq!(client.ip, ip("192.0.2.1"));
+
+        // 全て信頼するプロキシの場合は最も遠いアドレス
+        let headers = header_map(&[("X-Forwarded-For", "10.0.0.3, 10.0.0.2")]);
+        let client = setting.resolve(&headers, ip("10.0.0.1"));
+        assert_eq!(client.ip, ip("10.0.0.3"));
+
+        // 不正な値の場合は直前のプロキシ
+        let headers = header_map(&[("X-Forwarded-For", "192.0.2.1, invalid")]);
+        let client = setting.resolve(&headers, ip("10.0.0.1"));
+        assert_eq!(client.ip, ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn test_resolve_header_priority() {
+        let setting = new_setting("10.0.0.0/8");
+
+        // Forwardedを優先する
+        let headers = header_map(&[
+            ("Forwarded", "for=192.0.2.60;proto=https"),
+            ("X-Forwarded-For", "192.0.2.1"),
+            ("X-Real-IP", "192.0.2.2"),
+        ]);
+        let client = setting.resolve(&headers, ip("10.0.0.1"));
+        assert_eq!(client.ip, ip("192.0.2.60"));
+        assert_eq!(client.scheme, "https");
+
+        // X-Forwarded-Forが無い場合はX-Real-IP
+        let headers = header_map(&[("X-Real-IP", "192.0.2.2")]);
+        let client = setting.resolve(&headers, ip("10.0.0.1"));
+        assert_eq!(client.ip, ip("192.0.2.2"));
+
+        // ヘッダーが無い場合は接続元
+        let client = setting.resolve(&HeaderMap::new(), ip("10.0.0.1"));
+        assert_eq!(client.ip, ip("10.0.0.1"));
+        assert_eq!(client.scheme, "http");
+    }
+}
@@ -1,12 +1,27 @@
 // axum
 use axum::{extract::Request, http::header::HeaderMap};
 
+// 標準ライブラリ
+use std::net::IpAddr;
+
+// クライアントのIPアドレス用のモジュール
+use super::client_ip::client_ip_setting;
+use super::trusted_proxies::peer_ip;
+
 // 共通コンテキストの構造体
 #[derive(Clone, Debug)]
 pub struct Context {
     pub header: HeaderMap,
     pub method: String,
     pub uri: String,
+    // 認証済みユーザーの識別子（未認証の場合はNone）
+    pub principal: Option<String>,
+    // OpenTelemetryのトレースID（無効な場合はNone）
+    pub trace_id: Option<String>,
+    // クライアントのIPアドレス（信頼するプロキシを経由した場合は転送元のアドレス）
+    pub client_ip: Option<IpAddr>,
+    // クライアントが使用したスキーム（http | https）
+    pub scheme: String,
 }
 
 // コンテキスト作成関数
@@ -16,9 +31,15 @@ pub fn create_context(req: &Request) -> Context {
         hm.insert(key.clone(), value.clone());
     }
 
+    let client = client_ip_setting().resolve(req.headers(), peer_ip(req));
+
     Context {
         header: hm,
         method: req.method().to_string(),
         uri: req.uri().to_string(),
+        principal: None,
+        trace_id: None,
+        client_ip: client.ip,
+        scheme: client.scheme,
     }
 }
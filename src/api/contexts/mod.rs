@@ -1 +1,11 @@
+pub mod client_ip;
 pub mod context;
+pub mod principal;
+pub mod request_id;
+pub mod trusted_proxies;
+
+// テストコード用のモジュール
+mod client_ip_1_test;
+mod principal_1_test;
+mod request_id_1_test;
+mod trusted_proxies_1_test;
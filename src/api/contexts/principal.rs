@@ -0,0 +1,99 @@
+// axum
+use axum::http::{HeaderMap, header};
+
+// ハッシュ値の計算用
+use sha2::{Digest, Sha256};
+
+// 標準ライブラリ
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+// configsモジュール
+use crate::api::configs::config::{self, Config};
+
+// トークンのハッシュ値（設定値のトークンをメモリ上に平文で保持しないため）
+fn token_hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+// 認証用のトークンの設定値（「識別子:トークン」のカンマ区切り）を変換する関数
+pub fn parse_auth_tokens(value: &str) -> Result<Vec<(String, String)>, String> {
+    let mut tokens: Vec<(String, String)> = Vec::new();
+    for item in value.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+        let Some((principal, token)) = item
+            .split_once(':')
+            .map(|(principal, token)| (principal.trim(), token.trim()))
+            .filter(|(principal, token)| !principal.is_empty() && !token.is_empty())
+        else {
+            // トークンを含むためエラーメッセージに値を出力しない
+            return Err("「識別子:トークン」の形式で指定してください".to_string());
+        };
+        if tokens.iter().any(|(_, t)| t == token) {
+            return Err(format!("トークンが重複しています: {}", principal));
+        }
+        tokens.push((principal.to_string(), token.to_string()));
+    }
+    Ok(tokens)
+}
+
+// Authorizationヘッダーのトークン（ヘッダーが無い、ASCII以外の文字を含む、空の場合はNone）
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim_start_matches("Bearer ").trim())
+        .filter(|token| !token.is_empty())
+}
+
+// 検証済みのトークンの情報
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Credential<'a> {
+    // 認証済みユーザーの識別子
+    pub principal: &'a str,
+    // トークンの識別子（トークンのハッシュ値）
+    pub key_id: &'a str,
+}
+
+// 認証の設定
+#[derive(Clone, Debug, Default)]
+pub struct AuthSetting {
+    // トークンのハッシュ値ごとの認証済みユーザーの識別子
+    tokens: HashMap<String, String>,
+}
+
+impl AuthSetting {
+    pub fn from_config(config: &Config) -> Self {
+        // 起動時に検証済みのため、不正な値の場合はトークンを登録しない（全て認証エラー）
+        let value = config
+            .auth_tokens
+            .as_ref()
+            .map(|secret| secret.expose().as_str())
+            .unwrap_or_default();
+        let tokens = parse_auth_tokens(value)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(principal, token)| (token_hash(&token), principal))
+            .collect();
+        AuthSetting { tokens }
+    }
+
+    // トークンを検証し、認証済みユーザーの識別子を返す（登録されていない場合はNone）
+    pub fn authenticate(&self, token: &str) -> Option<&str> {
+        self.verify(token).map(|credential| credential.principal)
+    }
+
+    // トークンを検証し、トークンの情報を返す（登録されていない場合はNone）
+    pub fn verify(&self, token: &str) -> Option<Credential<'_>> {
+        self.tokens
+            .get_key_value(&token_hash(token))
+            .map(|(key_id, principal)| Credential { principal, key_id })
+    }
+}
+
+// 設定のインスタンス（プロセス内で共有）
+static AUTH_SETTING: OnceLock<AuthSetting> = OnceLock::new();
+
+// 設定を取得する関数
+pub fn auth_setting() -> &'static AuthSetting {
+    AUTH_SETTING.get_or_init(|| AuthSetting::from_config(config::get_config()))
+}
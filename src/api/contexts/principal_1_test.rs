@@ -0,0 +1,82 @@
+#[cfg(test)]
+// 認証用のトークンのテスト
+mod principal_test {
+    use crate::api::configs::config;
+    use crate::api::configs::secret::Secret;
+    use crate::api::contexts::principal::{AuthSetting, bearer_token, parse_auth_tokens};
+    use axum::http::HeaderMap;
+
+    #[test]
+    fn test_parse_auth_tokens() {
+        let tokens = parse_auth_tokens(" user-1:token-1, user-2 : token-2 ,").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                ("user-1".to_string(), "token-1".to_string()),
+                ("user-2".to_string(), "token-2".to_string()),
+            ]
+        );
+        assert!(parse_auth_tokens("").unwrap().is_empty());
+
+        // 形式が不正な場合はトークンを含めずにエラーを返す
+        let err = parse_auth_tokens("user-1:token-1,secret-token").unwrap_err();
+        assert!(!err.contains("secret-token"));
+        assert!(parse_auth_tokens("user-1:").is_err());
+        assert!(parse_auth_tokens(":token-1").is_err());
+        assert!(parse_auth_tokens("user-1:token-1,user-2:token-1").is_err());
+    }
+
+    #[test]
+    fn test_authenticate() {
+        let mut config = config::get_config().clone();
+        config.auth_tokens = Some(Secret::new(
+            "user-1:token-1,user-2:token-2,user-1:token-3".to_string(),
+        ));
+        let setting = AuthSetting::from_config(&config);
+        assert_eq!(setting.authenticate("token-1"), Some("user-1"));
+        assert_eq!(setting.authenticate("token-2"), Some("user-2"));
+        // 1人のユーザーに複数のトークンを登録できる
+        assert_eq!(setting.authenticate("token-3"), Some("user-1"));
+        assert_eq!(setting.authenticate("unknown"), None);
+        assert_eq!(setting.authenticate(""), None);
+
+        // トークンの識別子はトークンごとに異なり、トークンを含まない
+        let credential = setting.verify("token-3").unwrap();
+        assert_eq!(credential.principal, "user-1");
+        assert_ne!(credential.key_id, setting.verify("token-1").unwrap().key_id);
+        assert!(!credential.key_id.contains("token-3"));
+        assert_eq!(setting.verify("unknown"), None);
+
+        // 未指定の場合は全て認証エラー
+        config.auth_tokens = None;
+        let setting = AuthSetting::from_config(&config);
+        assert_eq!(setting.authenticate("token-1"), None);
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(bearer_token(&headers), None);
+        headers.insert("Authorization", "Bearer token-1".parse().unwrap());
+        assert_eq!(bearer_token(&headers), Some("token-1"));
+        headers.insert("Authorization", "Bearer ".parse().unwrap());
+        assert_eq!(bearer_token(&headers), None);
+        headers.insert("Authorization", "Bearer tökén".parse().unwrap());
+        assert_eq!(bearer_token(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unknown_token() {
+        let url = "http://localhost:8080/api/v1/users";
+        let res = reqwest::Client::new()
+            .get(url)
+            .header("Authorization", "Bearer unknown-token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 401);
+        assert!(res.headers().contains_key("X-Request-Id"));
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["message"], "Unauthorized");
+    }
+}
@@ -0,0 +1,92 @@
+// axum
+use axum::http::HeaderMap;
+
+// UUID
+use uuid::Uuid;
+
+// 標準ライブラリ
+use std::net::IpAddr;
+use std::sync::OnceLock;
+
+// configsモジュール
+use crate::api::configs::config::{self, Config, RequestIdGenerator};
+
+// 信頼するプロキシ（クライアントのIPアドレスと共通）
+use super::trusted_proxies::TrustedProxies;
+
+// 共通コンテキストに保存するリクエストIDのヘッダー名（受信時のヘッダー名に関わらず共通）
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+// 処理中のリクエストID（外部APIの呼び出し時に転送する）
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+// リクエストIDの設定
+#[derive(Clone, Debug)]
+pub struct RequestIdSetting {
+    pub header_name: String,
+    pub max_length: usize,
+    pub trusted_proxies: TrustedProxies,
+    pub generator: RequestIdGenerator,
+}
+
+impl RequestIdSetting {
+    pub fn from_config(config: &Config) -> Self {
+        RequestIdSetting {
+            header_name: config.request_id_header.clone(),
+            max_length: config.request_id_max_length,
+            trusted_proxies: TrustedProxies::from_config(config),
+            generator: config.request_id_generator,
+        }
+    }
+
+    // 受信したリクエストIDを使用するか判定し、使用しない場合は新しく生成する
+    // 接続元はクライアントのIPアドレスの判定と同じアドレス（PROXYプロトコルの受信後）を渡す
+    pub fn resolve(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> String {
+        let trusted = peer.is_some_and(|ip| self.trusted_proxies.contains(ip));
+        if trusted
+            && let Some(value) = headers
+                .get(self.header_name.as_str())
+                .and_then(|value| value.to_str().ok())
+            && is_valid_request_id(value, self.max_length)
+        {
+            return value.to_string();
+        }
+        self.generate()
+    }
+
+    pub fn generate(&self) -> String {
+        match self.generator {
+            RequestIdGenerator::UuidV7 => Uuid::now_v7().to_string(),
+            RequestIdGenerator::UuidV4 => Uuid::new_v4().to_string(),
+        }
+    }
+}
+
+// リクエストIDの形式チェック（英数字と「-_.:」のみ、最大長以内）
+pub fn is_valid_request_id(value: &str, max_length: usize) -> bool {
+    !value.is_empty()
+        && value.len() <= max_length
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+// 設定のインスタンス（プロセス内で共有）
+static REQUEST_ID_SETTING: OnceLock<RequestIdSetting> = OnceLock::new();
+
+// 設定を取得する関数
+pub fn request_id_setting() -> &'static RequestIdSetting {
+    REQUEST_ID_SETTING.get_or_init(|| RequestIdSetting::from_config(config::get_config()))
+}
+
+// リクエストIDを設定して処理を実行する関数
+pub async fn scope<F: Future>(request_id: String, f: F) -> F::Output {
+    CURRENT_REQUEST_ID.scope(request_id, f).await
+}
+
+// 処理中のリクエストIDを返す関数（リクエスト外の処理の場合はNone）
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(|id| id.clone()).ok()
+}
@@ -0,0 +1,87 @@
+#[cfg(test)]
+// リクエストIDのテスト
+mod request_id_test {
+    use crate::api::configs::config::RequestIdGenerator;
+    use crate::api::contexts::request_id::{RequestIdSetting, is_valid_request_id};
+    use crate::api::contexts::trusted_proxies::TrustedProxies;
+    use axum::http::HeaderMap;
+    use std::net::IpAddr;
+
+    fn setting(trusted_proxies: &str) -> RequestIdSetting {
+        RequestIdSetting {
+            header_name: "X-Correlation-Id".to_string(),
+            max_length: 16,
+            trusted_proxies: TrustedProxies::parse(trusted_proxies),
+            generator: RequestIdGenerator::UuidV4,
+        }
+    }
+
+    fn headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Correlation-Id", value.parse().unwrap());
+        headers
+    }
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    #[test]
+    fn test_is_valid_request_id() {
+        assert!(is_valid_request_id("abc-123_X.y:z", 16));
+        assert!(!is_valid_request_id("", 16));
+        assert!(!is_valid_request_id("a".repeat(17).as_str(), 16));
+        assert!(!is_valid_request_id("abc 123", 16));
+        assert!(!is_valid_request_id("abc\"<script>", 16));
+        assert!(!is_valid_request_id("日本語", 16));
+    }
+
+    #[test]
+    fn test_resolve_from_trusted_proxy() {
+        let setting = setting("10.0.0.0/8");
+        assert_eq!(
+            setting.resolve(&headers("gw-0001"), ip("10.0.0.1")),
+            "gw-0001"
+        );
+    }
+
+    #[test]
+    fn test_resolve_generates_new_id() {
+        let setting = setting("10.0.0.0/8");
+
+        // 信頼しない送信元
+        let id = setting.resolve(&headers("gw-0001"), ip("203.0.113.1"));
+        assert_ne!(id, "gw-0001");
+        assert_eq!(uuid::Uuid::parse_str(&id).unwrap().get_version_num(), 4);
+
+        // 送信元が不明
+        assert_ne!(setting.resolve(&headers("gw-0001"), None), "gw-0001");
+
+        // 不正な形式、最大長超過
+        assert_ne!(
+            setting.resolve(&headers("gw 0001"), ip("10.0.0.1")),
+            "gw 0001"
+        );
+        let too_long = "a".repeat(17);
+        assert_ne!(
+            setting.resolve(&headers(&too_long), ip("10.0.0.1")),
+            too_long
+        );
+
+        // 設定外のヘッダー名
+        let mut other = HeaderMap::new();
+        other.insert("X-Request-Id", "gw-0001".parse().unwrap());
+        assert_ne!(setting.resolve(&other, ip("10.0.0.1")), "gw-0001");
+    }
+
+    #[test]
+    fn test_generate_uuid_v7() {
+        let mut setting = setting("");
+        setting.generator = RequestIdGenerator::UuidV7;
+        let first = setting.generate();
+        let second = setting.generate();
+        assert_eq!(uuid::Uuid::parse_str(&first).unwrap().get_version_num(), 7);
+        // 時刻順に並ぶ
+        assert!(first < second);
+    }
+}
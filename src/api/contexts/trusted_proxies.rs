@@ -0,0 +1,59 @@
+// axum
+use axum::extract::{ConnectInfo, Request};
+
+// CIDR
+use ipnet::IpNet;
+
+// 標準ライブラリ
+use std::net::{IpAddr, SocketAddr};
+
+// configsモジュール
+use crate::api::configs::config::Config;
+
+// 全ての送信元を信頼する場合の設定値
+const TRUST_ALL_PROXIES: &str = "*";
+
+// 信頼するプロキシ（クライアントのIPアドレス、リクエストID、PROXYプロトコルで共通）
+#[derive(Clone, Debug, PartialEq)]
+pub enum TrustedProxies {
+    All,
+    List(Vec<IpNet>),
+}
+
+impl TrustedProxies {
+    pub fn from_config(config: &Config) -> Self {
+        TrustedProxies::parse(&config.proxy_trusted_cidrs)
+    }
+
+    // カンマ区切りのCIDRまたはIPアドレスから作成（不正な値は無視する）
+    pub fn parse(value: &str) -> Self {
+        let mut list = Vec::new();
+        for item in value.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+            if item == TRUST_ALL_PROXIES {
+                return TrustedProxies::All;
+            }
+            match item
+                .parse::<IpNet>()
+                .or_else(|_| item.parse::<IpAddr>().map(IpNet::from))
+            {
+                Ok(net) => list.push(net),
+                Err(_) => println!("信頼するプロキシの設定が不正なため無視します: {}", item),
+            }
+        }
+        TrustedProxies::List(list)
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match self {
+            TrustedProxies::All => true,
+            TrustedProxies::List(list) => list.iter().any(|net| net.contains(&ip)),
+        }
+    }
+}
+
+// 接続元のアドレスを取得する関数（PROXYプロトコルが有効な場合はプロキシから受信したアドレス）
+pub fn peer_ip(req: &Request) -> Option<IpAddr> {
+    req.extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+// 信頼するプロキシのテスト
+mod trusted_proxies_test {
+    use crate::api::contexts::trusted_proxies::{TrustedProxies, peer_ip};
+    use axum::body::Body;
+    use axum::extract::{ConnectInfo, Request};
+    use std::net::SocketAddr;
+
+    #[test]
+    fn test_trusted_proxies() {
+        let proxies = TrustedProxies::parse("10.0.0.0/8, 192.168.1.10,::1/128,invalid");
+        assert_eq!(
+            proxies,
+            TrustedProxies::List(vec![
+                "10.0.0.0/8".parse().unwrap(),
+                "192.168.1.10/32".parse().unwrap(),
+                "::1/128".parse().unwrap(),
+            ])
+        );
+        assert!(proxies.contains("10.1.2.3".parse().unwrap()));
+        assert!(proxies.contains("192.168.1.10".parse().unwrap()));
+        assert!(!proxies.contains("192.168.1.11".parse().unwrap()));
+        assert!(proxies.contains("::1".parse().unwrap()));
+
+        assert_eq!(TrustedProxies::parse("*"), TrustedProxies::All);
+        assert!(!TrustedProxies::parse("").contains("127.0.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_peer_ip() {
+        let mut req = Request::new(Body::empty());
+        assert_eq!(peer_ip(&req), None);
+
+        let addr: SocketAddr = "10.0.0.1:12345".parse().unwrap();
+        req.extensions_mut().insert(ConnectInfo(addr));
+        assert_eq!(peer_ip(&req), Some(addr.ip()));
+    }
+}
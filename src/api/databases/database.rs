@@ -1,18 +1,106 @@
-use sea_orm::{ConnectOptions, Database, DatabaseConnection, DbErr};
+use sea_orm::sqlx::ConnectOptions;
+use sea_orm::sqlx::postgres::{PgConnectOptions, PgPoolOptions, PgSslMode};
+use sea_orm::{DatabaseConnection, DbErr, SqlxPostgresConnector};
+
+// 標準ライブラリ
+use std::str::FromStr;
+use std::sync::OnceLock;
 
 // configsモジュール
-use crate::api::configs::config;
+use crate::api::configs::config::{self, Config};
+
+// OpenTelemetry用のモジュール
+use crate::api::telemetry::otel;
+
+// サーバー全体で共有するDB接続プール（未初期化の場合は都度接続する）
+static DB_POOL: OnceLock<DatabaseConnection> = OnceLock::new();
+
+// 共有するDB接続プールの初期化用関数（サーバー起動時に呼び出す）
+pub async fn init_db_pool() -> Result<(), DbErr> {
+    let db = connect().await?;
+    let _ = DB_POOL.set(db);
+    Ok(())
+}
+
+// 共有するDB接続プールを返す関数（未初期化の場合はNone）
+pub fn shared_db_pool() -> Option<&'static DatabaseConnection> {
+    DB_POOL.get()
+}
 
 // DB接続
 pub async fn db_connection() -> Result<DatabaseConnection, DbErr> {
+    match DB_POOL.get() {
+        Some(db) => Ok(db.clone()),
+        None => connect().await,
+    }
+}
+
+// DB接続プールの作成
+async fn connect() -> Result<DatabaseConnection, DbErr> {
     // 環境変数取得
     let config = config::get_config();
 
-    // DB接続
-    let mut opt = ConnectOptions::new(config.database_url);
-    // sqlxのログ出力をOFFに変更
-    opt.sqlx_logging(false);
-    let db = Database::connect(opt).await?;
+    // DB接続（sqlxのログ出力をOFFに変更）
+    let options = pg_connect_options(config)
+        .map_err(DbErr::Custom)?
+        .disable_statement_logging();
+    let pool = PgPoolOptions::new()
+        .connect_with(options)
+        .await
+        .map_err(|err| DbErr::Conn(sea_orm::RuntimeErr::SqlxError(err)))?;
+    let mut db = SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
+
+    // OpenTelemetryが有効な場合はクエリをspanとして記録
+    if config.otel_enabled {
+        db.set_metric_callback(otel::record_db_query);
+    }
 
     Ok(db)
 }
+
+// DBの接続設定を作成する関数（URLを優先し、無い場合は個別の項目からパスワードをURLの文字列にせずに作成）
+// SQLごとの実行時間の上限（0の場合は無効）はセッションの設定としてPostgresに渡す
+pub fn pg_connect_options(config: &Config) -> Result<PgConnectOptions, String> {
+    let options = base_connect_options(config)?;
+    if config.database_statement_timeout.is_zero() {
+        return Ok(options);
+    }
+    Ok(options.options([(
+        "statement_timeout",
+        config.database_statement_timeout.as_millis(),
+    )]))
+}
+
+fn base_connect_options(config: &Config) -> Result<PgConnectOptions, String> {
+    if let Some(url) = &config.database_url {
+        return PgConnectOptions::from_str(url.expose().as_str())
+            .map_err(|err| format!("DATABASE_URL: 不正な接続先です（{}）", err));
+    }
+
+    let Some(host) = &config.database_host else {
+        return Err(
+            "DATABASE_URL: 必須です（個別の項目で指定する場合はDATABASE_HOST）".to_string(),
+        );
+    };
+    let Some(user) = &config.database_user else {
+        return Err("DATABASE_USER: DATABASE_HOSTを指定した場合は必須です".to_string());
+    };
+    let ssl_mode = PgSslMode::from_str(&config.database_ssl_mode).map_err(|_| {
+        format!(
+            "DATABASE_SSL_MODE: 不正な値です: {}",
+            config.database_ssl_mode
+        )
+    })?;
+    let mut options = PgConnectOptions::new()
+        .host(host)
+        .port(config.database_port)
+        .username(user)
+        .ssl_mode(ssl_mode);
+    if let Some(password) = &config.database_password {
+        options = options.password(password.expose());
+    }
+    if let Some(name) = &config.database_name {
+        options = options.database(name);
+    }
+    Ok(options)
+}
@@ -2,4 +2,8 @@
 
 pub mod prelude;
 
+pub mod audit_logs;
+pub mod outbox;
 pub mod users;
+pub mod webhook_deliveries;
+pub mod webhook_subscriptions;
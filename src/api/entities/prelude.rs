@@ -5,3 +5,27 @@ pub use super::users::ActiveModel as UsersActiveModel;
 pub use super::users::Column as UsersColumn;
 pub use super::users::Entity as Users;
 pub use super::users::Model as UsersModel;
+
+// 監査ログ
+pub use super::audit_logs::ActiveModel as AuditLogsActiveModel;
+pub use super::audit_logs::Column as AuditLogsColumn;
+pub use super::audit_logs::Entity as AuditLogs;
+pub use super::audit_logs::Model as AuditLogsModel;
+
+// アウトボックス
+pub use super::outbox::ActiveModel as OutboxActiveModel;
+pub use super::outbox::Column as OutboxColumn;
+pub use super::outbox::Entity as Outbox;
+pub use super::outbox::Model as OutboxModel;
+
+// Webhookの購読
+pub use super::webhook_subscriptions::ActiveModel as WebhookSubscriptionsActiveModel;
+pub use super::webhook_subscriptions::Column as WebhookSubscriptionsColumn;
+pub use super::webhook_subscriptions::Entity as WebhookSubscriptions;
+pub use super::webhook_subscriptions::Model as WebhookSubscriptionsModel;
+
+// Webhookの配信ログ
+pub use super::webhook_deliveries::ActiveModel as WebhookDeliveriesActiveModel;
+pub use super::webhook_deliveries::Column as WebhookDeliveriesColumn;
+pub use super::webhook_deliveries::Entity as WebhookDeliveries;
+pub use super::webhook_deliveries::Model as WebhookDeliveriesModel;
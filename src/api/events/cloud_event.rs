@@ -0,0 +1,58 @@
+// 変換用のクレート
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// UUID
+use uuid::Uuid;
+
+// Usersモデル
+use crate::api::entities::prelude::UsersModel;
+
+// イベント種別
+pub const EVENT_TYPE_USER_CREATED: &str = "rust_api.user.created";
+pub const EVENT_TYPE_USER_UPDATED: &str = "rust_api.user.updated";
+pub const EVENT_TYPE_USER_DELETED: &str = "rust_api.user.deleted";
+
+// イベントの発生元
+pub const EVENT_SOURCE_USERS: &str = "/rust_api/users";
+
+// CloudEvents 1.0（JSON形式）のイベントの構造体
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CloudEvent {
+    pub specversion: String,
+    pub id: String,
+    pub source: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    pub time: String,
+    pub datacontenttype: String,
+    pub data: Value,
+}
+
+impl CloudEvent {
+    // イベント作成用メソッド
+    pub fn new(source: &str, event_type: &str, subject: Option<String>, data: Value) -> Self {
+        CloudEvent {
+            specversion: "1.0".to_string(),
+            id: Uuid::new_v4().to_string(),
+            source: source.to_string(),
+            event_type: event_type.to_string(),
+            subject,
+            time: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+            datacontenttype: "application/json".to_string(),
+            data,
+        }
+    }
+}
+
+// ユーザーのライフサイクルイベント作成用関数
+pub fn new_user_event(event_type: &str, user: &UsersModel) -> CloudEvent {
+    CloudEvent::new(
+        EVENT_SOURCE_USERS,
+        event_type,
+        Some(user.uid.clone()),
+        serde_json::to_value(user).unwrap_or_default(),
+    )
+}
@@ -0,0 +1,178 @@
+// axum
+use axum::http::StatusCode;
+
+// 共通エラー用モジュール
+use crate::api::errors::error::CommonError;
+
+// 標準ライブラリ
+use std::sync::Arc;
+use std::time::Duration;
+
+// DB接続用のモジュール
+use crate::api::databases::database::db_connection;
+
+// CloudEvents用のモジュール
+use crate::api::events::cloud_event::CloudEvent;
+
+// OpenTelemetry用のモジュール
+use crate::api::telemetry::otel::send_traced;
+
+// Webhook用のモジュール
+use crate::api::repositories::webhooks::webhooks_repository::create_webhook_deliveries_for_event;
+
+// イベント送信用のトレイト（モック化もできるように定義）
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait EventPublisher {
+    async fn publish(&self, event: &CloudEvent) -> Result<(), CommonError>;
+}
+
+// ログ出力のみ行うイベント送信の構造体
+pub struct LogEventPublisher;
+
+impl LogEventPublisher {
+    // 初期化用メソッド
+    pub fn new() -> Self {
+        LogEventPublisher
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for LogEventPublisher {
+    async fn publish(&self, event: &CloudEvent) -> Result<(), CommonError> {
+        let json = serde_json::to_string(event).unwrap_or_default();
+        tracing::info!("[LogEventPublisher.publish] {}", json);
+
+        Ok(())
+    }
+}
+
+// HTTP（Webhook）でイベントを送信する構造体
+pub struct HttpEventPublisher {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl HttpEventPublisher {
+    // 初期化用メソッド（送信先が応答しない場合にリレーが止まらないよう接続・応答を待つ時間を制限する）
+    pub fn new(url: String, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(timeout)
+            .timeout(timeout)
+            .build()
+            .unwrap_or_default();
+        HttpEventPublisher { client, url }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for HttpEventPublisher {
+    async fn publish(&self, event: &CloudEvent) -> Result<(), CommonError> {
+        // CloudEventsのstructuredモードで送信
+        let body = serde_json::to_vec(event).unwrap_or_default();
+        let result = send_traced(
+            self.client
+                .post(&self.url)
+                .header("Content-Type", "application/cloudevents+json")
+                .body(body),
+        )
+        .await;
+
+        match result {
+            Ok(res) if res.status().is_success() => Ok(()),
+            Ok(res) => Err(CommonError::CustomError {
+                status_code: StatusCode::BAD_GATEWAY,
+                message: format!(
+                    "[HttpEventPublisher.publish] 送信先がエラーを返しました。: {}",
+                    res.status()
+                ),
+            }),
+            Err(err) => Err(CommonError::CustomError {
+                status_code: StatusCode::BAD_GATEWAY,
+                message: format!("[HttpEventPublisher.publish] 送信エラー: {}", err),
+            }),
+        }
+    }
+}
+
+// イベント種別を購読しているWebhookに配信を登録する構造体
+// 実際の送信はWebhookDispatcherが行う
+pub struct WebhookEventPublisher;
+
+impl WebhookEventPublisher {
+    // 初期化用メソッド
+    pub fn new() -> Self {
+        WebhookEventPublisher
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for WebhookEventPublisher {
+    async fn publish(&self, event: &CloudEvent) -> Result<(), CommonError> {
+        let result = match db_connection().await {
+            Ok(db) => create_webhook_deliveries_for_event(&db, event).await,
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) => Err(CommonError::CustomError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("[WebhookEventPublisher.publish] 配信の登録エラー: {}", err),
+            }),
+        }
+    }
+}
+
+// 複数の送信先にイベントを送信する構造体（いずれかが失敗した場合はエラー）
+pub struct MultiEventPublisher {
+    publishers: Vec<Arc<dyn EventPublisher + Send + Sync + 'static>>,
+}
+
+impl MultiEventPublisher {
+    // 初期化用メソッド
+    pub fn new(publishers: Vec<Arc<dyn EventPublisher + Send + Sync + 'static>>) -> Self {
+        MultiEventPublisher { publishers }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for MultiEventPublisher {
+    async fn publish(&self, event: &CloudEvent) -> Result<(), CommonError> {
+        for publisher in self.publishers.iter() {
+            publisher.publish(event).await?;
+        }
+
+        Ok(())
+    }
+}
+
+// メモリ上にイベントを保持する構造体（テスト用）
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub struct InMemoryEventPublisher {
+    events: std::sync::Arc<std::sync::Mutex<Vec<CloudEvent>>>,
+}
+
+#[cfg(test)]
+impl InMemoryEventPublisher {
+    // 初期化用メソッド
+    pub fn new() -> Self {
+        InMemoryEventPublisher::default()
+    }
+
+    // 送信済みイベントの取得
+    pub fn events(&self) -> Vec<CloudEvent> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl EventPublisher for InMemoryEventPublisher {
+    async fn publish(&self, event: &CloudEvent) -> Result<(), CommonError> {
+        self.events.lock().unwrap().push(event.clone());
+
+        Ok(())
+    }
+}
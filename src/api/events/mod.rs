@@ -0,0 +1,11 @@
+pub mod cloud_event;
+pub mod event_publisher;
+pub mod outbox_relay;
+pub mod user_event_stream;
+pub mod webhook_dispatcher;
+pub mod webhook_sender;
+
+// テストコード用のモジュール
+mod outbox_relay_1_test;
+mod user_event_stream_1_test;
+mod webhook_dispatcher_1_test;
@@ -0,0 +1,230 @@
+// SeaORM
+use sea_orm::{DbErr, TransactionTrait};
+
+// 標準ライブラリ
+use std::sync::Arc;
+use std::time::Duration;
+
+// configsモジュール
+use crate::api::configs::config::{Config, OutboxPublisher};
+
+// DB接続用のモジュール
+use crate::api::databases::database::db_connection;
+
+// イベント用のモジュール
+use crate::api::events::cloud_event::CloudEvent;
+use crate::api::events::event_publisher::{
+    EventPublisher, HttpEventPublisher, LogEventPublisher, MultiEventPublisher,
+    WebhookEventPublisher,
+};
+
+// シャットダウン用のモジュール
+use crate::api::lifecycle::shutdown::ShutdownListener;
+
+// アウトボックス用のモジュール
+use crate::api::repositories::outbox::outbox_repository::{
+    claim_pending_events, mark_failed, mark_published,
+};
+
+// アウトボックスのリレー設定の構造体
+#[derive(Clone, Debug)]
+pub struct OutboxRelaySetting {
+    pub poll_interval: Duration,
+    pub batch_size: u64,
+    pub max_attempts: i32,
+    pub backoff_base: Duration,
+    pub backoff_max: Duration,
+    // HTTPでのイベント送信の接続・応答を待つ時間の上限
+    pub publish_timeout: Duration,
+}
+
+impl OutboxRelaySetting {
+    // 環境変数から設定を作成
+    pub fn from_config(config: &Config) -> Self {
+        OutboxRelaySetting {
+            poll_interval: config.outbox_poll_interval,
+            batch_size: config.outbox_batch_size,
+            max_attempts: config.outbox_max_attempts,
+            backoff_base: config.outbox_backoff_base,
+            backoff_max: config.outbox_backoff_max,
+            publish_timeout: config.outbox_publish_timeout,
+        }
+    }
+
+    // 送信中のイベントを他のレプリカが再取得しない期間
+    // バッチの全てのイベントの送信がタイムアウトしても期限切れにならない時間とする
+    pub fn lease(&self) -> Duration {
+        let count = u32::try_from(self.batch_size).unwrap_or(u32::MAX);
+        self.publish_timeout.saturating_mul(count.saturating_add(1))
+    }
+}
+
+// イベントの送信結果
+enum PublishResult {
+    Published,
+    // 送信エラー（バックオフしてリトライする）
+    Failed(String),
+    // ペイロードの変換エラー（リトライしない）
+    Invalid(String),
+}
+
+// アウトボックスのイベントを送信先に中継する構造体
+pub struct OutboxRelay {
+    publisher: Arc<dyn EventPublisher + Send + Sync + 'static>,
+    setting: OutboxRelaySetting,
+}
+
+impl OutboxRelay {
+    // 初期化用メソッド
+    pub fn new(
+        publisher: Arc<dyn EventPublisher + Send + Sync + 'static>,
+        setting: OutboxRelaySetting,
+    ) -> Self {
+        OutboxRelay { publisher, setting }
+    }
+
+    // 環境変数から作成（送信先が「none」の場合はNone）
+    // 送信先はカンマ区切りで複数指定可能（例: log,webhooks）
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let mut publishers: Vec<Arc<dyn EventPublisher + Send + Sync + 'static>> = Vec::new();
+        for publisher in config.outbox_publisher.iter() {
+            match publisher {
+                OutboxPublisher::Disabled => {}
+                OutboxPublisher::Http => publishers.push(Arc::new(HttpEventPublisher::new(
+                    config
+                        .outbox_webhook_url
+                        .as_ref()
+                        .map(|url| url.expose().to_string())
+                        .unwrap_or_default(),
+                    config.outbox_publish_timeout,
+                ))),
+                OutboxPublisher::Webhooks => {
+                    publishers.push(Arc::new(WebhookEventPublisher::new()))
+                }
+                OutboxPublisher::Log => publishers.push(Arc::new(LogEventPublisher::new())),
+            }
+        }
+
+        let publisher: Arc<dyn EventPublisher + Send + Sync + 'static> = match publishers.len() {
+            0 => return None,
+            1 => publishers.remove(0),
+            _ => Arc::new(MultiEventPublisher::new(publishers)),
+        };
+
+        Some(OutboxRelay::new(
+            publisher,
+            OutboxRelaySetting::from_config(config),
+        ))
+    }
+
+    // 送信対象のイベントを1バッチ分処理し、送信に成功した件数を返す
+    // 送信中にDB接続や行ロックを保持しないよう、取得と結果の記録は別のトランザクションで行う
+    pub async fn relay_once(&self) -> Result<usize, DbErr> {
+        let db = db_connection().await?;
+        let events =
+            claim_pending_events(&db, self.setting.batch_size, self.setting.lease()).await?;
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        // イベント送信（結果は送信後にまとめて記録する）
+        let mut results = Vec::with_capacity(events.len());
+        for event in events {
+            // ペイロードをCloudEventsの形式に変換
+            let cloud_event: CloudEvent = match serde_json::from_value(event.payload.clone()) {
+                Ok(cloud_event) => cloud_event,
+                Err(err) => {
+                    let msg = format!("ペイロードの変換エラー: {}", err);
+                    tracing::error!(
+                        "[OutboxRelay.relay_once] event_id={} {}",
+                        event.event_id,
+                        msg
+                    );
+                    results.push((event, PublishResult::Invalid(msg)));
+                    continue;
+                }
+            };
+
+            let result = match self.publisher.publish(&cloud_event).await {
+                Ok(_) => PublishResult::Published,
+                Err(err) => PublishResult::Failed(err.to_string()),
+            };
+            results.push((event, result));
+        }
+
+        let tx = db.begin().await?;
+        let mut published = 0;
+        for (event, result) in results {
+            let recorded = match result {
+                PublishResult::Published => {
+                    let recorded = mark_published(&tx, &event).await?;
+                    if recorded {
+                        published += 1;
+                    }
+                    recorded
+                }
+                PublishResult::Invalid(err) => mark_failed(&tx, &event, err, None).await?,
+                PublishResult::Failed(err) => {
+                    let attempts = event.attempts + 1;
+                    let next_attempt_at = if attempts >= self.setting.max_attempts {
+                        None
+                    } else {
+                        let delay = backoff_delay(
+                            attempts,
+                            self.setting.backoff_base,
+                            self.setting.backoff_max,
+                        );
+                        let delay = chrono::Duration::from_std(delay).unwrap_or_default();
+                        Some((chrono::Utc::now() + delay).into())
+                    };
+                    tracing::error!(
+                        "[OutboxRelay.relay_once] event_id={} attempts={} 送信エラー: {}",
+                        event.event_id,
+                        attempts,
+                        err
+                    );
+                    mark_failed(&tx, &event, err, next_attempt_at).await?
+                }
+            };
+            // 期限切れで他のレプリカが取得し直した場合は、そちらの結果を優先する
+            if !recorded {
+                tracing::warn!(
+                    "[OutboxRelay.relay_once] event_id={} 送信中の期限が切れたため結果を記録しません",
+                    event.event_id
+                );
+            }
+        }
+        tx.commit().await?;
+
+        Ok(published)
+    }
+
+    // 一定間隔でイベントの送信処理を実行（シャットダウン時は処理中のバッチを完了してから終了）
+    pub async fn run(self, mut shutdown: ShutdownListener) {
+        tracing::info!(
+            "Start outbox relay (interval:{}ms) !!",
+            self.setting.poll_interval.as_millis()
+        );
+
+        while !shutdown.is_triggered() {
+            match self.relay_once().await {
+                // 送信対象が残っている可能性があるため、バッチ上限まで処理した場合は待たずに続行
+                Ok(published) if published as u64 >= self.setting.batch_size => continue,
+                Ok(_) => {}
+                Err(err) => tracing::error!("[OutboxRelay.run] リレー処理エラー: {}", err),
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(self.setting.poll_interval) => {}
+                _ = shutdown.wait() => {}
+            }
+        }
+
+        tracing::info!("Stop outbox relay !!");
+    }
+}
+
+// 指数バックオフで次回送信までの待機時間を計算する関数
+pub fn backoff_delay(attempts: i32, base: Duration, max: Duration) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 31) as u32;
+    base.saturating_mul(2u32.saturating_pow(exponent)).min(max)
+}
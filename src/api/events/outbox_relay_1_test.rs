@@ -0,0 +1,259 @@
+#[cfg(test)]
+use test_env_helpers::*;
+
+#[before_each]
+#[after_each]
+#[cfg(test)]
+// OutboxRelayのテスト
+mod outbox_relay_test {
+    use crate::api::databases::database::db_connection;
+    use crate::api::entities::prelude::{
+        Outbox, OutboxActiveModel, OutboxColumn, OutboxModel, Users, UsersModel,
+    };
+    use crate::api::errors::error::CommonError;
+    use crate::api::events::cloud_event::EVENT_TYPE_USER_CREATED;
+    use crate::api::events::event_publisher::{
+        EventPublisher, HttpEventPublisher, InMemoryEventPublisher, MockEventPublisher,
+    };
+    use crate::api::events::outbox_relay::{OutboxRelay, OutboxRelaySetting, backoff_delay};
+    use crate::api::repositories::outbox::outbox_repository::{
+        claim_pending_events, mark_published,
+    };
+    use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    // テスト前に実行する処理
+    async fn before_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // usersテーブルとoutboxテーブルのデータを全て削除
+        Users::delete_many().exec(&db).await.unwrap();
+        Outbox::delete_many().exec(&db).await.unwrap();
+    }
+
+    // テスト後に実行する処理
+    async fn after_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // usersテーブルとoutboxテーブルのデータを全て削除
+        Users::delete_many().exec(&db).await.unwrap();
+        Outbox::delete_many().exec(&db).await.unwrap();
+    }
+
+    // テスト用のリレー設定
+    fn setting(max_attempts: i32) -> OutboxRelaySetting {
+        OutboxRelaySetting {
+            poll_interval: Duration::from_millis(100),
+            batch_size: 10,
+            max_attempts,
+            backoff_base: Duration::from_secs(60),
+            backoff_max: Duration::from_secs(600),
+            publish_timeout: Duration::from_secs(1),
+        }
+    }
+
+    // APIからユーザーを作成する関数
+    async fn create_user() -> UsersModel {
+        let data = serde_json::json!({
+            "last_name": "田中",
+            "first_name": "太郎",
+            "email": "t.tanaka@example.com"
+        });
+        let client = reqwest::Client::new();
+        let res = client
+            .post("http://localhost:8080/api/v1/user")
+            .json(&data)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 201);
+        serde_json::from_str(&res.text().await.unwrap()).unwrap()
+    }
+
+    // outboxテーブルのデータを取得する関数
+    async fn find_outbox() -> Vec<OutboxModel> {
+        let db = db_connection().await.unwrap();
+        Outbox::find().all(&db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_publishes_pending_events() {
+        let user = create_user().await;
+
+        // ユーザー作成と同じトランザクションでイベントが登録されていること
+        let outbox = find_outbox().await;
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox[0].status, "pending");
+
+        // リレーを実行
+        let publisher = InMemoryEventPublisher::new();
+        let relay = OutboxRelay::new(Arc::new(publisher.clone()), setting(3));
+        let published = relay.relay_once().await.unwrap();
+        assert_eq!(published, 1);
+
+        // CloudEventsの形式で送信されていること
+        let events = publisher.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].specversion, "1.0");
+        assert_eq!(events[0].event_type, EVENT_TYPE_USER_CREATED);
+        assert_eq!(events[0].subject, Some(user.uid.clone()));
+        assert_eq!(events[0].id, outbox[0].event_id);
+        assert_eq!(events[0].data["email"], "t.tanaka@example.com");
+
+        // 送信済みになり、再送されないこと
+        let outbox = find_outbox().await;
+        assert_eq!(outbox[0].status, "published");
+        assert!(outbox[0].published_at.is_some());
+        let published = relay.relay_once().await.unwrap();
+        assert_eq!(published, 0);
+        assert_eq!(publisher.events().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn it_retries_failed_events_with_backoff() {
+        create_user().await;
+
+        // 常に送信エラーになる送信先
+        let mut publisher = MockEventPublisher::new();
+        publisher
+            .expect_publish()
+            .times(2)
+            .returning(|_| Err(CommonError::InternalServerError));
+        let relay = OutboxRelay::new(Arc::new(publisher), setting(2));
+
+        // 1回目は次回送信日時を遅らせて未送信のまま
+        let published = relay.relay_once().await.unwrap();
+        assert_eq!(published, 0);
+        let outbox = find_outbox().await;
+        assert_eq!(outbox[0].status, "pending");
+        assert_eq!(outbox[0].attempts, 1);
+        assert!(outbox[0].last_error.is_some());
+        assert!(outbox[0].next_attempt_at > chrono::Utc::now());
+
+        // 次回送信日時前は送信対象外
+        let published = relay.relay_once().await.unwrap();
+        assert_eq!(published, 0);
+
+        // 次回送信日時を過ぎた場合は再送し、上限に達したら失敗にする
+        let db = db_connection().await.unwrap();
+        let mut event: OutboxActiveModel = outbox[0].clone().into();
+        event.next_attempt_at = Set(chrono::Utc::now().into());
+        event.update(&db).await.unwrap();
+        relay.relay_once().await.unwrap();
+        let outbox = Outbox::find()
+            .filter(OutboxColumn::Id.eq(outbox[0].id))
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(outbox.status, "failed");
+        assert_eq!(outbox.attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn it_calculates_backoff_delay() {
+        let base = Duration::from_secs(1);
+        let max = Duration::from_secs(10);
+        assert_eq!(backoff_delay(1, base, max), Duration::from_secs(1));
+        assert_eq!(backoff_delay(2, base, max), Duration::from_secs(2));
+        assert_eq!(backoff_delay(4, base, max), Duration::from_secs(8));
+        assert_eq!(backoff_delay(5, base, max), Duration::from_secs(10));
+        assert_eq!(backoff_delay(100, base, max), Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn it_skips_claimed_events() {
+        create_user().await;
+
+        // 他のレプリカが送信中のイベントは期限まで取得しない
+        let db = db_connection().await.unwrap();
+        let claimed = claim_pending_events(&db, 10, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(claimed.len(), 1);
+        let outbox = find_outbox().await;
+        assert_eq!(outbox[0].status, "pending");
+        assert!(outbox[0].next_attempt_at > chrono::Utc::now());
+
+        let publisher = InMemoryEventPublisher::new();
+        let relay = OutboxRelay::new(Arc::new(publisher.clone()), setting(3));
+        assert_eq!(relay.relay_once().await.unwrap(), 0);
+        assert!(publisher.events().is_empty());
+
+        // 期限を過ぎた場合は再送する
+        let mut event: OutboxActiveModel = outbox[0].clone().into();
+        event.next_attempt_at = Set(chrono::Utc::now().into());
+        event.update(&db).await.unwrap();
+        assert_eq!(relay.relay_once().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn it_ignores_result_after_lease_is_lost() {
+        create_user().await;
+
+        let db = db_connection().await.unwrap();
+        let claimed = claim_pending_events(&db, 10, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(
+            find_outbox().await[0].next_attempt_at,
+            claimed[0].next_attempt_at
+        );
+
+        // 期限切れで他のレプリカが取得し直した場合
+        let outbox = find_outbox().await;
+        let mut event: OutboxActiveModel = outbox[0].clone().into();
+        event.next_attempt_at = Set((chrono::Utc::now() + chrono::Duration::minutes(5)).into());
+        event.update(&db).await.unwrap();
+
+        // 期限切れの送信結果では上書きしない
+        let tx = db.begin().await.unwrap();
+        assert!(!mark_published(&tx, &claimed[0]).await.unwrap());
+        tx.commit().await.unwrap();
+        let outbox = find_outbox().await;
+        assert_eq!(outbox[0].status, "pending");
+        assert_eq!(outbox[0].attempts, 0);
+
+        // 取得し直した側の結果は記録する
+        let tx = db.begin().await.unwrap();
+        assert!(mark_published(&tx, &outbox[0]).await.unwrap());
+        tx.commit().await.unwrap();
+        assert_eq!(find_outbox().await[0].status, "published");
+    }
+
+    #[tokio::test]
+    async fn it_times_out_unresponsive_endpoint() {
+        // 接続を受け付けて応答しない送信先
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut conns = Vec::new();
+            while let Ok((conn, _)) = listener.accept().await {
+                conns.push(conn);
+            }
+        });
+
+        let user = create_user().await;
+        let event: crate::api::events::cloud_event::CloudEvent =
+            serde_json::from_value(find_outbox().await[0].payload.clone()).unwrap();
+        assert_eq!(event.subject, Some(user.uid));
+
+        let publisher = HttpEventPublisher::new(url, Duration::from_millis(200));
+        let result = tokio::time::timeout(Duration::from_secs(5), publisher.publish(&event)).await;
+        assert!(matches!(result, Ok(Err(_))));
+    }
+}
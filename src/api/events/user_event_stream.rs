@@ -0,0 +1,232 @@
+// Postgresの通知受信用
+use sea_orm::sqlx::postgres::{PgListener, PgPoolOptions};
+
+// 配信用のチャンネル
+use tokio::sync::broadcast;
+
+// 標準ライブラリ
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+// configsモジュール
+use crate::api::configs::config;
+
+// DB接続用のモジュール
+use crate::api::databases::database::{db_connection, pg_connect_options};
+
+// CloudEvents用のモジュール
+use crate::api::events::cloud_event::{CloudEvent, EVENT_SOURCE_USERS};
+
+// シャットダウン用のモジュール
+use crate::api::lifecycle::shutdown::ShutdownListener;
+
+// アウトボックスのリポジトリ用のモジュール
+use crate::api::repositories::outbox::outbox_repository::{
+    OUTBOX_NOTIFY_CHANNEL, get_outbox_event_from_id,
+};
+
+// 通知受信の再接続までの待機時間
+const LISTENER_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+// ストリームで配信するユーザーイベント（IDはアウトボックスのIDで全レプリカ共通）
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserStreamEvent {
+    pub id: i64,
+    pub event: CloudEvent,
+}
+
+// 購読開始時の結果
+pub struct UserEventSubscription {
+    // Last-Event-ID以降に発生したイベント
+    pub replay: Vec<UserStreamEvent>,
+    // Last-Event-ID以降のイベントがリプレイバッファから破棄済みの場合はtrue
+    pub expired: bool,
+    pub receiver: broadcast::Receiver<UserStreamEvent>,
+}
+
+// リプレイバッファ
+struct ReplayBuffer {
+    events: VecDeque<UserStreamEvent>,
+    // バッファから破棄したイベントの最大ID
+    evicted_max_id: Option<i64>,
+}
+
+// ユーザーイベントをSSEの接続に配信するハブ
+pub struct UserEventHub {
+    sender: broadcast::Sender<UserStreamEvent>,
+    buffer: Mutex<ReplayBuffer>,
+    replay_buffer_size: usize,
+}
+
+impl UserEventHub {
+    pub fn new(replay_buffer_size: usize, channel_capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(channel_capacity.max(1));
+        UserEventHub {
+            sender,
+            buffer: Mutex::new(ReplayBuffer {
+                events: VecDeque::with_capacity(replay_buffer_size),
+                evicted_max_id: None,
+            }),
+            replay_buffer_size,
+        }
+    }
+
+    // イベントをリプレイバッファに追加して接続中のクライアントに配信
+    pub fn publish(&self, event: UserStreamEvent) {
+        let mut buffer = self.buffer.lock().unwrap();
+
+        // 複数回通知された場合は無視する
+        if buffer.events.iter().any(|e| e.id == event.id) {
+            return;
+        }
+
+        buffer.events.push_back(event.clone());
+        while buffer.events.len() > self.replay_buffer_size {
+            if let Some(evicted) = buffer.events.pop_front() {
+                buffer.evicted_max_id = buffer.evicted_max_id.max(Some(evicted.id));
+            }
+        }
+
+        // 購読者がいない場合のエラーは無視する
+        let _ = self.sender.send(event);
+    }
+
+    // 購読開始（リプレイとライブ配信の間で欠落や重複が起きないようロック中に受信側を作成する）
+    pub fn subscribe(&self, last_event_id: Option<i64>) -> UserEventSubscription {
+        let buffer = self.buffer.lock().unwrap();
+        let receiver = self.sender.subscribe();
+
+        let Some(last_event_id) = last_event_id else {
+            return UserEventSubscription {
+                replay: Vec::new(),
+                expired: false,
+                receiver,
+            };
+        };
+
+        // コミット順とID順が前後する場合があるため、バッファ内の位置を優先して再送範囲を決める
+        let replay: Vec<UserStreamEvent> =
+            match buffer.events.iter().position(|e| e.id == last_event_id) {
+                Some(index) => buffer.events.iter().skip(index + 1).cloned().collect(),
+                None => buffer
+                    .events
+                    .iter()
+                    .filter(|e| e.id > last_event_id)
+                    .cloned()
+                    .collect(),
+            };
+        let expired = match buffer.evicted_max_id {
+            Some(evicted_max_id) => evicted_max_id > last_event_id,
+            None => false,
+        };
+
+        UserEventSubscription {
+            replay,
+            expired,
+            receiver,
+        }
+    }
+}
+
+// ハブのインスタンス（プロセス内で共有）
+static USER_EVENT_HUB: OnceLock<UserEventHub> = OnceLock::new();
+
+// ハブを取得する関数
+pub fn user_event_hub() -> &'static UserEventHub {
+    USER_EVENT_HUB.get_or_init(|| {
+        let config = config::get_config();
+        UserEventHub::new(
+            config.user_events_replay_buffer_size,
+            config.user_events_channel_capacity,
+        )
+    })
+}
+
+// 全レプリカで発生したユーザーイベントをLISTEN/NOTIFYで受信してハブに配信する処理
+pub async fn run_user_event_listener(mut shutdown: ShutdownListener) {
+    while !shutdown.is_triggered() {
+        // 受信処理は書き込みを行わないため、シャットダウン時はそのまま中断する
+        tokio::select! {
+            result = listen_user_events(user_event_hub()) => {
+                if let Err(err) = result {
+                    tracing::error!("[UserEventListener] ユーザーイベントの受信エラー: {}", err);
+                }
+            }
+            _ = shutdown.wait() => break,
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(LISTENER_RETRY_INTERVAL) => {}
+            _ = shutdown.wait() => {}
+        }
+    }
+
+    tracing::info!("[UserEventListener] stop listening !!");
+}
+
+// 通知を受信し続ける処理（接続エラー時に戻る）
+async fn listen_user_events(hub: &UserEventHub) -> Result<(), String> {
+    // 環境変数取得
+    let config = config::get_config();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_lazy_with(pg_connect_options(config)?);
+    let mut listener = PgListener::connect_with(&pool)
+        .await
+        .map_err(|err| format!("LISTEN用のDB接続エラー: {}", err))?;
+    listener
+        .listen(OUTBOX_NOTIFY_CHANNEL)
+        .await
+        .map_err(|err| format!("LISTENエラー: {}", err))?;
+    let db = db_connection()
+        .await
+        .map_err(|err| format!("DB接続エラー: {}", err))?;
+
+    tracing::info!(
+        "[UserEventListener] start listening {} !!",
+        OUTBOX_NOTIFY_CHANNEL
+    );
+
+    loop {
+        let notification = listener
+            .recv()
+            .await
+            .map_err(|err| format!("通知の受信エラー: {}", err))?;
+
+        let id: i64 = match notification.payload().parse() {
+            Ok(id) => id,
+            Err(_) => {
+                tracing::warn!(
+                    "[UserEventListener] 不正な通知のため無視します: {}",
+                    notification.payload()
+                );
+                continue;
+            }
+        };
+
+        let outbox_event = match get_outbox_event_from_id(&db, id).await {
+            Ok(Some(outbox_event)) => outbox_event,
+            Ok(None) => continue,
+            Err(err) => return Err(format!("イベント取得エラー: {}", err)),
+        };
+
+        // ユーザーのイベントのみ配信する
+        let event: CloudEvent = match serde_json::from_value(outbox_event.payload) {
+            Ok(event) => event,
+            Err(err) => {
+                tracing::warn!(
+                    "[UserEventListener] イベントの変換エラー（id={}）: {}",
+                    id,
+                    err
+                );
+                continue;
+            }
+        };
+        if event.source != EVENT_SOURCE_USERS {
+            continue;
+        }
+
+        hub.publish(UserStreamEvent { id, event });
+    }
+}
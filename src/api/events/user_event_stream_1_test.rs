@@ -0,0 +1,80 @@
+#[cfg(test)]
+// UserEventHubのテスト
+mod user_event_hub_test {
+    use crate::api::events::cloud_event::{
+        CloudEvent, EVENT_SOURCE_USERS, EVENT_TYPE_USER_CREATED,
+    };
+    use crate::api::events::user_event_stream::{UserEventHub, UserStreamEvent};
+
+    // テスト用のイベント作成
+    fn new_event(id: i64) -> UserStreamEvent {
+        UserStreamEvent {
+            id,
+            event: CloudEvent::new(
+                EVENT_SOURCE_USERS,
+                EVENT_TYPE_USER_CREATED,
+                Some(format!("uid-{}", id)),
+                serde_json::json!({}),
+            ),
+        }
+    }
+
+    // イベントIDの一覧を返す関数
+    fn ids(events: &[UserStreamEvent]) -> Vec<i64> {
+        events.iter().map(|e| e.id).collect()
+    }
+
+    #[tokio::test]
+    async fn it_delivers_live_events() {
+        let hub = UserEventHub::new(10, 10);
+        let mut subscription = hub.subscribe(None);
+        assert!(subscription.replay.is_empty());
+        assert!(!subscription.expired);
+
+        hub.publish(new_event(1));
+        hub.publish(new_event(2));
+        // 同じイベントが複数回通知された場合は配信しない
+        hub.publish(new_event(2));
+
+        assert_eq!(subscription.receiver.recv().await.unwrap().id, 1);
+        assert_eq!(subscription.receiver.recv().await.unwrap().id, 2);
+        assert!(subscription.receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn it_replays_events_after_last_event_id() {
+        let hub = UserEventHub::new(10, 10);
+        hub.publish(new_event(1));
+        hub.publish(new_event(3));
+        // コミット順によりIDが前後して届いた場合もバッファ内の順序で再送する
+        hub.publish(new_event(2));
+        hub.publish(new_event(4));
+
+        let subscription = hub.subscribe(Some(3));
+        assert_eq!(ids(&subscription.replay), vec![2, 4]);
+        assert!(!subscription.expired);
+
+        // バッファにないIDの場合はIDが大きいイベントを再送する
+        let subscription = hub.subscribe(Some(0));
+        assert_eq!(ids(&subscription.replay), vec![1, 3, 2, 4]);
+        assert!(!subscription.expired);
+    }
+
+    #[tokio::test]
+    async fn it_expires_when_replay_buffer_overflows() {
+        let hub = UserEventHub::new(2, 10);
+        for id in 1..=4 {
+            hub.publish(new_event(id));
+        }
+
+        // 破棄済みのイベントがある場合はリセットが必要
+        let subscription = hub.subscribe(Some(1));
+        assert_eq!(ids(&subscription.replay), vec![3, 4]);
+        assert!(subscription.expired);
+
+        // 破棄済みのイベントを受信済みの場合は再送のみ
+        let subscription = hub.subscribe(Some(2));
+        assert_eq!(ids(&subscription.replay), vec![3, 4]);
+        assert!(!subscription.expired);
+    }
+}
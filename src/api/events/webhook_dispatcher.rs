@@ -0,0 +1,198 @@
+// SeaORM
+use sea_orm::{DbErr, TransactionTrait};
+
+// 標準ライブラリ
+use std::collections::HashMap;
+use std::time::Duration;
+
+// configsモジュール
+use crate::api::configs::config::Config;
+
+// DB接続用のモジュール
+use crate::api::databases::database::db_connection;
+
+// イベント用のモジュール
+use crate::api::events::cloud_event::CloudEvent;
+use crate::api::events::outbox_relay::backoff_delay;
+use crate::api::events::webhook_sender::{WebhookSendResult, send_webhook, webhook_client};
+
+// シャットダウン用のモジュール
+use crate::api::lifecycle::shutdown::ShutdownListener;
+
+// Webhook用のモジュール
+use crate::api::repositories::webhooks::webhooks_repository::{
+    claim_pending_deliveries, find_active_webhooks, mark_delivery_result,
+};
+
+// Webhookの配信設定の構造体
+#[derive(Clone, Debug)]
+pub struct WebhookDispatcherSetting {
+    pub poll_interval: Duration,
+    pub batch_size: u64,
+    pub max_attempts: i32,
+    pub backoff_base: Duration,
+    pub backoff_max: Duration,
+    pub timeout: Duration,
+}
+
+impl WebhookDispatcherSetting {
+    // 環境変数から設定を作成
+    pub fn from_config(config: &Config) -> Self {
+        WebhookDispatcherSetting {
+            poll_interval: config.webhook_poll_interval,
+            batch_size: config.webhook_batch_size,
+            max_attempts: config.webhook_max_attempts,
+            backoff_base: config.webhook_backoff_base,
+            backoff_max: config.webhook_backoff_max,
+            timeout: config.webhook_timeout,
+        }
+    }
+
+    // 送信中の配信を他のレプリカが再取得しない期間
+    // バッチの全ての配信の送信がタイムアウトしても期限切れにならない時間とする
+    pub fn lease(&self) -> Duration {
+        let count = u32::try_from(self.batch_size).unwrap_or(u32::MAX);
+        self.timeout.saturating_mul(count.saturating_add(1))
+    }
+}
+
+// 未送信のWebhookを署名付きで配信する構造体
+pub struct WebhookDispatcher {
+    client: reqwest::Client,
+    setting: WebhookDispatcherSetting,
+}
+
+impl WebhookDispatcher {
+    // 初期化用メソッド
+    pub fn new(setting: WebhookDispatcherSetting) -> Self {
+        WebhookDispatcher {
+            client: webhook_client(setting.timeout),
+            setting,
+        }
+    }
+
+    // 環境変数から作成（無効な場合はNone）
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if !config.webhook_dispatcher_enabled {
+            return None;
+        }
+
+        Some(WebhookDispatcher::new(
+            WebhookDispatcherSetting::from_config(config),
+        ))
+    }
+
+    // 送信対象の配信を1バッチ分処理し、送信に成功した件数を返す
+    // 送信中にDB接続や行ロックを保持しないよう、取得と結果の記録は別のトランザクションで行う
+    pub async fn dispatch_once(&self) -> Result<usize, DbErr> {
+        let db = db_connection().await?;
+        let deliveries =
+            claim_pending_deliveries(&db, self.setting.batch_size, self.setting.lease()).await?;
+        if deliveries.is_empty() {
+            return Ok(0);
+        }
+
+        // 配信先の購読情報
+        let webhooks: HashMap<String, _> = find_active_webhooks(&db)
+            .await?
+            .into_iter()
+            .map(|webhook| (webhook.uid.clone(), webhook))
+            .collect();
+
+        // 配信（結果は送信後にまとめて記録する）
+        let mut results = Vec::with_capacity(deliveries.len());
+        for delivery in deliveries {
+            let attempts = delivery.attempts + 1;
+
+            // 購読が削除済みの場合やペイロードが不正な場合はリトライせずデッドレターにする
+            let webhook = match webhooks.get(&delivery.subscription_uid) {
+                Some(webhook) => webhook,
+                None => {
+                    let result = WebhookSendResult {
+                        response_status: None,
+                        error: Some("購読が削除されています。".to_string()),
+                    };
+                    results.push((delivery, result, None));
+                    continue;
+                }
+            };
+            let event: CloudEvent = match serde_json::from_value(delivery.payload.clone()) {
+                Ok(event) => event,
+                Err(err) => {
+                    let result = WebhookSendResult {
+                        response_status: None,
+                        error: Some(format!("ペイロードの変換エラー: {}", err)),
+                    };
+                    results.push((delivery, result, None));
+                    continue;
+                }
+            };
+
+            // 署名付きで送信（失敗時は指数バックオフでリトライし、上限に達したらデッドレター）
+            let result = send_webhook(&self.client, &webhook.url, &webhook.secret, &event).await;
+            if result.is_success() {
+                results.push((delivery, result, None));
+                continue;
+            }
+
+            let next_attempt_at = if attempts >= self.setting.max_attempts {
+                None
+            } else {
+                let delay = backoff_delay(
+                    attempts,
+                    self.setting.backoff_base,
+                    self.setting.backoff_max,
+                );
+                let delay = chrono::Duration::from_std(delay).unwrap_or_default();
+                Some((chrono::Utc::now() + delay).into())
+            };
+            tracing::error!(
+                "[WebhookDispatcher.dispatch_once] delivery_id={} attempts={} {}",
+                delivery.id,
+                attempts,
+                result.error.clone().unwrap_or_default()
+            );
+            results.push((delivery, result, next_attempt_at));
+        }
+
+        let tx = db.begin().await?;
+        let mut succeeded = 0;
+        for (delivery, result, next_attempt_at) in results {
+            let success = result.is_success();
+            if !mark_delivery_result(&tx, &delivery, result, next_attempt_at).await? {
+                // 期限切れで他のレプリカが取得し直した場合は、そちらの結果を優先する
+                tracing::warn!(
+                    "[WebhookDispatcher.dispatch_once] delivery_id={} 送信中の期限が切れたため結果を記録しません",
+                    delivery.id
+                );
+                continue;
+            }
+            if success {
+                succeeded += 1;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(succeeded)
+    }
+
+    // 一定間隔で配信処理を実行
+    pub async fn run(self, mut shutdown: ShutdownListener) {
+        tracing::info!(
+            "Start webhook dispatcher (interval:{}ms) !!",
+            self.setting.poll_interval.as_millis()
+        );
+
+        while !shutdown.is_triggered() {
+            if let Err(err) = self.dispatch_once().await {
+                tracing::error!("[WebhookDispatcher.run] 配信処理エラー: {}", err);
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(self.setting.poll_interval) => {}
+                _ = shutdown.wait() => {}
+            }
+        }
+
+        tracing::info!("Stop webhook dispatcher !!");
+    }
+}
@@ -0,0 +1,309 @@
+#[cfg(test)]
+use test_env_helpers::*;
+
+#[before_each]
+#[after_each]
+#[cfg(test)]
+// WebhookDispatcherのテスト
+mod webhook_dispatcher_test {
+    use crate::api::databases::database::db_connection;
+    use crate::api::entities::prelude::{
+        Outbox, Users, WebhookDeliveries, WebhookDeliveriesActiveModel, WebhookDeliveriesModel,
+        WebhookSubscriptions, WebhookSubscriptionsActiveModel,
+    };
+    use crate::api::events::cloud_event::{CloudEvent, EVENT_TYPE_USER_CREATED};
+    use crate::api::events::event_publisher::WebhookEventPublisher;
+    use crate::api::events::outbox_relay::{OutboxRelay, OutboxRelaySetting};
+    use crate::api::events::webhook_dispatcher::{WebhookDispatcher, WebhookDispatcherSetting};
+    use crate::api::events::webhook_sender::WebhookSendResult;
+    use crate::api::events::webhook_sender::{
+        HEADER_WEBHOOK_SIGNATURE, HEADER_WEBHOOK_TIMESTAMP, verify,
+    };
+    use crate::api::repositories::webhooks::webhooks_repository::{
+        claim_pending_deliveries, mark_delivery_result,
+    };
+    use axum::{Router, body::Bytes, http::HeaderMap, http::StatusCode, routing::post};
+    use sea_orm::{
+        ActiveModelTrait, ConnectionTrait, DbBackend, EntityTrait, Set, Statement, TransactionTrait,
+    };
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    // テスト前に実行する処理
+    async fn before_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // 関連するテーブルのデータを全て削除
+        Users::delete_many().exec(&db).await.unwrap();
+        Outbox::delete_many().exec(&db).await.unwrap();
+        WebhookSubscriptions::delete_many().exec(&db).await.unwrap();
+        WebhookDeliveries::delete_many().exec(&db).await.unwrap();
+    }
+
+    // テスト後に実行する処理
+    async fn after_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // 関連するテーブルのデータを全て削除
+        Users::delete_many().exec(&db).await.unwrap();
+        Outbox::delete_many().exec(&db).await.unwrap();
+        WebhookSubscriptions::delete_many().exec(&db).await.unwrap();
+        WebhookDeliveries::delete_many().exec(&db).await.unwrap();
+    }
+
+    // 受信したリクエスト
+    type Received = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    // Webhookの受信側の代わりにローカルでaxumを起動する関数
+    async fn start_receiver(status: StatusCode) -> (String, Received) {
+        let received: Received = Arc::new(Mutex::new(Vec::new()));
+        let state = received.clone();
+        let app = Router::new().route(
+            "/webhooks",
+            post(move |headers: HeaderMap, body: Bytes| {
+                let state = state.clone();
+                async move {
+                    state.lock().unwrap().push((headers, body));
+                    status
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        (format!("http://{}/webhooks", addr), received)
+    }
+
+    // DBに購読を登録する関数
+    async fn create_webhook(url: &str, event_types: serde_json::Value) {
+        let db = db_connection().await.unwrap();
+        let webhook = WebhookSubscriptionsActiveModel {
+            uid: Set(uuid::Uuid::new_v4().to_string()),
+            url: Set(url.to_string()),
+            event_types: Set(event_types),
+            secret: Set("test-secret-0123456789".to_string()),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+
+        // ログ等に出力しても署名用のシークレットを含めない
+        assert!(!format!("{:?}", webhook).contains("test-secret-0123456789"));
+    }
+
+    // APIからユーザーを作成し、アウトボックスのイベントを配信に登録する関数
+    async fn create_user_and_relay() {
+        let data = serde_json::json!({
+            "last_name": "田中",
+            "first_name": "太郎",
+            "email": "t.tanaka@example.com"
+        });
+        let client = reqwest::Client::new();
+        let res = client
+            .post("http://localhost:8080/api/v1/user")
+            .json(&data)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 201);
+
+        let setting = OutboxRelaySetting {
+            poll_interval: Duration::from_millis(100),
+            batch_size: 10,
+            max_attempts: 3,
+            backoff_base: Duration::from_secs(1),
+            backoff_max: Duration::from_secs(10),
+            publish_timeout: Duration::from_secs(1),
+        };
+        let relay = OutboxRelay::new(Arc::new(WebhookEventPublisher::new()), setting);
+        assert_eq!(relay.relay_once().await.unwrap(), 1);
+    }
+
+    // テスト用の配信設定
+    fn setting(max_attempts: i32) -> WebhookDispatcherSetting {
+        WebhookDispatcherSetting {
+            poll_interval: Duration::from_millis(100),
+            batch_size: 10,
+            max_attempts,
+            backoff_base: Duration::from_secs(60),
+            backoff_max: Duration::from_secs(600),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    // webhook_deliveriesテーブルのデータを取得する関数
+    async fn find_deliveries() -> Vec<WebhookDeliveriesModel> {
+        let db = db_connection().await.unwrap();
+        WebhookDeliveries::find().all(&db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_delivers_subscribed_events() {
+        let (receiver_url, received) = start_receiver(StatusCode::OK).await;
+        create_webhook(&receiver_url, serde_json::json!([EVENT_TYPE_USER_CREATED])).await;
+        create_webhook(&receiver_url, serde_json::json!(["rust_api.user.deleted"])).await;
+
+        // 購読しているWebhookのみ配信が登録されること
+        create_user_and_relay().await;
+        let deliveries = find_deliveries().await;
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].status, "pending");
+
+        // 配信を実行
+        let dispatcher = WebhookDispatcher::new(setting(3));
+        assert_eq!(dispatcher.dispatch_once().await.unwrap(), 1);
+
+        // 署名付きで受信できること
+        let requests = received.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        let (headers, body) = &requests[0];
+        let timestamp: i64 = headers
+            .get(HEADER_WEBHOOK_TIMESTAMP)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let signature = headers
+            .get(HEADER_WEBHOOK_SIGNATURE)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        let now = chrono::Utc::now().timestamp();
+        assert!(verify(
+            "test-secret-0123456789",
+            timestamp,
+            body,
+            signature,
+            now,
+            300
+        ));
+        let event: CloudEvent = serde_json::from_slice(body).unwrap();
+        assert_eq!(event.event_type, EVENT_TYPE_USER_CREATED);
+
+        // 配信済みになること
+        let deliveries = find_deliveries().await;
+        assert_eq!(deliveries[0].status, "succeeded");
+        assert!(deliveries[0].delivered_at.is_some());
+        assert_eq!(dispatcher.dispatch_once().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn it_moves_to_dead_letter_after_max_attempts() {
+        let (receiver_url, received) = start_receiver(StatusCode::SERVICE_UNAVAILABLE).await;
+        create_webhook(&receiver_url, serde_json::json!(["*"])).await;
+        create_user_and_relay().await;
+
+        // 1回目は次回送信日時を遅らせてリトライ待ち
+        let dispatcher = WebhookDispatcher::new(setting(2));
+        assert_eq!(dispatcher.dispatch_once().await.unwrap(), 0);
+        let deliveries = find_deliveries().await;
+        assert_eq!(deliveries[0].status, "pending");
+        assert_eq!(deliveries[0].attempts, 1);
+        assert_eq!(deliveries[0].response_status, Some(503));
+        assert!(deliveries[0].next_attempt_at > chrono::Utc::now());
+
+        // 次回送信日時を過ぎた場合は再送し、上限に達したらデッドレターにする
+        let db = db_connection().await.unwrap();
+        let mut delivery: WebhookDeliveriesActiveModel = deliveries[0].clone().into();
+        delivery.next_attempt_at = Set(chrono::Utc::now().into());
+        delivery.update(&db).await.unwrap();
+        assert_eq!(dispatcher.dispatch_once().await.unwrap(), 0);
+        let deliveries = find_deliveries().await;
+        assert_eq!(deliveries[0].status, "dead");
+        assert_eq!(deliveries[0].attempts, 2);
+        assert_eq!(received.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn it_does_not_lock_rows_while_sending() {
+        // 応答が遅い送信先
+        let app = Router::new().route(
+            "/webhooks",
+            post(|| async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                StatusCode::OK
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/webhooks", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        create_webhook(&url, serde_json::json!(["*"])).await;
+        create_user_and_relay().await;
+
+        let dispatcher = WebhookDispatcher::new(setting(3));
+        let dispatch = tokio::spawn(async move { dispatcher.dispatch_once().await });
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        // 送信中は行ロックを保持せず、他のレプリカは期限まで取得しない
+        let db = db_connection().await.unwrap();
+        let tx = db.begin().await.unwrap();
+        tx.execute(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT id FROM webhook_deliveries FOR UPDATE NOWAIT",
+        ))
+        .await
+        .unwrap();
+        tx.rollback().await.unwrap();
+        let claimed = claim_pending_deliveries(&db, 10, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(claimed.is_empty());
+
+        assert_eq!(dispatch.await.unwrap().unwrap(), 1);
+        assert_eq!(find_deliveries().await[0].status, "succeeded");
+    }
+
+    #[tokio::test]
+    async fn it_ignores_result_after_lease_is_lost() {
+        create_webhook("http://127.0.0.1:9/webhooks", serde_json::json!(["*"])).await;
+        create_user_and_relay().await;
+
+        let db = db_connection().await.unwrap();
+        let claimed = claim_pending_deliveries(&db, 10, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(claimed.len(), 1);
+
+        // 期限切れで他のレプリカが取得し直した場合
+        let deliveries = find_deliveries().await;
+        assert_eq!(deliveries[0].next_attempt_at, claimed[0].next_attempt_at);
+        let mut delivery: WebhookDeliveriesActiveModel = deliveries[0].clone().into();
+        delivery.next_attempt_at = Set((chrono::Utc::now() + chrono::Duration::minutes(5)).into());
+        delivery.update(&db).await.unwrap();
+
+        // 期限切れの送信結果では上書きしない
+        let result = WebhookSendResult {
+            response_status: Some(200),
+            error: None,
+        };
+        let tx = db.begin().await.unwrap();
+        assert!(
+            !mark_delivery_result(&tx, &claimed[0], result, None)
+                .await
+                .unwrap()
+        );
+        tx.commit().await.unwrap();
+        let deliveries = find_deliveries().await;
+        assert_eq!(deliveries[0].status, "pending");
+        assert_eq!(deliveries[0].attempts, 0);
+    }
+}
@@ -0,0 +1,129 @@
+// HMAC-SHA256
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+// 標準ライブラリ
+use std::time::Duration;
+
+// CloudEvents用のモジュール
+use crate::api::events::cloud_event::CloudEvent;
+
+// OpenTelemetry用のモジュール
+use crate::api::telemetry::otel::send_traced;
+
+// 署名用のHTTPヘッダー
+pub const HEADER_WEBHOOK_ID: &str = "X-Webhook-Id";
+pub const HEADER_WEBHOOK_TIMESTAMP: &str = "X-Webhook-Timestamp";
+pub const HEADER_WEBHOOK_SIGNATURE: &str = "X-Webhook-Signature";
+
+// 署名のバージョン
+const SIGNATURE_VERSION: &str = "v1";
+
+type HmacSha256 = Hmac<Sha256>;
+
+// 署名対象の文字列（「{タイムスタンプ}.{ボディ}」）のHMACを作成する関数
+fn signature_mac(secret: &str, timestamp: i64, body: &[u8]) -> HmacSha256 {
+    // HMACは任意長のキーを受け付けるためエラーにならない
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+// 署名（「v1=16進数のHMAC-SHA256」）を作成する関数
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mac = signature_mac(secret, timestamp, body);
+    format!(
+        "{}={}",
+        SIGNATURE_VERSION,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+// 受信側での署名検証用の関数（タイムスタンプが許容範囲外の場合はリプレイとみなす）
+pub fn verify(
+    secret: &str,
+    timestamp: i64,
+    body: &[u8],
+    signature: &str,
+    now: i64,
+    tolerance_secs: i64,
+) -> bool {
+    if (now - timestamp).abs() > tolerance_secs {
+        return false;
+    }
+
+    let Some(hex_signature) = signature.strip_prefix(&format!("{}=", SIGNATURE_VERSION)) else {
+        return false;
+    };
+    let Ok(bytes) = hex::decode(hex_signature) else {
+        return false;
+    };
+
+    // 定数時間で比較
+    signature_mac(secret, timestamp, body)
+        .verify_slice(&bytes)
+        .is_ok()
+}
+
+// Webhookの送信結果の構造体
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebhookSendResult {
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+}
+
+impl WebhookSendResult {
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+// 送信用のHTTPクライアントを作成する関数
+pub fn webhook_client(timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .unwrap_or_default()
+}
+
+// 署名付きでWebhookを送信する関数
+pub async fn send_webhook(
+    client: &reqwest::Client,
+    url: &str,
+    secret: &str,
+    event: &CloudEvent,
+) -> WebhookSendResult {
+    let body = serde_json::to_vec(event).unwrap_or_default();
+    let timestamp = chrono::Utc::now().timestamp();
+    let signature = sign(secret, timestamp, &body);
+
+    // トレースコンテキストを伝播して送信
+    let result = send_traced(
+        client
+            .post(url)
+            .header("Content-Type", "application/cloudevents+json")
+            .header(HEADER_WEBHOOK_ID, &event.id)
+            .header(HEADER_WEBHOOK_TIMESTAMP, timestamp.to_string())
+            .header(HEADER_WEBHOOK_SIGNATURE, signature)
+            .body(body),
+    )
+    .await;
+
+    match result {
+        Ok(res) if res.status().is_success() => WebhookSendResult {
+            response_status: Some(res.status().as_u16() as i32),
+            error: None,
+        },
+        Ok(res) => WebhookSendResult {
+            response_status: Some(res.status().as_u16() as i32),
+            error: Some(format!("送信先がエラーを返しました。: {}", res.status())),
+        },
+        Err(err) => WebhookSendResult {
+            response_status: None,
+            error: Some(format!("送信エラー: {}", err)),
+        },
+    }
+}
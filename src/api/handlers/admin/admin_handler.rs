@@ -0,0 +1,56 @@
+// axum
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+
+// 変換用のクレート
+use serde::Serialize;
+
+// OpenAPI用
+use utoipa::ToSchema;
+
+// 共通エラー用モジュール
+use crate::api::errors::error;
+
+// configsモジュール
+use crate::api::configs::config_reload::active_config;
+
+// 有効な設定の版のレスポンス
+#[derive(Serialize, ToSchema)]
+pub struct ConfigVersionResponseBody {
+    // 再読み込みで反映するたびに1ずつ増える（起動時は1）
+    #[schema(example = 1)]
+    version: u64,
+    // 反映した日時（RFC 3339形式）
+    #[schema(example = "2026-10-19T00:00:00.000Z")]
+    loaded_at: String,
+    // 再読み込みできる項目の現在の値
+    #[schema(value_type = Object, example = json!({"cors_allow_origins": "http://localhost:3000", "rust_log": "info"}))]
+    reloadable: serde_json::Map<String, serde_json::Value>,
+}
+
+// 有効な設定の版の取得
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/config",
+    description = "有効な設定の版と再読み込みできる項目の現在の値の取得",
+    security(("bearerAuth" = [])),
+    responses(
+        (status = 200, description = "正常終了", body = ConfigVersionResponseBody),
+        (status = 403, description = "Forbidden", body = error::CustomErrorResponseBody),
+        (status = 429, description = "Too Many Requests", body = error::CustomErrorResponseBody),
+    ),
+    tag = "admin",
+)]
+pub async fn get_config_version() -> Response {
+    let active = active_config();
+    let body = ConfigVersionResponseBody {
+        version: active.version,
+        loaded_at: active
+            .loaded_at
+            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        reloadable: active.reloadable_values(),
+    };
+    (StatusCode::OK, Json(body)).into_response()
+}
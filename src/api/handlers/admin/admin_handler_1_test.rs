@@ -0,0 +1,37 @@
+#[cfg(test)]
+// 有効な設定の版の取得のテスト
+mod get_config_version_test {
+    use serde_json::Value;
+
+    #[tokio::test]
+    async fn it_returns_active_config_version() {
+        // リクエストを実行
+        let url = "http://localhost:8080/api/v1/admin/config";
+        let client = reqwest::Client::new();
+        let res = client
+            .get(url)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        assert!(res.headers().contains_key("X-Request-Id"));
+
+        // 版と再読み込みできる項目の値を返す
+        let body: Value = res.json().await.unwrap();
+        assert!(body["version"].as_u64().unwrap() >= 1);
+        assert!(body["loaded_at"].as_str().unwrap().ends_with('Z'));
+        let reloadable = body["reloadable"].as_object().unwrap();
+        assert_eq!(reloadable["cors_allow_origins"], "http://localhost:3000");
+        assert!(reloadable.contains_key("rust_log"));
+        assert!(reloadable["feature_flags"].is_array());
+        assert!(!reloadable.contains_key("database_url"));
+    }
+
+    #[tokio::test]
+    async fn it_requires_authorization() {
+        let url = "http://localhost:8080/api/v1/admin/config";
+        let res = reqwest::get(url).await.unwrap();
+        assert_eq!(res.status(), 400);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod admin_handler;
+
+// テストコード用のモジュール
+mod admin_handler_1_test;
@@ -0,0 +1,76 @@
+// axum
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+
+// json変換用マクロ
+use serde_json::json;
+
+// OpenAPI用
+use utoipa::ToSchema;
+
+// ヘルスチェック用のモジュール
+use crate::api::health::health_check::{HealthReport, STATUS_OK, health_state};
+
+// OpenAPI用の定義
+#[derive(ToSchema)]
+struct LivenessResponseBody {
+    #[allow(dead_code)]
+    #[schema(example = "ok")]
+    status: String,
+}
+
+// 結果に応じたステータスコードでレスポンスを返す
+fn report_response(report: HealthReport) -> Response {
+    let status = if report.is_ok() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report)).into_response()
+}
+
+// ライブネスチェック（プロセスが応答できるか）
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    description = "ライブネスチェック",
+    responses(
+        (status = 200, description = "正常終了", body = LivenessResponseBody),
+    ),
+    tag = "health",
+)]
+pub async fn healthz() -> Response {
+    (StatusCode::OK, Json(json!({ "status": STATUS_OK }))).into_response()
+}
+
+// レディネスチェック（DB、マイグレーション、登録されたチェック、シャットダウン中でないか）
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    description = "レディネスチェック",
+    responses(
+        (status = 200, description = "正常終了", body = HealthReport),
+        (status = 503, description = "Service Unavailable", body = HealthReport),
+    ),
+    tag = "health",
+)]
+pub async fn readyz() -> Response {
+    report_response(health_state().readiness().await)
+}
+
+// スタートアップチェック（起動処理の完了、マイグレーション）
+#[utoipa::path(
+    get,
+    path = "/startupz",
+    description = "スタートアップチェック",
+    responses(
+        (status = 200, description = "正常終了", body = HealthReport),
+        (status = 503, description = "Service Unavailable", body = HealthReport),
+    ),
+    tag = "health",
+)]
+pub async fn startupz() -> Response {
+    report_response(health_state().startup().await)
+}
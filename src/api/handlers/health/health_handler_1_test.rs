@@ -0,0 +1,53 @@
+#[cfg(test)]
+// ヘルスチェックのテスト
+mod health_test {
+    use serde_json::Value;
+
+    async fn get(path: &str) -> (u16, Value) {
+        let res = reqwest::get(format!("http://localhost:8080{}", path))
+            .await
+            .unwrap();
+        let status = res.status().as_u16();
+        (status, res.json::<Value>().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn it_reports_liveness() {
+        let (status, body) = get("/healthz").await;
+        assert_eq!(status, 200);
+        assert_eq!(body["status"], "ok");
+    }
+
+    #[tokio::test]
+    async fn it_reports_readiness_per_check() {
+        let (status, body) = get("/readyz").await;
+        assert_eq!(status, 200);
+        assert_eq!(body["status"], "ok");
+
+        // DBとマイグレーションのチェック結果と処理時間
+        let checks = body["checks"].as_array().unwrap();
+        let names: Vec<&str> = checks.iter().map(|c| c["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["database", "migrations"]);
+        for check in checks {
+            assert_eq!(check["status"], "ok");
+            assert!(check["latency_ms"].as_f64().is_some());
+            assert!(check.get("error").is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn it_reports_startup() {
+        let (status, body) = get("/startupz").await;
+        assert_eq!(status, 200);
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["checks"][0]["name"], "startup");
+    }
+
+    #[tokio::test]
+    async fn it_skips_request_logging_middleware() {
+        // 共通ミドルウェアを通らないためリクエストIDは付与されない
+        let res = reqwest::get("http://localhost:8080/healthz").await.unwrap();
+        assert_eq!(res.status(), 200);
+        assert!(res.headers().get("X-Request-Id").is_none());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod health_handler;
+
+// テストコード用のモジュール
+mod health_handler_1_test;
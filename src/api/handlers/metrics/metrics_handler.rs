@@ -0,0 +1,37 @@
+// axum
+use axum::{
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+
+// DB接続用のモジュール
+use crate::api::databases::database::shared_db_pool;
+
+// メトリクス用のモジュール
+use crate::api::telemetry::metrics::app_metrics;
+
+// Prometheusのテキスト形式のContent-Type
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+// Prometheusのメトリクス取得（メトリクス用のポートでのみ公開）
+pub async fn metrics() -> Response {
+    let metrics = app_metrics();
+
+    // DB接続プールの状態は取得時点の値を記録
+    if let Some(db) = shared_db_pool() {
+        metrics.observe_db_pool(db);
+    }
+
+    match metrics.encode() {
+        Ok(body) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+            body,
+        )
+            .into_response(),
+        Err(err) => {
+            tracing::error!("[metrics] メトリクスの出力エラー: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+use test_env_helpers::*;
+
+#[before_each]
+#[after_each]
+#[cfg(test)]
+// metricsのテスト
+mod metrics_test {
+    use crate::api::databases::database::db_connection;
+    use crate::api::entities::prelude::{Outbox, Users, UsersActiveModel};
+    use sea_orm::{EntityTrait, Set};
+
+    // メトリクスのURL（APIとは別のポート）
+    const METRICS_URL: &str = "http://localhost:9090/metrics";
+
+    // テスト前に実行する処理
+    async fn before_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // ユーザー作成
+        Users::insert(UsersActiveModel {
+            uid: Set("test-metrics-001".to_string()),
+            last_name: Set("田中".to_string()),
+            first_name: Set("太郎".to_string()),
+            email: Set("t.tanaka@example.com".to_string()),
+            ..Default::default()
+        })
+        .exec(&db)
+        .await
+        .unwrap();
+    }
+
+    // テスト後に実行する処理
+    async fn after_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // 関連するテーブルのデータを全て削除
+        Users::delete_many().exec(&db).await.unwrap();
+        Outbox::delete_many().exec(&db).await.unwrap();
+    }
+
+    // メトリクスを取得
+    async fn scrape() -> String {
+        let res = reqwest::get(METRICS_URL).await.unwrap();
+        assert_eq!(res.status(), 200);
+        assert!(
+            res.headers()["Content-Type"]
+                .to_str()
+                .unwrap()
+                .starts_with("text/plain")
+        );
+        res.text().await.unwrap()
+    }
+
+    // カウンターの値を取得（存在しない場合は0）
+    fn counter_value(body: &str, name: &str) -> f64 {
+        body.lines()
+            .find(|line| line.starts_with(&format!("{} ", name)))
+            .and_then(|line| line.split_whitespace().last())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0.0)
+    }
+
+    #[tokio::test]
+    async fn it_labels_requests_with_route_template() {
+        // リクエストを実行
+        let url = "http://localhost:8080/api/v1/user/test-metrics-001";
+        let client = reqwest::Client::new();
+        let res = client
+            .get(url)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+
+        // ルートのテンプレート、ステータスの分類、メソッドでラベル付けされている
+        let body = scrape().await;
+        assert!(body.contains(
+            "http_requests_total{method=\"GET\",route=\"/api/v1/user/{uid}\",status=\"2xx\"}"
+        ));
+        assert!(body.contains(
+            "http_request_duration_seconds_bucket{method=\"GET\",route=\"/api/v1/user/{uid}\",status=\"2xx\",le=\"0.1\"}"
+        ));
+        assert!(!body.contains("test-metrics-001"));
+
+        // 処理中のリクエスト数とDB接続プールの状態
+        assert!(body.contains("http_requests_in_flight "));
+        assert!(body.contains("db_pool_connections{state=\"idle\"}"));
+        assert!(body.contains("db_pool_connections{state=\"in_use\"}"));
+        assert!(body.contains("db_pool_max_connections "));
+    }
+
+    #[tokio::test]
+    async fn it_counts_created_and_deleted_users() {
+        let before = scrape().await;
+        let client = reqwest::Client::new();
+
+        // ユーザー作成
+        let res = client
+            .post("http://localhost:8080/api/v1/user")
+            .header("Content-Type", "application/json")
+            .body(r#"{"last_name":"佐藤","first_name":"花子","email":"h.sato@example.com"}"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 201);
+
+        // ユーザー削除
+        let res = client
+            .delete("http://localhost:8080/api/v1/user/test-metrics-001")
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+
+        let after = scrape().await;
+        assert_eq!(
+            counter_value(&after, "users_created_total"),
+            counter_value(&before, "users_created_total") + 1.0
+        );
+        assert_eq!(
+            counter_value(&after, "users_deleted_total"),
+            counter_value(&before, "users_deleted_total") + 1.0
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod metrics_handler;
+
+// テストコード用のモジュール
+mod metrics_handler_1_test;
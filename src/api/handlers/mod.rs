@@ -1,2 +1,7 @@
+pub mod admin;
+pub mod health;
+pub mod metrics;
 pub mod sample;
 pub mod users;
+pub mod webhooks;
+pub mod ws;
@@ -1,6 +1,5 @@
-#[cfg(test)]
-
 // sample_getのテスト
+#[cfg(test)]
 mod sample_get_test {
     use crate::api::contexts::context::Context;
     use crate::api::repositories::sample::sample_repository::SampleRepository;
@@ -51,6 +50,10 @@ mod sample_get_test {
             header: headers,
             method: "GET".to_string(),
             uri: "/api/v1/sample/get".to_string(),
+            principal: None,
+            trace_id: None,
+            client_ip: None,
+            scheme: "http".to_string(),
         };
 
         // ユースケースの実行
@@ -59,6 +62,9 @@ mod sample_get_test {
         // レスポンスステータスの検証
         assert_eq!(res.status(), 200);
 
+        // リクエストIDのヘッダーは設定されたヘッダー名でrequest_middlewareが設定するため含めない
+        assert!(!res.headers().contains_key("X-Request-Id"));
+
         // レスポンスボディの検証
         let limit = 1024 * 1024;
         let body_bytes = body::to_bytes(res.into_body(), limit).await.unwrap();
@@ -72,6 +78,7 @@ mod sample_get_test {
 }
 
 // sample_get_path_queryのテスト
+#[cfg(test)]
 mod sample_get_path_query_test {
     use serde::{Deserialize, Serialize};
 
@@ -104,6 +111,7 @@ mod sample_get_path_query_test {
 }
 
 // sample_postのテスト
+#[cfg(test)]
 mod sample_post_test {
     use serde::{Deserialize, Serialize};
 
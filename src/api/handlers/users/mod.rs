@@ -6,3 +6,5 @@ mod users_handler_2_test;
 mod users_handler_3_test;
 mod users_handler_4_test;
 mod users_handler_5_test;
+mod users_handler_6_test;
+mod users_handler_7_test;
@@ -1,6 +1,6 @@
 // axum
 use axum::{
-    extract::{Extension, Path},
+    extract::{Extension, Path, Query},
     http::StatusCode,
     response::{IntoResponse, Json, Response},
 };
@@ -15,12 +15,13 @@ use validator::Validate;
 use serde_json::json;
 
 // OpenAPI用
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 // 共通コンテキストの構造体
 use crate::api::contexts::context::Context;
 
 // リポジトリーのモジュール
+use crate::api::repositories::audit_logs::audit_logs_repository::AuditLogsRepository;
 use crate::api::repositories::users::users_repository::UsersRepository;
 
 // サービスのモジュール
@@ -33,9 +34,13 @@ use crate::api::usecases::users::create_user_usecase::{
 use crate::api::usecases::users::delete_user_usecase::{
     DeleteUserCommonService, DeleteUserUsecase,
 };
+use crate::api::usecases::users::get_user_events_usecase::GetUserEventsUsecase;
 use crate::api::usecases::users::get_user_from_uid_usecase::{
     GetUserFromUidCommonService, GetUserFromUidUsecase,
 };
+use crate::api::usecases::users::get_user_history_usecase::{
+    GetUserHistoryCommonService, GetUserHistoryUsecase,
+};
 use crate::api::usecases::users::get_users_usecase::{GetUsersCommonService, GetUsersUsecase};
 use crate::api::usecases::users::update_user_usecase::{
     UpdateUserCommonService, UpdateUserUsecase,
@@ -44,6 +49,15 @@ use crate::api::usecases::users::update_user_usecase::{
 // 共通エラー用モジュール
 use crate::api::errors::error;
 
+// configsモジュール
+use crate::api::configs::config;
+
+// ユーザーイベント配信用のモジュール
+use crate::api::events::user_event_stream::user_event_hub;
+
+// シャットダウン用のモジュール
+use crate::api::lifecycle::shutdown::shutdown;
+
 // ユーザー作成のリクエストボディの構造体
 #[derive(Deserialize, Debug, Validate, ToSchema)]
 pub struct CreateUserRequestBody {
@@ -73,6 +87,20 @@ pub struct UpdateUserRequestBody {
     pub email: Option<String>,
 }
 
+// ユーザー変更履歴取得のクエリパラメータの構造体
+#[derive(Deserialize, Debug, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetUserHistoryQuery {
+    // ページ番号（1始まり）
+    #[param(example = 1)]
+    #[validate(range(min = 1, message = "1以上の値を指定して下さい。"))]
+    pub page: Option<u64>,
+    // 1ページあたりの件数
+    #[param(example = 20)]
+    #[validate(range(min = 1, max = 100, message = "1〜100の値を指定して下さい。"))]
+    pub per_page: Option<u64>,
+}
+
 // OpenAPI用の定義
 #[derive(ToSchema)]
 pub struct UserModelResponseBody {
@@ -102,6 +130,52 @@ pub struct UserModelResponseBody {
     pub deleted_at: Option<String>,
 }
 
+#[derive(ToSchema)]
+pub struct AuditLogModelResponseBody {
+    #[allow(dead_code)]
+    #[schema(example = 1)]
+    pub id: i64,
+    #[allow(dead_code)]
+    #[schema(example = "719cc8f3-6309-4b5a-b554-b8034358c471")]
+    pub user_uid: String,
+    #[allow(dead_code)]
+    #[schema(example = "anonymous")]
+    pub actor: String,
+    #[allow(dead_code)]
+    #[schema(example = "0f9f6a9e-1c3b-4a8e-9a57-3a1a7b1c2d3e")]
+    pub request_id: String,
+    #[allow(dead_code)]
+    #[schema(example = "update")]
+    pub action: String,
+    #[allow(dead_code)]
+    #[schema(value_type = Option<Object>, example = json!({"last_name": "田中"}))]
+    pub before: Option<serde_json::Value>,
+    #[allow(dead_code)]
+    #[schema(value_type = Option<Object>, example = json!({"last_name": "更新"}))]
+    pub after: Option<serde_json::Value>,
+    #[allow(dead_code)]
+    #[schema(format = "date-time", example = "2025-05-15T13:39:39.348822Z")]
+    pub created_at: String,
+    #[allow(dead_code)]
+    #[schema(example = "203.0.113.7")]
+    pub client_ip: Option<String>,
+}
+
+#[derive(ToSchema)]
+struct GetUserHistoryResponseBody {
+    #[allow(dead_code)]
+    audit_logs: Vec<AuditLogModelResponseBody>,
+    #[allow(dead_code)]
+    #[schema(example = 1)]
+    page: u64,
+    #[allow(dead_code)]
+    #[schema(example = 20)]
+    per_page: u64,
+    #[allow(dead_code)]
+    #[schema(example = 3)]
+    total: u64,
+}
+
 #[derive(ToSchema)]
 struct DeleteUserResponseBody {
     #[allow(dead_code)]
@@ -118,6 +192,7 @@ struct DeleteUserResponseBody {
         (status = 201, description = "正常終了", body = UserModelResponseBody),
         (status = 415, description = "Unsupported Media Type"),
         (status = 422, description = "Unprocessable Entity"),
+        (status = 429, description = "Too Many Requests", body = error::CustomErrorResponseBody),
         (status = 500, description = "Internal Server Error", body = error::CustomErrorResponseBody),
     ),
     tag = "users",
@@ -134,7 +209,11 @@ pub async fn create_user(
 
     // サービスのインスタンス化
     let users_repo = Box::new(UsersRepository::new());
-    let users_common_repo = UsersCommonRepository { users_repo };
+    let audit_logs_repo = Box::new(AuditLogsRepository::new());
+    let users_common_repo = UsersCommonRepository {
+        users_repo,
+        audit_logs_repo,
+    };
     let users_service = UsersService::new(users_common_repo);
     let users_common_service = CreateUserCommonService { users_service };
 
@@ -160,7 +239,11 @@ pub async fn create_user(
 pub async fn get_users(Extension(ctx): Extension<Context>) -> Response {
     // サービスのインスタンス化
     let users_repo = Box::new(UsersRepository::new());
-    let users_common_repo = UsersCommonRepository { users_repo };
+    let audit_logs_repo = Box::new(AuditLogsRepository::new());
+    let users_common_repo = UsersCommonRepository {
+        users_repo,
+        audit_logs_repo,
+    };
     let users_service = UsersService::new(users_common_repo);
     let users_common_service = GetUsersCommonService { users_service };
 
@@ -189,7 +272,11 @@ pub async fn get_user_from_uid(
 ) -> Response {
     // サービスのインスタンス化
     let users_repo = Box::new(UsersRepository::new());
-    let users_common_repo = UsersCommonRepository { users_repo };
+    let audit_logs_repo = Box::new(AuditLogsRepository::new());
+    let users_common_repo = UsersCommonRepository {
+        users_repo,
+        audit_logs_repo,
+    };
     let users_service = UsersService::new(users_common_repo);
     let users_common_service = GetUserFromUidCommonService { users_service };
 
@@ -227,7 +314,11 @@ pub async fn update_user(
 
     // サービスのインスタンス化
     let users_repo = Box::new(UsersRepository::new());
-    let users_common_repo = UsersCommonRepository { users_repo };
+    let audit_logs_repo = Box::new(AuditLogsRepository::new());
+    let users_common_repo = UsersCommonRepository {
+        users_repo,
+        audit_logs_repo,
+    };
     let users_service = UsersService::new(users_common_repo);
     let users_common_service = UpdateUserCommonService { users_service };
 
@@ -246,6 +337,7 @@ pub async fn update_user(
     security(("bearerAuth" = [])),
     responses(
         (status = 200, description = "正常終了", body = DeleteUserResponseBody),
+        (status = 403, description = "Forbidden", body = error::CustomErrorResponseBody),
         (status = 500, description = "Internal Server Error", body = error::CustomErrorResponseBody),
     ),
     tag = "users",
@@ -253,7 +345,11 @@ pub async fn update_user(
 pub async fn delete_user(Path(uid): Path<String>, Extension(ctx): Extension<Context>) -> Response {
     // サービスのインスタンス化
     let users_repo = Box::new(UsersRepository::new());
-    let users_common_repo = UsersCommonRepository { users_repo };
+    let audit_logs_repo = Box::new(AuditLogsRepository::new());
+    let users_common_repo = UsersCommonRepository {
+        users_repo,
+        audit_logs_repo,
+    };
     let users_service = UsersService::new(users_common_repo);
     let users_common_service = DeleteUserCommonService { users_service };
 
@@ -263,3 +359,73 @@ pub async fn delete_user(Path(uid): Path<String>, Extension(ctx): Extension<Cont
     };
     usecase.exec(ctx, uid).await
 }
+
+// 対象ユーザーの変更履歴取得
+#[utoipa::path(
+    get,
+    path = "/api/v1/user/{uid}/history",
+    description = "対象ユーザーの変更履歴取得（新しい順）",
+    security(("bearerAuth" = [])),
+    params(GetUserHistoryQuery),
+    responses(
+        (status = 200, description = "正常終了", body = GetUserHistoryResponseBody),
+        (status = 422, description = "Unprocessable Entity"),
+        (status = 500, description = "Internal Server Error", body = error::CustomErrorResponseBody),
+    ),
+    tag = "users",
+)]
+pub async fn get_user_history(
+    Path(uid): Path<String>,
+    Query(query): Query<GetUserHistoryQuery>,
+    Extension(ctx): Extension<Context>,
+) -> Response {
+    // バリデーションチェックを実行
+    if let Err(e) = query.validate() {
+        let msg = Json(json!({ "message": e.to_string()}));
+        return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response();
+    }
+
+    // サービスのインスタンス化
+    let users_repo = Box::new(UsersRepository::new());
+    let audit_logs_repo = Box::new(AuditLogsRepository::new());
+    let users_common_repo = UsersCommonRepository {
+        users_repo,
+        audit_logs_repo,
+    };
+    let users_service = UsersService::new(users_common_repo);
+    let users_common_service = GetUserHistoryCommonService { users_service };
+
+    // ユースケースを実行
+    let usecase = GetUserHistoryUsecase {
+        service: users_common_service,
+    };
+    usecase.exec(ctx, uid, query).await
+}
+
+// ユーザーの作成・更新・削除イベントをSSEで配信
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/events",
+    description = "ユーザーの作成・更新・削除イベントをServer-Sent Eventsで配信（Last-Event-IDヘッダーで切断中のイベントを再送）",
+    security(("bearerAuth" = [])),
+    params(
+        ("Last-Event-ID" = Option<String>, Header, description = "最後に受信したイベントのID"),
+    ),
+    responses(
+        (status = 200, description = "正常終了", content_type = "text/event-stream", body = String),
+        (status = 400, description = "Bad Request"),
+    ),
+    tag = "users",
+)]
+pub async fn get_user_events(Extension(ctx): Extension<Context>) -> Response {
+    // 環境変数取得
+    let config = config::get_config();
+
+    // ユースケースを実行
+    let usecase = GetUserEventsUsecase {
+        hub: user_event_hub(),
+        heartbeat_interval: config.user_events_heartbeat_interval,
+        shutdown: shutdown().subscribe(),
+    };
+    usecase.exec(ctx).await
+}
@@ -0,0 +1,175 @@
+#[cfg(test)]
+use test_env_helpers::*;
+
+#[before_each]
+#[after_each]
+#[cfg(test)]
+// get_user_historyのテスト
+mod get_user_history_test {
+    use crate::api::databases::database::db_connection;
+    use crate::api::entities::prelude::{AuditLogs, AuditLogsModel, Users, UsersModel};
+    use sea_orm::EntityTrait;
+    use serde::{Deserialize, Serialize};
+
+    // テスト前に実行する処理
+    async fn before_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // usersテーブルとaudit_logsテーブルのデータを全て削除
+        Users::delete_many().exec(&db).await.unwrap();
+        AuditLogs::delete_many().exec(&db).await.unwrap();
+    }
+
+    // テスト後に実行する処理
+    async fn after_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // usersテーブルとaudit_logsテーブルのデータを全て削除
+        Users::delete_many().exec(&db).await.unwrap();
+        AuditLogs::delete_many().exec(&db).await.unwrap();
+    }
+
+    // レスポンス結果の構造体
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct HistoryResponse {
+        audit_logs: Vec<AuditLogsModel>,
+        page: u64,
+        per_page: u64,
+        total: u64,
+    }
+
+    #[tokio::test]
+    async fn it_response_ok() {
+        let client = reqwest::Client::new();
+
+        // ユーザー作成
+        let data = serde_json::json!({
+            "last_name": "田中",
+            "first_name": "太郎",
+            "email": "t.tanaka@example.com"
+        });
+        let res = client
+            .post("http://localhost:8080/api/v1/user")
+            .json(&data)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 201);
+        let user: UsersModel = serde_json::from_str(&res.text().await.unwrap()).unwrap();
+
+        // ユーザー更新
+        let url = format!("http://localhost:8080/api/v1/user/{}", user.uid);
+        let data = serde_json::json!({ "last_name": "更新" });
+        // 信頼するプロキシ（テスト環境はlocalhost）を経由したリクエスト
+        let res = client
+            .put(&url)
+            .json(&data)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .header("X-Forwarded-For", "203.0.113.7")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+
+        // ユーザー削除
+        let res = client
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+
+        // リクエストを実行
+        let res = client
+            .get(format!("{}/history?page=1&per_page=2", url))
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+
+        // レスポンスステータスの検証
+        assert_eq!(res.status(), 200);
+        let request_id = res
+            .headers()
+            .get("X-Request-Id")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(!request_id.is_empty());
+
+        // レスポンスボディの検証（新しい順に2件）
+        let text_body = res.text().await.unwrap();
+        let res_body: HistoryResponse = serde_json::from_str(&text_body).unwrap();
+        assert_eq!(res_body.total, 3);
+        assert_eq!(res_body.page, 1);
+        assert_eq!(res_body.per_page, 2);
+        assert_eq!(res_body.audit_logs.len(), 2);
+        assert_eq!(res_body.audit_logs[0].action, "delete");
+        assert_eq!(res_body.audit_logs[1].action, "update");
+        // 操作者は認証済みユーザーの識別子（テスト環境のトークン「xxx」は test-user）
+        assert_eq!(res_body.audit_logs[0].actor, "test-user");
+        assert_eq!(res_body.audit_logs[1].actor, "test-user");
+
+        // クライアントのIPアドレスが記録されること（プロキシを経由した場合は転送元のアドレス）
+        assert_eq!(
+            res_body.audit_logs[1].client_ip.as_deref(),
+            Some("203.0.113.7")
+        );
+        assert!(res_body.audit_logs[0].client_ip.is_some());
+
+        // 更新履歴は変更された項目のみ記録されること
+        let before = res_body.audit_logs[1].before.clone().unwrap();
+        let after = res_body.audit_logs[1].after.clone().unwrap();
+        assert_eq!(before["last_name"], "田中");
+        assert_eq!(after["last_name"], "更新");
+        assert!(after.get("first_name").is_none());
+
+        // 2ページ目には作成履歴が含まれること
+        let res = client
+            .get(format!("{}/history?page=2&per_page=2", url))
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        let text_body = res.text().await.unwrap();
+        let res_body: HistoryResponse = serde_json::from_str(&text_body).unwrap();
+        assert_eq!(res_body.audit_logs.len(), 1);
+        assert_eq!(res_body.audit_logs[0].action, "create");
+        assert!(res_body.audit_logs[0].before.is_none());
+        assert_eq!(
+            res_body.audit_logs[0].after.clone().unwrap()["email"],
+            "t.tanaka@example.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_response_unprocessable_entity() {
+        // リクエストを実行
+        let url = "http://localhost:8080/api/v1/user/test-xxx-yyy-001/history?page=0";
+        let client = reqwest::Client::new();
+        let res = client
+            .get(url)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+
+        // レスポンスステータスの検証
+        assert_eq!(res.status(), 422);
+    }
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+use test_env_helpers::*;
+
+#[before_each]
+#[after_each]
+#[cfg(test)]
+// get_user_eventsのテスト
+mod get_user_events_test {
+    use crate::api::databases::database::db_connection;
+    use crate::api::entities::prelude::{Outbox, Users, UsersModel};
+    use sea_orm::EntityTrait;
+    use std::time::Duration;
+
+    // テスト前に実行する処理
+    async fn before_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // usersテーブルとoutboxテーブルのデータを全て削除
+        Users::delete_many().exec(&db).await.unwrap();
+        Outbox::delete_many().exec(&db).await.unwrap();
+    }
+
+    // テスト後に実行する処理
+    async fn after_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // usersテーブルとoutboxテーブルのデータを全て削除
+        Users::delete_many().exec(&db).await.unwrap();
+        Outbox::delete_many().exec(&db).await.unwrap();
+    }
+
+    // SSEのイベント
+    struct SseEvent {
+        id: String,
+        event: String,
+        data: serde_json::Value,
+    }
+
+    // SSEの接続を開始する関数
+    async fn connect(last_event_id: Option<&str>) -> reqwest::Response {
+        let client = reqwest::Client::new();
+        let mut req = client
+            .get("http://localhost:8080/api/v1/users/events")
+            .header("Authorization", format!("Bearer {}", "xxx"));
+        if let Some(last_event_id) = last_event_id {
+            req = req.header("Last-Event-ID", last_event_id);
+        }
+        let res = req.send().await.unwrap();
+        assert_eq!(res.status(), 200);
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "text/event-stream"
+        );
+        res
+    }
+
+    // 指定した種別のイベントを受信するまで読み込む関数
+    async fn read_event(res: &mut reqwest::Response, buf: &mut String, event: &str) -> SseEvent {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                // 受信済みのメッセージから対象のイベントを探す
+                while let Some(pos) = buf.find("\n\n") {
+                    let message: String = buf.drain(..pos + 2).collect();
+                    let mut sse_event = SseEvent {
+                        id: String::new(),
+                        event: String::new(),
+                        data: serde_json::Value::Null,
+                    };
+                    for line in message.lines() {
+                        if let Some(v) = line.strip_prefix("id: ") {
+                            sse_event.id = v.to_string();
+                        } else if let Some(v) = line.strip_prefix("event: ") {
+                            sse_event.event = v.to_string();
+                        } else if let Some(v) = line.strip_prefix("data: ") {
+                            sse_event.data = serde_json::from_str(v).unwrap();
+                        }
+                    }
+                    if sse_event.event == event {
+                        return sse_event;
+                    }
+                }
+
+                let chunk = res
+                    .chunk()
+                    .await
+                    .unwrap()
+                    .expect("ストリームが終了しました");
+                buf.push_str(&String::from_utf8_lossy(&chunk));
+            }
+        })
+        .await
+        .expect("イベントを受信できませんでした")
+    }
+
+    #[tokio::test]
+    async fn it_streams_and_replays_user_events() {
+        let mut res = connect(None).await;
+        let mut buf = String::new();
+
+        // ユーザー作成
+        let client = reqwest::Client::new();
+        let data = serde_json::json!({
+            "last_name": "田中",
+            "first_name": "太郎",
+            "email": "t.tanaka@example.com"
+        });
+        let create_res = client
+            .post("http://localhost:8080/api/v1/user")
+            .json(&data)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(create_res.status(), 201);
+        let user: UsersModel = serde_json::from_str(&create_res.text().await.unwrap()).unwrap();
+
+        // 作成イベントを受信できること
+        let created = read_event(&mut res, &mut buf, "rust_api.user.created").await;
+        assert_eq!(created.data["subject"], user.uid.as_str());
+        assert_eq!(created.data["data"]["email"], "t.tanaka@example.com");
+
+        // 切断中にユーザーを更新
+        drop(res);
+        let update_res = client
+            .put(format!("http://localhost:8080/api/v1/user/{}", user.uid))
+            .json(&serde_json::json!({ "first_name": "次郎" }))
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(update_res.status(), 200);
+
+        // Last-Event-IDを指定して再接続すると切断中のイベントが再送されること
+        let mut res = connect(Some(&created.id)).await;
+        let mut buf = String::new();
+        let updated = read_event(&mut res, &mut buf, "rust_api.user.updated").await;
+        assert!(updated.id.parse::<i64>().unwrap() > created.id.parse::<i64>().unwrap());
+        assert_eq!(updated.data["data"]["first_name"], "次郎");
+    }
+
+    #[tokio::test]
+    async fn it_response_bad_request() {
+        let client = reqwest::Client::new();
+
+        // 認証ヘッダーなし
+        let res = client
+            .get("http://localhost:8080/api/v1/users/events")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 400);
+
+        // 不正なLast-Event-ID
+        let res = client
+            .get("http://localhost:8080/api/v1/users/events")
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .header("Last-Event-ID", "xxx")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 400);
+    }
+}
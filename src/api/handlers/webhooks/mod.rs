@@ -0,0 +1,5 @@
+pub mod webhooks_handler;
+
+// テストコード用のモジュール
+mod webhooks_handler_1_test;
+mod webhooks_handler_2_test;
@@ -0,0 +1,380 @@
+// axum
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+
+// 変換用のクレート
+use serde::Deserialize;
+
+// バリデーション用のクレート
+use validator::{Validate, ValidationError};
+
+// json変換用マクロ
+use serde_json::json;
+
+// OpenAPI用
+use utoipa::{IntoParams, ToSchema};
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// イベント種別
+use crate::api::events::cloud_event::{
+    EVENT_TYPE_USER_CREATED, EVENT_TYPE_USER_DELETED, EVENT_TYPE_USER_UPDATED,
+};
+
+// リポジトリーのモジュール
+use crate::api::repositories::webhooks::webhooks_repository::{
+    WEBHOOK_EVENT_TYPE_ALL, WebhooksRepository,
+};
+
+// サービスのモジュール
+use crate::api::services::webhooks::webhooks_service::{WebhooksCommonRepository, WebhooksService};
+
+// ユースケースのモジュール
+use crate::api::usecases::webhooks::create_webhook_usecase::{
+    CreateWebhookCommonService, CreateWebhookUsecase,
+};
+use crate::api::usecases::webhooks::delete_webhook_usecase::{
+    DeleteWebhookCommonService, DeleteWebhookUsecase,
+};
+use crate::api::usecases::webhooks::get_webhook_deliveries_usecase::{
+    GetWebhookDeliveriesCommonService, GetWebhookDeliveriesUsecase,
+};
+use crate::api::usecases::webhooks::get_webhook_from_uid_usecase::{
+    GetWebhookFromUidCommonService, GetWebhookFromUidUsecase,
+};
+use crate::api::usecases::webhooks::get_webhooks_usecase::{
+    GetWebhooksCommonService, GetWebhooksUsecase,
+};
+use crate::api::usecases::webhooks::send_test_webhook_usecase::{
+    SendTestWebhookCommonService, SendTestWebhookUsecase,
+};
+
+// 共通エラー用モジュール
+use crate::api::errors::error;
+
+// 購読可能なイベント種別のチェック用関数
+fn validate_event_types(event_types: &[String]) -> Result<(), ValidationError> {
+    let allowed = [
+        EVENT_TYPE_USER_CREATED,
+        EVENT_TYPE_USER_UPDATED,
+        EVENT_TYPE_USER_DELETED,
+        WEBHOOK_EVENT_TYPE_ALL,
+    ];
+    if event_types
+        .iter()
+        .all(|event_type| allowed.contains(&event_type.as_str()))
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::new("event_types")
+            .with_message("購読できないイベント種別が含まれています。".into()))
+    }
+}
+
+// Webhookの購読作成のリクエストボディの構造体
+#[derive(Deserialize, Debug, Validate, ToSchema)]
+pub struct CreateWebhookRequestBody {
+    #[schema(example = "https://partner.example.com/webhooks")]
+    #[validate(url(message = "URL形式で入力して下さい。"))]
+    pub url: String,
+    #[schema(example = json!(["rust_api.user.created", "rust_api.user.deleted"]))]
+    #[validate(
+        length(min = 1, message = "必須項目です。"),
+        custom(function = "validate_event_types")
+    )]
+    pub event_types: Vec<String>,
+    // 未指定の場合は生成する
+    #[schema(example = "whsec_xxxxxxxxxxxxxxxx")]
+    #[validate(length(min = 16, message = "16文字以上で入力して下さい。"))]
+    pub secret: Option<String>,
+}
+
+// 配信ログ取得のクエリパラメータの構造体
+#[derive(Deserialize, Debug, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetWebhookDeliveriesQuery {
+    // ページ番号（1始まり）
+    #[param(example = 1)]
+    #[validate(range(min = 1, message = "1以上の値を指定して下さい。"))]
+    pub page: Option<u64>,
+    // 1ページあたりの件数
+    #[param(example = 20)]
+    #[validate(range(min = 1, max = 100, message = "1〜100の値を指定して下さい。"))]
+    pub per_page: Option<u64>,
+}
+
+// OpenAPI用の定義
+#[derive(ToSchema)]
+pub struct WebhookModelResponseBody {
+    #[allow(dead_code)]
+    #[schema(example = 1)]
+    pub id: i64,
+    #[allow(dead_code)]
+    #[schema(example = "5f3c9f0e-8e0b-4bb4-9a53-2a9f3c1d7e21")]
+    pub uid: String,
+    #[allow(dead_code)]
+    #[schema(example = "https://partner.example.com/webhooks")]
+    pub url: String,
+    #[allow(dead_code)]
+    #[schema(example = json!(["rust_api.user.created"]))]
+    pub event_types: Vec<String>,
+    #[allow(dead_code)]
+    #[schema(format = "date-time", example = "2025-05-15T13:39:39.348822Z")]
+    pub created_at: String,
+    #[allow(dead_code)]
+    #[schema(format = "date-time", example = "2025-05-15T13:39:39.348822Z")]
+    pub updated_at: String,
+    #[allow(dead_code)]
+    #[schema(format = "date-time", example = "null")]
+    pub deleted_at: Option<String>,
+}
+
+#[derive(ToSchema)]
+struct CreateWebhookResponseBody {
+    #[allow(dead_code)]
+    #[schema(inline)]
+    webhook: WebhookModelResponseBody,
+    #[allow(dead_code)]
+    #[schema(example = "whsec_xxxxxxxxxxxxxxxx")]
+    secret: String,
+}
+
+#[derive(ToSchema)]
+pub struct WebhookDeliveryModelResponseBody {
+    #[allow(dead_code)]
+    #[schema(example = 1)]
+    pub id: i64,
+    #[allow(dead_code)]
+    #[schema(example = "5f3c9f0e-8e0b-4bb4-9a53-2a9f3c1d7e21")]
+    pub subscription_uid: String,
+    #[allow(dead_code)]
+    #[schema(example = "0b8f5c3e-2d7a-4c1b-9f6e-1a2b3c4d5e6f")]
+    pub event_id: String,
+    #[allow(dead_code)]
+    #[schema(example = "rust_api.user.created")]
+    pub event_type: String,
+    #[allow(dead_code)]
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    #[allow(dead_code)]
+    #[schema(example = "succeeded")]
+    pub status: String,
+    #[allow(dead_code)]
+    #[schema(example = 1)]
+    pub attempts: i32,
+    #[allow(dead_code)]
+    #[schema(format = "date-time", example = "2025-05-15T13:39:39.348822Z")]
+    pub next_attempt_at: String,
+    #[allow(dead_code)]
+    #[schema(example = 200)]
+    pub response_status: Option<i32>,
+    #[allow(dead_code)]
+    #[schema(example = "null")]
+    pub last_error: Option<String>,
+    #[allow(dead_code)]
+    #[schema(format = "date-time", example = "2025-05-15T13:39:39.348822Z")]
+    pub created_at: String,
+    #[allow(dead_code)]
+    #[schema(format = "date-time", example = "2025-05-15T13:39:39.348822Z")]
+    pub delivered_at: Option<String>,
+}
+
+#[derive(ToSchema)]
+struct GetWebhookDeliveriesResponseBody {
+    #[allow(dead_code)]
+    deliveries: Vec<WebhookDeliveryModelResponseBody>,
+    #[allow(dead_code)]
+    #[schema(example = 1)]
+    page: u64,
+    #[allow(dead_code)]
+    #[schema(example = 20)]
+    per_page: u64,
+    #[allow(dead_code)]
+    #[schema(example = 3)]
+    total: u64,
+}
+
+#[derive(ToSchema)]
+struct DeleteWebhookResponseBody {
+    #[allow(dead_code)]
+    #[schema(example = "OK")]
+    message: String,
+}
+
+// サービスのインスタンス化
+fn webhooks_service() -> WebhooksService {
+    let webhooks_repo = Box::new(WebhooksRepository::new());
+    let webhooks_common_repo = WebhooksCommonRepository { webhooks_repo };
+    WebhooksService::new(webhooks_common_repo)
+}
+
+// Webhookの購読作成
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhook",
+    description = "Webhookの購読作成（署名用のシークレットは作成時のみ返却）",
+    security(("bearerAuth" = [])),
+    responses(
+        (status = 201, description = "正常終了", body = CreateWebhookResponseBody),
+        (status = 415, description = "Unsupported Media Type"),
+        (status = 422, description = "Unprocessable Entity"),
+        (status = 500, description = "Internal Server Error", body = error::CustomErrorResponseBody),
+    ),
+    tag = "webhooks",
+)]
+pub async fn create_webhook(
+    Extension(ctx): Extension<Context>,
+    Json(body): Json<CreateWebhookRequestBody>,
+) -> Response {
+    // バリデーションチェックを実行
+    if let Err(e) = body.validate() {
+        let msg = Json(json!({ "message": e.to_string()}));
+        return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response();
+    }
+
+    // ユースケースを実行
+    let usecase = CreateWebhookUsecase {
+        service: CreateWebhookCommonService {
+            webhooks_service: webhooks_service(),
+        },
+    };
+    usecase.exec(ctx, body).await
+}
+
+// 全ての有効なWebhookの購読取得
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks",
+    description = "全ての有効なWebhookの購読取得",
+    security(("bearerAuth" = [])),
+    responses(
+        (status = 200, description = "正常終了", body = Vec<WebhookModelResponseBody>),
+        (status = 500, description = "Internal Server Error", body = error::CustomErrorResponseBody),
+    ),
+    tag = "webhooks",
+)]
+pub async fn get_webhooks(Extension(ctx): Extension<Context>) -> Response {
+    // ユースケースを実行
+    let usecase = GetWebhooksUsecase {
+        service: GetWebhooksCommonService {
+            webhooks_service: webhooks_service(),
+        },
+    };
+    usecase.exec(ctx).await
+}
+
+// 有効な対象Webhookの購読取得
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhook/{uid}",
+    description = "有効な対象Webhookの購読取得",
+    security(("bearerAuth" = [])),
+    responses(
+        (status = 200, description = "正常終了", body = WebhookModelResponseBody),
+        (status = 404, description = "Not Found", body = error::CustomErrorResponseBody),
+        (status = 500, description = "Internal Server Error", body = error::CustomErrorResponseBody),
+    ),
+    tag = "webhooks",
+)]
+pub async fn get_webhook_from_uid(
+    Path(uid): Path<String>,
+    Extension(ctx): Extension<Context>,
+) -> Response {
+    // ユースケースを実行
+    let usecase = GetWebhookFromUidUsecase {
+        service: GetWebhookFromUidCommonService {
+            webhooks_service: webhooks_service(),
+        },
+    };
+    usecase.exec(ctx, uid).await
+}
+
+// Webhookの購読削除（論理削除）
+#[utoipa::path(
+    delete,
+    path = "/api/v1/webhook/{uid}",
+    description = "対象Webhookの購読削除（論理削除）",
+    security(("bearerAuth" = [])),
+    responses(
+        (status = 200, description = "正常終了", body = DeleteWebhookResponseBody),
+        (status = 404, description = "Not Found", body = error::CustomErrorResponseBody),
+        (status = 500, description = "Internal Server Error", body = error::CustomErrorResponseBody),
+    ),
+    tag = "webhooks",
+)]
+pub async fn delete_webhook(
+    Path(uid): Path<String>,
+    Extension(ctx): Extension<Context>,
+) -> Response {
+    // ユースケースを実行
+    let usecase = DeleteWebhookUsecase {
+        service: DeleteWebhookCommonService {
+            webhooks_service: webhooks_service(),
+        },
+    };
+    usecase.exec(ctx, uid).await
+}
+
+// Webhookの配信ログ取得
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhook/{uid}/deliveries",
+    description = "対象Webhookの配信ログ取得（新しい順）",
+    security(("bearerAuth" = [])),
+    params(GetWebhookDeliveriesQuery),
+    responses(
+        (status = 200, description = "正常終了", body = GetWebhookDeliveriesResponseBody),
+        (status = 422, description = "Unprocessable Entity"),
+        (status = 500, description = "Internal Server Error", body = error::CustomErrorResponseBody),
+    ),
+    tag = "webhooks",
+)]
+pub async fn get_webhook_deliveries(
+    Path(uid): Path<String>,
+    Query(query): Query<GetWebhookDeliveriesQuery>,
+    Extension(ctx): Extension<Context>,
+) -> Response {
+    // バリデーションチェックを実行
+    if let Err(e) = query.validate() {
+        let msg = Json(json!({ "message": e.to_string()}));
+        return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response();
+    }
+
+    // ユースケースを実行
+    let usecase = GetWebhookDeliveriesUsecase {
+        service: GetWebhookDeliveriesCommonService {
+            webhooks_service: webhooks_service(),
+        },
+    };
+    usecase.exec(ctx, uid, query).await
+}
+
+// テストイベントの送信
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhook/{uid}/test",
+    description = "対象Webhookにテストイベントを即時送信し、配信結果を返す",
+    security(("bearerAuth" = [])),
+    responses(
+        (status = 200, description = "正常終了", body = WebhookDeliveryModelResponseBody),
+        (status = 404, description = "Not Found", body = error::CustomErrorResponseBody),
+        (status = 500, description = "Internal Server Error", body = error::CustomErrorResponseBody),
+    ),
+    tag = "webhooks",
+)]
+pub async fn send_test_webhook(
+    Path(uid): Path<String>,
+    Extension(ctx): Extension<Context>,
+) -> Response {
+    // ユースケースを実行
+    let usecase = SendTestWebhookUsecase {
+        service: SendTestWebhookCommonService {
+            webhooks_service: webhooks_service(),
+        },
+    };
+    usecase.exec(ctx, uid).await
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+use test_env_helpers::*;
+
+#[before_each]
+#[after_each]
+#[cfg(test)]
+// Webhookの購読管理のテスト
+mod webhooks_test {
+    use crate::api::databases::database::db_connection;
+    use crate::api::entities::prelude::{WebhookSubscriptions, WebhookSubscriptionsModel};
+    use sea_orm::EntityTrait;
+
+    // テスト前に実行する処理
+    async fn before_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // webhook_subscriptionsテーブルのデータを全て削除
+        WebhookSubscriptions::delete_many().exec(&db).await.unwrap();
+    }
+
+    // テスト後に実行する処理
+    async fn after_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // webhook_subscriptionsテーブルのデータを全て削除
+        WebhookSubscriptions::delete_many().exec(&db).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_response_ok() {
+        let client = reqwest::Client::new();
+
+        // 購読作成
+        let data = serde_json::json!({
+            "url": "https://partner.example.com/webhooks",
+            "event_types": ["rust_api.user.created", "rust_api.user.deleted"]
+        });
+        let res = client
+            .post("http://localhost:8080/api/v1/webhook")
+            .json(&data)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 201);
+
+        // シークレットは作成時のみ返されること
+        let res_body: serde_json::Value = serde_json::from_str(&res.text().await.unwrap()).unwrap();
+        let uid = res_body["uid"].as_str().unwrap().to_string();
+        assert!(res_body["secret"].as_str().unwrap().starts_with("whsec_"));
+        assert_eq!(res_body["url"], "https://partner.example.com/webhooks");
+
+        // 一覧取得
+        let res = client
+            .get("http://localhost:8080/api/v1/webhooks")
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        let text_body = res.text().await.unwrap();
+        assert!(!text_body.contains("secret"));
+        let webhooks: Vec<WebhookSubscriptionsModel> = serde_json::from_str(&text_body).unwrap();
+        assert_eq!(webhooks.len(), 1);
+        assert_eq!(webhooks[0].uid, uid);
+
+        // 個別取得
+        let url = format!("http://localhost:8080/api/v1/webhook/{}", uid);
+        let res = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        let webhook: WebhookSubscriptionsModel =
+            serde_json::from_str(&res.text().await.unwrap()).unwrap();
+        assert_eq!(
+            webhook.event_types,
+            serde_json::json!(["rust_api.user.created", "rust_api.user.deleted"])
+        );
+
+        // 削除
+        let res = client
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+
+        // 削除後は取得できないこと
+        let res = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn it_response_unprocessable_entity() {
+        let client = reqwest::Client::new();
+
+        // 購読できないイベント種別
+        let data = serde_json::json!({
+            "url": "https://partner.example.com/webhooks",
+            "event_types": ["rust_api.unknown"]
+        });
+        let res = client
+            .post("http://localhost:8080/api/v1/webhook")
+            .json(&data)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 422);
+
+        // URL形式でない
+        let data = serde_json::json!({
+            "url": "partner.example.com",
+            "event_types": ["*"]
+        });
+        let res = client
+            .post("http://localhost:8080/api/v1/webhook")
+            .json(&data)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 422);
+    }
+}
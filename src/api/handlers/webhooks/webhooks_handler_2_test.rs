@@ -0,0 +1,240 @@
+#[cfg(test)]
+use test_env_helpers::*;
+
+#[before_each]
+#[after_each]
+#[cfg(test)]
+// send_test_webhookのテスト
+mod send_test_webhook_test {
+    use crate::api::databases::database::db_connection;
+    use crate::api::entities::prelude::{
+        WebhookDeliveries, WebhookDeliveriesModel, WebhookSubscriptions,
+    };
+    use crate::api::events::cloud_event::CloudEvent;
+    use crate::api::events::webhook_sender::{
+        HEADER_WEBHOOK_ID, HEADER_WEBHOOK_SIGNATURE, HEADER_WEBHOOK_TIMESTAMP, verify,
+    };
+    use axum::{Router, body::Bytes, http::HeaderMap, http::StatusCode, routing::post};
+    use sea_orm::EntityTrait;
+    use std::sync::{Arc, Mutex};
+
+    // テスト前に実行する処理
+    async fn before_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // Webhook関連のテーブルのデータを全て削除
+        WebhookSubscriptions::delete_many().exec(&db).await.unwrap();
+        WebhookDeliveries::delete_many().exec(&db).await.unwrap();
+    }
+
+    // テスト後に実行する処理
+    async fn after_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // Webhook関連のテーブルのデータを全て削除
+        WebhookSubscriptions::delete_many().exec(&db).await.unwrap();
+        WebhookDeliveries::delete_many().exec(&db).await.unwrap();
+    }
+
+    // 受信したリクエスト
+    type Received = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    // Webhookの受信側の代わりにローカルでaxumを起動する関数
+    async fn start_receiver(status: StatusCode) -> (String, Received) {
+        let received: Received = Arc::new(Mutex::new(Vec::new()));
+        let state = received.clone();
+        let app = Router::new().route(
+            "/webhooks",
+            post(move |headers: HeaderMap, body: Bytes| {
+                let state = state.clone();
+                async move {
+                    state.lock().unwrap().push((headers, body));
+                    status
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        (format!("http://{}/webhooks", addr), received)
+    }
+
+    // APIから購読を作成し、Uidとシークレットを返す関数
+    async fn create_webhook(url: &str) -> (String, String) {
+        let data = serde_json::json!({
+            "url": url,
+            "event_types": ["*"],
+            "secret": "test-secret-0123456789"
+        });
+        let client = reqwest::Client::new();
+        let res = client
+            .post("http://localhost:8080/api/v1/webhook")
+            .json(&data)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 201);
+        let res_body: serde_json::Value = serde_json::from_str(&res.text().await.unwrap()).unwrap();
+
+        (
+            res_body["uid"].as_str().unwrap().to_string(),
+            res_body["secret"].as_str().unwrap().to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn it_response_ok() {
+        let (receiver_url, received) = start_receiver(StatusCode::OK).await;
+        let (uid, secret) = create_webhook(&receiver_url).await;
+
+        // リクエストを実行
+        let client = reqwest::Client::new();
+        let url = format!("http://localhost:8080/api/v1/webhook/{}/test", uid);
+        let res = client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+
+        // レスポンスの検証
+        assert_eq!(res.status(), 200);
+        let delivery: WebhookDeliveriesModel =
+            serde_json::from_str(&res.text().await.unwrap()).unwrap();
+        assert_eq!(delivery.status, "succeeded");
+        assert_eq!(delivery.response_status, Some(200));
+        assert_eq!(delivery.attempts, 1);
+
+        // 受信側で署名が検証できること
+        let requests = received.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        let (headers, body) = &requests[0];
+        let timestamp: i64 = headers
+            .get(HEADER_WEBHOOK_TIMESTAMP)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let signature = headers
+            .get(HEADER_WEBHOOK_SIGNATURE)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        let now = chrono::Utc::now().timestamp();
+        assert!(verify(&secret, timestamp, body, signature, now, 300));
+        assert!(!verify(
+            "wrong-secret",
+            timestamp,
+            body,
+            signature,
+            now,
+            300
+        ));
+        assert!(!verify(&secret, timestamp, body, signature, now + 600, 300));
+        let event: CloudEvent = serde_json::from_slice(body).unwrap();
+        assert_eq!(event.event_type, "rust_api.webhook.test");
+        assert_eq!(
+            headers.get(HEADER_WEBHOOK_ID).unwrap().to_str().unwrap(),
+            event.id
+        );
+
+        // 配信ログが取得できること
+        let res = client
+            .get(format!(
+                "http://localhost:8080/api/v1/webhook/{}/deliveries",
+                uid
+            ))
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        let res_body: serde_json::Value = serde_json::from_str(&res.text().await.unwrap()).unwrap();
+        assert_eq!(res_body["total"], 1);
+        assert_eq!(res_body["deliveries"][0]["event_id"], event.id.as_str());
+    }
+
+    #[tokio::test]
+    async fn it_forwards_request_id() {
+        let (receiver_url, received) = start_receiver(StatusCode::OK).await;
+        let (uid, _secret) = create_webhook(&receiver_url).await;
+
+        // 呼び出し元のリクエストIDを指定してリクエストを実行
+        let client = reqwest::Client::new();
+        let url = format!("http://localhost:8080/api/v1/webhook/{}/test", uid);
+        let res = client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .header("X-Request-Id", "gateway-webhook-0001")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+
+        // 外部への送信時にリクエストIDが転送されること
+        let requests = received.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        let (headers, _body) = &requests[0];
+        assert_eq!(
+            headers.get("X-Request-Id").unwrap().to_str().unwrap(),
+            "gateway-webhook-0001"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_records_failed_delivery() {
+        let (receiver_url, received) = start_receiver(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let (uid, _secret) = create_webhook(&receiver_url).await;
+
+        // リクエストを実行
+        let client = reqwest::Client::new();
+        let url = format!("http://localhost:8080/api/v1/webhook/{}/test", uid);
+        let res = client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+
+        // 送信先のエラーが配信ログに記録されること
+        assert_eq!(res.status(), 200);
+        let delivery: WebhookDeliveriesModel =
+            serde_json::from_str(&res.text().await.unwrap()).unwrap();
+        assert_eq!(delivery.status, "failed");
+        assert_eq!(delivery.response_status, Some(500));
+        assert!(delivery.last_error.is_some());
+        assert_eq!(received.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn it_response_not_found() {
+        // リクエストを実行
+        let client = reqwest::Client::new();
+        let res = client
+            .post("http://localhost:8080/api/v1/webhook/test-xxx-yyy-001/test")
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+
+        // レスポンスステータスの検証
+        assert_eq!(res.status(), 404);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod ws_handler;
+
+// テストコード用のモジュール
+mod ws_handler_1_test;
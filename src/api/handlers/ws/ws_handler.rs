@@ -0,0 +1,49 @@
+// axum
+use axum::{
+    extract::{Extension, ws::WebSocketUpgrade},
+    response::Response,
+};
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// configsモジュール
+use crate::api::configs::config;
+use crate::api::configs::config_reload::active_config;
+
+// ユーザーイベント配信用のモジュール
+use crate::api::events::user_event_stream::user_event_hub;
+
+// シャットダウン用のモジュール
+use crate::api::lifecycle::shutdown::shutdown;
+
+// ユースケースのモジュール
+use crate::api::usecases::ws::ws_session_usecase::{WsSessionSetting, WsSessionUsecase};
+
+// WebSocket接続（購読したトピックのユーザーイベントを配信）
+#[utoipa::path(
+    get,
+    path = "/api/v1/ws",
+    description = "WebSocket接続。JSON形式のメッセージ（{\"type\":\"subscribe\",\"topic\":\"user:{uid}\"}、{\"type\":\"unsubscribe\",\"topic\":\"users\"}、{\"type\":\"ping\"}）でトピックを購読し、ユーザーイベントを受信する",
+    security(("bearerAuth" = [])),
+    responses(
+        (status = 101, description = "Switching Protocols"),
+        (status = 400, description = "Bad Request"),
+    ),
+    tag = "ws",
+)]
+pub async fn ws(Extension(ctx): Extension<Context>, upgrade: WebSocketUpgrade) -> Response {
+    // 環境変数取得
+    let config = config::get_config();
+
+    // ユースケースを実行
+    let usecase = WsSessionUsecase {
+        hub: user_event_hub(),
+        // 再読み込みした設定（流量制限等）を新しい接続から反映する
+        setting: WsSessionSetting::from_config(&active_config().config),
+        shutdown: shutdown().subscribe(),
+    };
+    upgrade
+        .max_message_size(config.ws_max_message_bytes)
+        .on_upgrade(move |socket| async move { usecase.exec(ctx, socket).await })
+}
@@ -0,0 +1,235 @@
+#[cfg(test)]
+use test_env_helpers::*;
+
+#[before_each]
+#[after_each]
+#[cfg(test)]
+// wsのテスト
+mod ws_test {
+    use crate::api::databases::database::db_connection;
+    use crate::api::entities::prelude::{Outbox, Users};
+    use crate::api::usecases::ws::ws_session_usecase::WsServerMessage;
+    use futures_util::{SinkExt, StreamExt};
+    use sea_orm::EntityTrait;
+    use std::time::Duration;
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::{
+        MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message,
+        tungstenite::client::IntoClientRequest,
+    };
+
+    type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    // テスト前に実行する処理
+    async fn before_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // usersテーブルとoutboxテーブルのデータを全て削除
+        Users::delete_many().exec(&db).await.unwrap();
+        Outbox::delete_many().exec(&db).await.unwrap();
+    }
+
+    // テスト後に実行する処理
+    async fn after_each() {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                panic!("DB接続エラー: {}", err);
+            }
+        };
+
+        // usersテーブルとoutboxテーブルのデータを全て削除
+        Users::delete_many().exec(&db).await.unwrap();
+        Outbox::delete_many().exec(&db).await.unwrap();
+    }
+
+    // WebSocketの接続を開始する関数
+    async fn connect() -> WsStream {
+        let mut req = "ws://localhost:8080/api/v1/ws"
+            .into_client_request()
+            .unwrap();
+        req.headers_mut()
+            .insert("Authorization", "Bearer xxx".parse().unwrap());
+        let (stream, res) = connect_async(req).await.unwrap();
+        assert!(res.headers().get("X-Request-Id").is_some());
+        stream
+    }
+
+    // JSON形式のメッセージを送信する関数
+    async fn send(stream: &mut WsStream, msg: serde_json::Value) {
+        stream
+            .send(Message::Text(msg.to_string().into()))
+            .await
+            .unwrap();
+    }
+
+    // サーバーからのメッセージを受信する関数（Ping等の制御フレームは読み飛ばす）
+    async fn recv(stream: &mut WsStream) -> WsServerMessage {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                match stream.next().await.unwrap().unwrap() {
+                    Message::Text(text) => return serde_json::from_str(text.as_str()).unwrap(),
+                    Message::Close(frame) => panic!("切断されました: {:?}", frame),
+                    _ => continue,
+                }
+            }
+        })
+        .await
+        .expect("メッセージを受信できませんでした")
+    }
+
+    #[tokio::test]
+    async fn it_delivers_subscribed_topics() {
+        let mut stream = connect().await;
+
+        // ping/pong
+        send(&mut stream, serde_json::json!({ "type": "ping" })).await;
+        assert_eq!(recv(&mut stream).await, WsServerMessage::Pong);
+
+        // 全ユーザーのイベントを購読
+        send(
+            &mut stream,
+            serde_json::json!({ "type": "subscribe", "topic": "users" }),
+        )
+        .await;
+        assert_eq!(
+            recv(&mut stream).await,
+            WsServerMessage::Subscribed {
+                topic: "users".to_string()
+            }
+        );
+
+        // ユーザー作成
+        let client = reqwest::Client::new();
+        let data = serde_json::json!({
+            "last_name": "田中",
+            "first_name": "太郎",
+            "email": "t.tanaka@example.com"
+        });
+        let res = client
+            .post("http://localhost:8080/api/v1/user")
+            .json(&data)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 201);
+        let res_body: serde_json::Value = serde_json::from_str(&res.text().await.unwrap()).unwrap();
+        let uid = res_body["uid"].as_str().unwrap().to_string();
+
+        // 作成イベントを受信できること
+        match recv(&mut stream).await {
+            WsServerMessage::Event { topic, event, .. } => {
+                assert_eq!(topic, "users");
+                assert_eq!(event.event_type, "rust_api.user.created");
+                assert_eq!(event.subject, Some(uid.clone()));
+            }
+            msg => panic!("想定外のメッセージ: {:?}", msg),
+        }
+
+        // 対象ユーザーのみに購読を切り替え
+        let user_topic = format!("user:{}", uid);
+        send(
+            &mut stream,
+            serde_json::json!({ "type": "subscribe", "topic": user_topic }),
+        )
+        .await;
+        assert_eq!(
+            recv(&mut stream).await,
+            WsServerMessage::Subscribed {
+                topic: user_topic.clone()
+            }
+        );
+        send(
+            &mut stream,
+            serde_json::json!({ "type": "unsubscribe", "topic": "users" }),
+        )
+        .await;
+        assert_eq!(
+            recv(&mut stream).await,
+            WsServerMessage::Unsubscribed {
+                topic: "users".to_string()
+            }
+        );
+
+        // ユーザー更新
+        let res = client
+            .put(format!("http://localhost:8080/api/v1/user/{}", uid))
+            .json(&serde_json::json!({ "first_name": "次郎" }))
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+
+        // 更新イベントを対象ユーザーのトピックで受信できること
+        match recv(&mut stream).await {
+            WsServerMessage::Event { topic, event, .. } => {
+                assert_eq!(topic, user_topic);
+                assert_eq!(event.event_type, "rust_api.user.updated");
+            }
+            msg => panic!("想定外のメッセージ: {:?}", msg),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_rejects_invalid_messages() {
+        let mut stream = connect().await;
+
+        // 不正なトピック
+        send(
+            &mut stream,
+            serde_json::json!({ "type": "subscribe", "topic": "admins" }),
+        )
+        .await;
+        match recv(&mut stream).await {
+            WsServerMessage::Error { code, .. } => assert_eq!(code, "invalid_topic"),
+            msg => panic!("想定外のメッセージ: {:?}", msg),
+        }
+
+        // 不正なメッセージ
+        stream.send(Message::Text("xxx".into())).await.unwrap();
+        match recv(&mut stream).await {
+            WsServerMessage::Error { code, .. } => assert_eq!(code, "invalid_message"),
+            msg => panic!("想定外のメッセージ: {:?}", msg),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_limits_message_rate() {
+        let mut stream = connect().await;
+
+        // 流量制限を超えるメッセージを送信
+        for _ in 0..25 {
+            send(&mut stream, serde_json::json!({ "type": "ping" })).await;
+        }
+
+        // 流量制限を超えた分はエラーになること
+        let mut rate_limited = 0;
+        for _ in 0..25 {
+            if let WsServerMessage::Error { code, .. } = recv(&mut stream).await {
+                assert_eq!(code, "rate_limited");
+                rate_limited += 1;
+            }
+        }
+        assert!(rate_limited >= 1);
+    }
+
+    #[tokio::test]
+    async fn it_response_bad_request() {
+        // 認証ヘッダーなし
+        let err = connect_async("ws://localhost:8080/api/v1/ws")
+            .await
+            .unwrap_err();
+        match err {
+            tokio_tungstenite::tungstenite::Error::Http(res) => assert_eq!(res.status(), 400),
+            err => panic!("想定外のエラー: {}", err),
+        }
+    }
+}
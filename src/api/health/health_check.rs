@@ -0,0 +1,234 @@
+// SeaORM
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+
+// 変換用のクレート
+use serde::Serialize;
+
+// OpenAPI用
+use utoipa::ToSchema;
+
+// 標準ライブラリ
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+// configsモジュール
+use crate::api::configs::config;
+
+// DB接続用のモジュール
+use crate::api::databases::database::db_connection;
+
+// 状態の値
+pub const STATUS_OK: &str = "ok";
+pub const STATUS_ERROR: &str = "error";
+pub const STATUS_SHUTTING_DOWN: &str = "shutting_down";
+
+// 起動時に適用済みである必要があるマイグレーション（マイグレーションを追加した場合は更新する）
+pub const REQUIRED_MIGRATION: &str = "m20261019_000006_add_client_ip_to_audit_logs";
+
+// ヘルスチェック用のトレイト（依存先ごとに実装して登録する）
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait HealthCheck: Send + Sync {
+    fn name(&self) -> String;
+    async fn check(&self) -> Result<(), String>;
+}
+
+// チェックごとの結果
+#[derive(Serialize, Clone, Debug, PartialEq, ToSchema)]
+pub struct CheckReport {
+    #[schema(example = "database")]
+    pub name: String,
+    #[schema(example = "ok")]
+    pub status: String,
+    #[schema(example = 1.234)]
+    pub latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ヘルスチェックの結果
+#[derive(Serialize, Clone, Debug, PartialEq, ToSchema)]
+pub struct HealthReport {
+    #[schema(example = "ok")]
+    pub status: String,
+    pub checks: Vec<CheckReport>,
+}
+
+impl HealthReport {
+    pub fn is_ok(&self) -> bool {
+        self.status == STATUS_OK
+    }
+}
+
+// ヘルスチェックの状態（起動完了、シャットダウン中、登録されたチェック）
+pub struct HealthState {
+    timeout: Duration,
+    started: AtomicBool,
+    shutting_down: AtomicBool,
+    readiness_checks: RwLock<Vec<Arc<dyn HealthCheck>>>,
+    startup_checks: RwLock<Vec<Arc<dyn HealthCheck>>>,
+}
+
+impl HealthState {
+    pub fn new(timeout: Duration) -> Self {
+        HealthState {
+            timeout,
+            started: AtomicBool::new(false),
+            shutting_down: AtomicBool::new(false),
+            readiness_checks: RwLock::new(Vec::new()),
+            startup_checks: RwLock::new(Vec::new()),
+        }
+    }
+
+    // レディネスチェックを登録
+    pub fn register_readiness_check(&self, check: Arc<dyn HealthCheck>) {
+        self.readiness_checks.write().unwrap().push(check);
+    }
+
+    // スタートアップチェックを登録
+    pub fn register_startup_check(&self, check: Arc<dyn HealthCheck>) {
+        self.startup_checks.write().unwrap().push(check);
+    }
+
+    // 起動処理の完了を記録
+    pub fn mark_started(&self) {
+        self.started.store(true, Ordering::SeqCst);
+    }
+
+    // シャットダウンの開始を記録（以降のレディネスチェックは失敗する）
+    pub fn begin_shutdown(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    // レディネスチェックを実行
+    pub async fn readiness(&self) -> HealthReport {
+        let checks = self.readiness_checks.read().unwrap().clone();
+        let mut report = self.run_checks(checks).await;
+        if self.is_shutting_down() {
+            report.status = STATUS_SHUTTING_DOWN.to_string();
+        }
+        report
+    }
+
+    // スタートアップチェックを実行（起動処理が完了するまでは失敗する）
+    pub async fn startup(&self) -> HealthReport {
+        let checks = self.startup_checks.read().unwrap().clone();
+        let mut report = self.run_checks(checks).await;
+        let started = self.started.load(Ordering::SeqCst);
+        report.checks.insert(
+            0,
+            CheckReport {
+                name: "startup".to_string(),
+                status: if started { STATUS_OK } else { STATUS_ERROR }.to_string(),
+                latency_ms: 0.0,
+                error: (!started).then(|| "起動処理が完了していません".to_string()),
+            },
+        );
+        if !started {
+            report.status = STATUS_ERROR.to_string();
+        }
+        report
+    }
+
+    // チェックをタイムアウト付きで並列に実行
+    async fn run_checks(&self, checks: Vec<Arc<dyn HealthCheck>>) -> HealthReport {
+        let futures = checks.into_iter().map(|check| async move {
+            let start = Instant::now();
+            let result = match tokio::time::timeout(self.timeout, check.check()).await {
+                Ok(result) => result,
+                Err(_) => Err(format!(
+                    "タイムアウトしました（{}ms）",
+                    self.timeout.as_millis()
+                )),
+            };
+            CheckReport {
+                name: check.name(),
+                status: if result.is_ok() {
+                    STATUS_OK
+                } else {
+                    STATUS_ERROR
+                }
+                .to_string(),
+                latency_ms: start.elapsed().as_secs_f64() * 1000.0,
+                error: result.err(),
+            }
+        });
+        let checks = futures_util::future::join_all(futures).await;
+
+        let status = if checks.iter().all(|c| c.status == STATUS_OK) {
+            STATUS_OK
+        } else {
+            STATUS_ERROR
+        };
+        HealthReport {
+            status: status.to_string(),
+            checks,
+        }
+    }
+}
+
+// DBへの接続確認
+pub struct DatabaseCheck;
+
+#[async_trait::async_trait]
+impl HealthCheck for DatabaseCheck {
+    fn name(&self) -> String {
+        "database".to_string()
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        let db = db_connection().await.map_err(|err| err.to_string())?;
+        db.ping().await.map_err(|err| err.to_string())
+    }
+}
+
+// マイグレーションの適用確認
+pub struct MigrationCheck {
+    pub version: String,
+}
+
+#[async_trait::async_trait]
+impl HealthCheck for MigrationCheck {
+    fn name(&self) -> String {
+        "migrations".to_string()
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        let db = db_connection().await.map_err(|err| err.to_string())?;
+        let row = db
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "SELECT version FROM seaql_migrations WHERE version = $1",
+                [self.version.clone().into()],
+            ))
+            .await
+            .map_err(|err| err.to_string())?;
+        match row {
+            Some(_) => Ok(()),
+            None => Err(format!("マイグレーションが未適用です: {}", self.version)),
+        }
+    }
+}
+
+// ヘルスチェックの状態のインスタンス（プロセス内で共有）
+static HEALTH_STATE: OnceLock<HealthState> = OnceLock::new();
+
+// ヘルスチェックの状態を取得する関数（初回にDBとマイグレーションのチェックを登録する）
+pub fn health_state() -> &'static HealthState {
+    HEALTH_STATE.get_or_init(|| {
+        let config = config::get_config();
+        let state = HealthState::new(config.health_check_timeout);
+        let migration_check = Arc::new(MigrationCheck {
+            version: REQUIRED_MIGRATION.to_string(),
+        });
+        state.register_readiness_check(Arc::new(DatabaseCheck));
+        state.register_readiness_check(migration_check.clone());
+        state.register_startup_check(migration_check);
+        state
+    })
+}
@@ -0,0 +1,87 @@
+#[cfg(test)]
+// ヘルスチェックの状態のテスト
+mod health_check_test {
+    use crate::api::health::health_check::{
+        HealthState, MockHealthCheck, STATUS_ERROR, STATUS_OK, STATUS_SHUTTING_DOWN,
+    };
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn mock_check(name: &str, result: Result<(), String>) -> Arc<MockHealthCheck> {
+        let mut check = MockHealthCheck::new();
+        check.expect_name().return_const(name.to_string());
+        check.expect_check().returning(move || result.clone());
+        Arc::new(check)
+    }
+
+    // 応答しないチェック
+    struct SlowCheck;
+
+    #[async_trait::async_trait]
+    impl crate::api::health::health_check::HealthCheck for SlowCheck {
+        fn name(&self) -> String {
+            "slow".to_string()
+        }
+
+        async fn check(&self) -> Result<(), String> {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_readiness_reports_each_check() {
+        let state = HealthState::new(Duration::from_secs(1));
+        state.register_readiness_check(mock_check("database", Ok(())));
+        state.register_readiness_check(mock_check("cache", Err("接続エラー".to_string())));
+
+        let report = state.readiness().await;
+        assert_eq!(report.status, STATUS_ERROR);
+        assert_eq!(report.checks.len(), 2);
+        assert_eq!(report.checks[0].name, "database");
+        assert_eq!(report.checks[0].status, STATUS_OK);
+        assert_eq!(report.checks[0].error, None);
+        assert_eq!(report.checks[1].name, "cache");
+        assert_eq!(report.checks[1].status, STATUS_ERROR);
+        assert_eq!(report.checks[1].error, Some("接続エラー".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_readiness_times_out() {
+        let state = HealthState::new(Duration::from_millis(50));
+        state.register_readiness_check(Arc::new(SlowCheck));
+
+        let report = state.readiness().await;
+        assert_eq!(report.status, STATUS_ERROR);
+        assert!(report.checks[0].error.as_ref().unwrap().contains("50ms"));
+        assert!(report.checks[0].latency_ms < 1000.0);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_during_shutdown() {
+        let state = HealthState::new(Duration::from_secs(1));
+        state.register_readiness_check(mock_check("database", Ok(())));
+        assert!(state.readiness().await.is_ok());
+
+        state.begin_shutdown();
+        let report = state.readiness().await;
+        assert_eq!(report.status, STATUS_SHUTTING_DOWN);
+        assert!(!report.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_startup_requires_started() {
+        let state = HealthState::new(Duration::from_secs(1));
+        state.register_startup_check(mock_check("migrations", Ok(())));
+
+        let report = state.startup().await;
+        assert_eq!(report.status, STATUS_ERROR);
+        assert_eq!(report.checks[0].name, "startup");
+        assert_eq!(report.checks[0].status, STATUS_ERROR);
+
+        state.mark_started();
+        let report = state.startup().await;
+        assert_eq!(report.status, STATUS_OK);
+        assert_eq!(report.checks.len(), 2);
+    }
+}
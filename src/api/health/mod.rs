@@ -0,0 +1,4 @@
+pub mod health_check;
+
+// テストコード用のモジュール
+mod health_check_1_test;
@@ -0,0 +1,4 @@
+pub mod shutdown;
+
+// テストコード用のモジュール
+mod shutdown_1_test;
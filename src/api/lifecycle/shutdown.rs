@@ -0,0 +1,152 @@
+// tokio
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+// 標準ライブラリ
+use std::sync::OnceLock;
+use std::time::Duration;
+
+// configsモジュール
+use crate::api::configs::config::Config;
+
+// シャットダウンの設定
+#[derive(Clone, Debug)]
+pub struct ShutdownSetting {
+    // 処理中のリクエストとバックグラウンド処理の終了を待つ時間
+    pub drain_timeout: Duration,
+    // レディネスチェックを失敗させてから新規の受付を停止するまでの待ち時間
+    pub readiness_delay: Duration,
+}
+
+impl ShutdownSetting {
+    pub fn from_config(config: &Config) -> Self {
+        ShutdownSetting {
+            drain_timeout: config.shutdown_drain_timeout,
+            readiness_delay: config.shutdown_readiness_delay,
+        }
+    }
+}
+
+// シャットダウンの通知
+pub struct Shutdown {
+    tx: watch::Sender<bool>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (tx, _) = watch::channel(false);
+        Shutdown { tx }
+    }
+
+    // シャットダウンを通知
+    pub fn trigger(&self) {
+        self.tx.send_replace(true);
+    }
+
+    // 通知の受信用
+    pub fn subscribe(&self) -> ShutdownListener {
+        ShutdownListener {
+            rx: self.tx.subscribe(),
+        }
+    }
+}
+
+// シャットダウンの通知の受信用
+#[derive(Clone)]
+pub struct ShutdownListener {
+    rx: watch::Receiver<bool>,
+}
+
+impl ShutdownListener {
+    pub fn is_triggered(&self) -> bool {
+        *self.rx.borrow()
+    }
+
+    // シャットダウンが通知されるまで待つ（通知済みの場合はすぐに戻る）
+    pub async fn wait(&mut self) {
+        let _ = self.rx.wait_for(|triggered| *triggered).await;
+    }
+}
+
+// シャットダウンの通知のインスタンス（プロセス内で共有）
+static SHUTDOWN: OnceLock<Shutdown> = OnceLock::new();
+
+// シャットダウンの通知を取得する関数
+pub fn shutdown() -> &'static Shutdown {
+    SHUTDOWN.get_or_init(Shutdown::new)
+}
+
+// SIGTERMまたはSIGINTを受信するまで待つ関数（受信したシグナル名を返す）
+pub async fn wait_for_signal() -> &'static str {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            tracing::error!("SIGINTの受信設定エラー: {}", err);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                tracing::error!("SIGTERMの受信設定エラー: {}", err);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => "SIGINT",
+        _ = terminate => "SIGTERM",
+    }
+}
+
+// バックグラウンド処理の管理（シャットダウン時に終了を待つ）
+#[derive(Default)]
+pub struct BackgroundWorkers {
+    handles: Vec<(&'static str, JoinHandle<()>)>,
+}
+
+impl BackgroundWorkers {
+    pub fn new() -> Self {
+        BackgroundWorkers {
+            handles: Vec::new(),
+        }
+    }
+
+    pub fn spawn<F>(&mut self, name: &'static str, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.handles.push((name, tokio::spawn(future)));
+    }
+
+    // 全ての処理の終了を待ち、時間内に終了しなかった処理を中断する（中断した処理の数を返す）
+    pub async fn join(self, timeout: Duration) -> usize {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut aborted = 0;
+        for (name, mut handle) in self.handles {
+            match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => tracing::error!("[{}] バックグラウンド処理のエラー: {}", name, err),
+                Err(_) => {
+                    tracing::warn!("[{}] 終了しなかったため中断します", name);
+                    handle.abort();
+                    aborted += 1;
+                }
+            }
+        }
+        aborted
+    }
+}
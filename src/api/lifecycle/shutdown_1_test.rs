@@ -0,0 +1,64 @@
+#[cfg(test)]
+// シャットダウンのテスト
+mod shutdown_test {
+    use crate::api::lifecycle::shutdown::{BackgroundWorkers, Shutdown};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_listener_waits_for_trigger() {
+        let shutdown = Shutdown::new();
+        let mut listener = shutdown.subscribe();
+        assert!(!listener.is_triggered());
+
+        // 通知されるまでは待ち続ける
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), listener.wait())
+                .await
+                .is_err()
+        );
+
+        shutdown.trigger();
+        assert!(listener.is_triggered());
+        tokio::time::timeout(Duration::from_millis(50), listener.wait())
+            .await
+            .unwrap();
+
+        // 通知後に受信を開始した場合もすぐに戻る
+        let mut late = shutdown.subscribe();
+        tokio::time::timeout(Duration::from_millis(50), late.wait())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_workers_finish_on_shutdown() {
+        let shutdown = Shutdown::new();
+        let finished = Arc::new(AtomicBool::new(false));
+
+        let mut workers = BackgroundWorkers::new();
+        let mut listener = shutdown.subscribe();
+        let worker_finished = finished.clone();
+        workers.spawn("worker", async move {
+            listener.wait().await;
+            worker_finished.store(true, Ordering::SeqCst);
+        });
+
+        shutdown.trigger();
+        assert_eq!(workers.join(Duration::from_secs(1)).await, 0);
+        assert!(finished.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_workers_aborted_after_timeout() {
+        let mut workers = BackgroundWorkers::new();
+        workers.spawn("finished", async {});
+        workers.spawn("stuck", async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+
+        // 時間内に終了しなかった処理の数を返す
+        assert_eq!(workers.join(Duration::from_millis(50)).await, 1);
+    }
+}
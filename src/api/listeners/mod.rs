@@ -0,0 +1,4 @@
+pub mod proxy_protocol;
+
+// テストコード用のモジュール
+mod proxy_protocol_1_test;
@@ -0,0 +1,329 @@
+// axum
+use axum::serve::Listener;
+
+// tokio
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+// 標準ライブラリ
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+// configsモジュール
+use crate::api::configs::config::Config;
+
+// 信頼するプロキシ
+use crate::api::contexts::trusted_proxies::TrustedProxies;
+
+// PROXYプロトコルのシグネチャ
+const V1_SIGNATURE: &[u8] = b"PROXY ";
+const V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+
+// v1のヘッダーの最大長（CRLFを含む）とv2の固定長部分の長さ
+const V1_MAX_LENGTH: usize = 107;
+const V2_HEADER_LENGTH: usize = 16;
+
+// v2のコマンドとアドレスファミリー（TCPのみ送信元のアドレスを使用する）
+const V2_COMMAND_LOCAL: u8 = 0x0;
+const V2_COMMAND_PROXY: u8 = 0x1;
+const V2_FAMILY_TCP4: u8 = 0x11;
+const V2_FAMILY_TCP6: u8 = 0x21;
+
+// 受け付けた接続を渡すキューの長さ
+const ACCEPT_QUEUE_SIZE: usize = 1024;
+
+// 接続の受け付けエラー時に再試行するまでの時間
+const ACCEPT_ERROR_DELAY: Duration = Duration::from_secs(1);
+
+// PROXYプロトコルのヘッダーの内容
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProxyHeader {
+    // ヘッダー無し
+    None,
+    // 送信元のアドレスが無い（v1のUNKNOWN、v2のLOCALやTCP以外、接続元のアドレスを使用する）
+    Local,
+    // 送信元のアドレス
+    Proxy(SocketAddr),
+}
+
+// v1のヘッダー（例：PROXY TCP4 192.0.2.1 198.51.100.1 51000 80）を変換する関数
+fn parse_v1(line: &[u8]) -> Result<ProxyHeader, String> {
+    let line = std::str::from_utf8(line).map_err(|_| "v1のヘッダーが不正です".to_string())?;
+    let parts: Vec<&str> = line.split(' ').collect();
+    match parts.as_slice() {
+        ["PROXY", "UNKNOWN", ..] => Ok(ProxyHeader::Local),
+        [
+            "PROXY",
+            family @ ("TCP4" | "TCP6"),
+            src,
+            _dst,
+            src_port,
+            _dst_port,
+        ] => {
+            let ip = src
+                .parse::<IpAddr>()
+                .ok()
+                .filter(|ip| ip.is_ipv4() == (*family == "TCP4"))
+                .ok_or_else(|| format!("v1の送信元のアドレスが不正です: {}", src))?;
+            let port = src_port
+                .parse::<u16>()
+                .map_err(|_| format!("v1の送信元のポートが不正です: {}", src_port))?;
+            Ok(ProxyHeader::Proxy(SocketAddr::new(ip, port)))
+        }
+        _ => Err(format!("v1のヘッダーが不正です: {}", line)),
+    }
+}
+
+// v2のアドレス部分から送信元のアドレスを取得する関数
+fn parse_v2_address(family: u8, address: &[u8]) -> Result<ProxyHeader, String> {
+    let port = |offset: usize| u16::from_be_bytes([address[offset], address[offset + 1]]);
+    match family {
+        V2_FAMILY_TCP4 if address.len() >= 12 => {
+            let ip = Ipv4Addr::new(address[0], address[1], address[2], address[3]);
+            Ok(ProxyHeader::Proxy(SocketAddr::new(ip.into(), port(8))))
+        }
+        V2_FAMILY_TCP6 if address.len() >= 36 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(&address[..16]);
+            let ip = Ipv6Addr::from(octets);
+            Ok(ProxyHeader::Proxy(SocketAddr::new(ip.into(), port(32))))
+        }
+        V2_FAMILY_TCP4 | V2_FAMILY_TCP6 => Err("v2のアドレスの長さが不正です".to_string()),
+        _ => Ok(ProxyHeader::Local),
+    }
+}
+
+// 受信したデータからPROXYプロトコルのヘッダーを取得する関数
+// ヘッダーの内容と長さを返し、判定にデータが足りない場合はNoneを返す
+pub fn parse_proxy_header(buf: &[u8]) -> Result<Option<(ProxyHeader, usize)>, String> {
+    if buf.starts_with(V2_SIGNATURE) {
+        if buf.len() < V2_HEADER_LENGTH {
+            return Ok(None);
+        }
+        let version = buf[12] >> 4;
+        let command = buf[12] & 0x0f;
+        if version != 2 {
+            return Err(format!("v2のバージョンが不正です: {}", version));
+        }
+        let length = V2_HEADER_LENGTH + u16::from_be_bytes([buf[14], buf[15]]) as usize;
+        if buf.len() < length {
+            return Ok(None);
+        }
+        let header = match command {
+            V2_COMMAND_LOCAL => ProxyHeader::Local,
+            V2_COMMAND_PROXY => parse_v2_address(buf[13], &buf[V2_HEADER_LENGTH..length])?,
+            _ => return Err(format!("v2のコマンドが不正です: {}", command)),
+        };
+        return Ok(Some((header, length)));
+    }
+    if buf.starts_with(V1_SIGNATURE) {
+        let searched = &buf[..buf.len().min(V1_MAX_LENGTH)];
+        return match searched.windows(2).position(|w| w == b"\r\n") {
+            Some(end) => Ok(Some((parse_v1(&buf[..end])?, end + 2))),
+            None if buf.len() >= V1_MAX_LENGTH => Err("v1のヘッダーが長すぎます".to_string()),
+            None => Ok(None),
+        };
+    }
+    // シグネチャの途中までしか受信していない場合は続きを待つ
+    if V2_SIGNATURE.starts_with(buf) || V1_SIGNATURE.starts_with(buf) {
+        return Ok(None);
+    }
+    Ok(Some((ProxyHeader::None, 0)))
+}
+
+// PROXYプロトコルのヘッダーを受信する関数（ヘッダーの後に受信したデータも返す）
+pub async fn read_proxy_header<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> io::Result<(ProxyHeader, Vec<u8>)> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 512];
+    loop {
+        match parse_proxy_header(&buf) {
+            Ok(Some((header, length))) => return Ok((header, buf.split_off(length))),
+            Ok(None) => {}
+            Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+        }
+        let n = reader.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+// ヘッダーの確認時に受信したデータを先に返す接続
+pub struct ProxiedStream {
+    prefix: Vec<u8>,
+    pos: usize,
+    inner: TcpStream,
+}
+
+impl ProxiedStream {
+    pub fn new(inner: TcpStream, prefix: Vec<u8>) -> Self {
+        ProxiedStream {
+            prefix,
+            pos: 0,
+            inner,
+        }
+    }
+}
+
+impl AsyncRead for ProxiedStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.pos < this.prefix.len() {
+            let n = buf.remaining().min(this.prefix.len() - this.pos);
+            buf.put_slice(&this.prefix[this.pos..this.pos + n]);
+            this.pos += n;
+            return Poll::Ready(Ok(()));
+        }
+        Pin::new(&mut this.inner).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for ProxiedStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+// PROXYプロトコルの設定
+#[derive(Clone, Debug)]
+pub struct ProxyProtocolSetting {
+    pub enabled: bool,
+    pub timeout: Duration,
+    // ヘッダーを受け付ける接続元
+    pub trusted_proxies: TrustedProxies,
+}
+
+impl ProxyProtocolSetting {
+    pub fn from_config(config: &Config) -> Self {
+        ProxyProtocolSetting {
+            enabled: config.proxy_protocol_enabled,
+            timeout: config.proxy_protocol_timeout,
+            trusted_proxies: TrustedProxies::from_config(config),
+        }
+    }
+
+    // 信頼するプロキシからの接続のみヘッダーを受信し、送信元のアドレスを接続元のアドレスにする
+    // それ以外の接続はそのまま渡す（ヘッダーを送信した場合はHTTPのリクエストとして不正になる）
+    pub async fn accept(
+        &self,
+        mut stream: TcpStream,
+        peer: SocketAddr,
+    ) -> io::Result<(ProxiedStream, SocketAddr)> {
+        if !self.enabled || !self.trusted_proxies.contains(peer.ip()) {
+            return Ok((ProxiedStream::new(stream, Vec::new()), peer));
+        }
+        let (header, rest) = tokio::time::timeout(self.timeout, read_proxy_header(&mut stream))
+            .await
+            .map_err(|_| io::Error::from(io::ErrorKind::TimedOut))??;
+        let addr = match header {
+            ProxyHeader::Proxy(addr) => addr,
+            ProxyHeader::None | ProxyHeader::Local => peer,
+        };
+        Ok((ProxiedStream::new(stream, rest), addr))
+    }
+}
+
+// PROXYプロトコルに対応したリスナー
+// ヘッダーの受信は接続ごとのタスクで行い、受信を待つ間も他の接続を受け付ける
+pub struct ProxyProtocolListener {
+    local_addr: SocketAddr,
+    rx: mpsc::Receiver<(ProxiedStream, SocketAddr)>,
+}
+
+impl ProxyProtocolListener {
+    pub fn new(listener: TcpListener, setting: ProxyProtocolSetting) -> io::Result<Self> {
+        let local_addr = listener.local_addr()?;
+        let (tx, rx) = mpsc::channel(ACCEPT_QUEUE_SIZE);
+        tokio::spawn(accept_loop(listener, Arc::new(setting), tx));
+        Ok(ProxyProtocolListener { local_addr, rx })
+    }
+}
+
+// 接続を受け付ける処理（リスナーの破棄時に終了する）
+async fn accept_loop(
+    listener: TcpListener,
+    setting: Arc<ProxyProtocolSetting>,
+    tx: mpsc::Sender<(ProxiedStream, SocketAddr)>,
+) {
+    loop {
+        let (stream, peer) = tokio::select! {
+            _ = tx.closed() => break,
+            result = listener.accept() => match result {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    tracing::error!("接続の受け付けエラー: {}", err);
+                    tokio::time::sleep(ACCEPT_ERROR_DELAY).await;
+                    continue;
+                }
+            },
+        };
+
+        let setting = setting.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            match setting.accept(stream, peer).await {
+                Ok(accepted) => {
+                    let _ = tx.send(accepted).await;
+                }
+                Err(err) => tracing::warn!(
+                    peer = %peer,
+                    "PROXYプロトコルのヘッダーの受信エラーのため切断しました: {}",
+                    err
+                ),
+            }
+        });
+    }
+}
+
+impl Listener for ProxyProtocolListener {
+    type Io = ProxiedStream;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.rx.recv().await {
+            Some(accepted) => accepted,
+            // 受け付ける処理は終了しないため、ここには到達しない
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
@@ -0,0 +1,196 @@
+#[cfg(test)]
+// PROXYプロトコルのテスト
+mod proxy_protocol_test {
+    use crate::api::contexts::trusted_proxies::TrustedProxies;
+    use crate::api::listeners::proxy_protocol::{
+        ProxiedStream, ProxyHeader, ProxyProtocolListener, ProxyProtocolSetting,
+        parse_proxy_header, read_proxy_header,
+    };
+    use axum::extract::ConnectInfo;
+    use axum::serve::{Listener, ListenerExt};
+    use axum::{Router, routing::get};
+    use std::net::SocketAddr;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    fn addr(value: &str) -> SocketAddr {
+        value.parse().unwrap()
+    }
+
+    // v2のヘッダーを作成する関数
+    fn v2_header(command: u8, family: u8, address: &[u8]) -> Vec<u8> {
+        let mut header = b"\r\n\r\n\0\r\nQUIT\n".to_vec();
+        header.push(0x20 | command);
+        header.push(family);
+        header.extend_from_slice(&(address.len() as u16).to_be_bytes());
+        header.extend_from_slice(address);
+        header
+    }
+
+    #[test]
+    fn test_parse_v1() {
+        let data = b"PROXY TCP4 192.0.2.1 198.51.100.1 51000 80\r\nGET / HTTP/1.1\r\n";
+        let (header, length) = parse_proxy_header(data).unwrap().unwrap();
+        assert_eq!(header, ProxyHeader::Proxy(addr("192.0.2.1:51000")));
+        assert_eq!(&data[length..], b"GET / HTTP/1.1\r\n");
+
+        let data = b"PROXY TCP6 2001:db8::1 2001:db8::2 51000 80\r\n";
+        let (header, _) = parse_proxy_header(data).unwrap().unwrap();
+        assert_eq!(header, ProxyHeader::Proxy(addr("[2001:db8::1]:51000")));
+
+        let data = b"PROXY UNKNOWN\r\n";
+        let (header, length) = parse_proxy_header(data).unwrap().unwrap();
+        assert_eq!((header, length), (ProxyHeader::Local, data.len()));
+
+        // 途中までの場合は続きを待つ
+        assert_eq!(parse_proxy_header(b"PRO").unwrap(), None);
+        assert_eq!(parse_proxy_header(b"PROXY TCP4 192.0.2.1").unwrap(), None);
+
+        // 不正な値
+        assert!(parse_proxy_header(b"PROXY TCP4 2001:db8::1 192.0.2.1 1 2\r\n").is_err());
+        assert!(parse_proxy_header(b"PROXY TCP4 192.0.2.1 192.0.2.2 x 2\r\n").is_err());
+        assert!(parse_proxy_header(&[b"PROXY ".as_slice(), &[b'a'; 120]].concat()).is_err());
+    }
+
+    #[test]
+    fn test_parse_v2() {
+        // TCP4（送信元、送信先、送信元のポート、送信先のポート）
+        let address = [192, 0, 2, 1, 198, 51, 100, 1, 0xc7, 0x38, 0, 80];
+        let mut data = v2_header(0x1, 0x11, &address);
+        let header_length = data.len();
+        data.extend_from_slice(b"GET");
+        let (header, length) = parse_proxy_header(&data).unwrap().unwrap();
+        assert_eq!(header, ProxyHeader::Proxy(addr("192.0.2.1:51000")));
+        assert_eq!(length, header_length);
+
+        // TCP6
+        let mut address = vec![0x20, 0x01, 0x0d, 0xb8];
+        address.extend_from_slice(&[0; 11]);
+        address.push(1);
+        address.extend_from_slice(&[0; 16]);
+        address.extend_from_slice(&[0xc7, 0x38, 0, 80]);
+        let data = v2_header(0x1, 0x21, &address);
+        let (header, _) = parse_proxy_header(&data).unwrap().unwrap();
+        assert_eq!(header, ProxyHeader::Proxy(addr("[2001:db8::1]:51000")));
+
+        // LOCAL、TCP以外は接続元のアドレスを使用する
+        let data = v2_header(0x0, 0x00, &[]);
+        assert_eq!(
+            parse_proxy_header(&data).unwrap(),
+            Some((ProxyHeader::Local, 16))
+        );
+        let data = v2_header(0x1, 0x12, &[0; 12]);
+        assert_eq!(
+            parse_proxy_header(&data).unwrap().unwrap().0,
+            ProxyHeader::Local
+        );
+
+        // 途中までの場合は続きを待つ
+        let data = v2_header(0x1, 0x11, &[0; 12]);
+        assert_eq!(parse_proxy_header(&data[..20]).unwrap(), None);
+
+        // 不正なコマンド、アドレスの長さ
+        assert!(parse_proxy_header(&v2_header(0x2, 0x11, &[0; 12])).is_err());
+        assert!(parse_proxy_header(&v2_header(0x1, 0x11, &[0; 4])).is_err());
+    }
+
+    #[test]
+    fn test_parse_without_header() {
+        assert_eq!(
+            parse_proxy_header(b"GET / HTTP/1.1\r\n").unwrap(),
+            Some((ProxyHeader::None, 0))
+        );
+        assert_eq!(
+            parse_proxy_header(b"POST / HTTP/1.1\r\n").unwrap(),
+            Some((ProxyHeader::None, 0))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_proxy_header() {
+        // ヘッダーの後に受信したデータを返す
+        let mut reader: &[u8] = b"PROXY TCP4 192.0.2.1 198.51.100.1 51000 80\r\nGET /";
+        let (header, rest) = read_proxy_header(&mut reader).await.unwrap();
+        assert_eq!(header, ProxyHeader::Proxy(addr("192.0.2.1:51000")));
+        assert_eq!(rest, b"GET /");
+
+        // ヘッダーの途中で切断された場合はエラー
+        let mut reader: &[u8] = b"PROXY TCP4";
+        assert!(read_proxy_header(&mut reader).await.is_err());
+    }
+
+    // 接続元のアドレスを返すサーバーを起動する関数
+    async fn start_server(trusted_proxies: &str) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let setting = ProxyProtocolSetting {
+            enabled: true,
+            timeout: Duration::from_secs(1),
+            trusted_proxies: TrustedProxies::parse(trusted_proxies),
+        };
+        let listener = ProxyProtocolListener::new(listener, setting).unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let app = Router::new().route(
+            "/",
+            get(|ConnectInfo(addr): ConnectInfo<SocketAddr>| async move { addr.to_string() }),
+        );
+        tokio::spawn(async move {
+            axum::serve(
+                listener.tap_io(|_: &mut ProxiedStream| {}),
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap();
+        });
+        local_addr
+    }
+
+    // 生のデータを送信してレスポンスを受信する関数
+    async fn send(server: SocketAddr, data: &[u8]) -> String {
+        let mut stream = TcpStream::connect(server).await.unwrap();
+        stream.write_all(data).await.unwrap();
+        let mut res = String::new();
+        let _ = stream.read_to_string(&mut res).await;
+        res
+    }
+
+    const REQUEST: &[u8] = b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+    #[tokio::test]
+    async fn it_uses_source_address_from_trusted_proxy() {
+        let server = start_server("127.0.0.1").await;
+
+        // ヘッダーの送信元を接続元のアドレスにする
+        let data = [
+            b"PROXY TCP4 203.0.113.7 127.0.0.1 51000 80\r\n".as_slice(),
+            REQUEST,
+        ]
+        .concat();
+        let res = send(server, &data).await;
+        assert!(res.starts_with("HTTP/1.1 200"));
+        assert!(res.ends_with("203.0.113.7:51000"));
+
+        // ヘッダーが無い場合はそのまま処理する
+        let res = send(server, REQUEST).await;
+        assert!(res.starts_with("HTTP/1.1 200"));
+        assert!(res.contains("127.0.0.1:"));
+
+        // 不正なヘッダーの場合は切断する
+        let res = send(server, b"PROXY TCP4 invalid\r\n").await;
+        assert!(res.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_ignores_header_from_untrusted_peer() {
+        // 信頼するプロキシ以外からのヘッダーはHTTPのリクエストとして不正になる
+        let server = start_server("10.0.0.0/8").await;
+        let data = [
+            b"PROXY TCP4 203.0.113.7 127.0.0.1 51000 80\r\n".as_slice(),
+            REQUEST,
+        ]
+        .concat();
+        let res = send(server, &data).await;
+        assert!(!res.contains("203.0.113.7"));
+        assert!(!res.starts_with("HTTP/1.1 200"));
+    }
+}
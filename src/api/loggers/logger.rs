@@ -1,55 +1,439 @@
-use chrono::TimeZone;
-use log;
-use std::io::Write;
+use axum::http::StatusCode;
+use chrono::SecondsFormat;
+use chrono_tz::Tz;
+use serde_json::{Value, json};
+use std::fmt::Write as _;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber, span};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::{
+    EnvFilter, Layer, Registry,
+    fmt::{FmtContext, FormatEvent, FormatFields, format::Writer},
+    layer::{Context as LayerContext, SubscriberExt},
+    registry::LookupSpan,
+    reload,
+    util::SubscriberInitExt,
+};
 
+use crate::api::configs::config::{self, LogFormat};
 use crate::api::contexts::context::Context;
+use crate::api::loggers::redaction::{RedactionSetting, Redactor};
+use crate::api::telemetry::otel;
+
+// リクエスト単位のspan名
+pub const REQUEST_SPAN_NAME: &str = "request";
+
+// アクセスログのターゲット
+pub const ACCESS_LOG_TARGET: &str = "rust_api::access";
+
+// ログの項目（出力順を維持する）
+pub type LogFields = Vec<(String, Value)>;
 
 // ロガーの初期化用関数
 pub fn init_logger() {
-    // 日本時間を取得
-    let jst = chrono::offset::FixedOffset::east_opt(9 * 3600)
-        .unwrap()
-        .from_utc_datetime(&chrono::Utc::now().naive_utc());
-
-    // カスタムロガーの初期化
-    env_logger::builder()
-        .format(move |buf, record| {
+    // 環境変数取得
+    let config = config::get_config();
+
+    // ログレベルの設定（RUST_LOGの形式が不正な場合はinfo）
+    let filter = EnvFilter::try_new(&config.rust_log).unwrap_or_else(|err| {
+        println!("RUST_LOGの設定エラー: {}", err);
+        EnvFilter::new("info")
+    });
+
+    // カスタムロガーの初期化（logクレートのログもtracingに転送される）
+    let formatter = LogFormatter {
+        json: config.log_format == LogFormat::Json,
+        timestamp: LogTimestamp::from_config(config),
+        redactor: redactor().clone(),
+    };
+    // ログレベルは設定の再読み込み時に置き換えられるようにする
+    let (filter, handle) = reload::Layer::new(filter);
+    let _ = LOG_FILTER.set(handle);
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(SpanFieldsLayer)
+        .with(tracing_subscriber::fmt::layer().event_format(formatter))
+        // OpenTelemetryが有効な場合はspanをエクスポートする
+        .with(otel::otel_layer())
+        .init();
+}
+
+// ログレベルの置き換え用（ロガーの初期化時に設定）
+static LOG_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+// ログレベルを変更する関数（RUST_LOGと同じ形式、ロガーが未初期化の場合は何もしない）
+pub fn reload_log_filter(directives: &str) -> Result<(), String> {
+    let filter = EnvFilter::try_new(directives).map_err(|err| err.to_string())?;
+    match LOG_FILTER.get() {
+        Some(handle) => handle.reload(filter).map_err(|err| err.to_string()),
+        None => Ok(()),
+    }
+}
+
+// ログのタイムスタンプの設定（ログ出力のたびに現在時刻を取得して変換する）
+#[derive(Clone, Debug)]
+pub struct LogTimestamp {
+    pub timezone: Tz,
+    pub precision: SecondsFormat,
+}
+
+impl LogTimestamp {
+    // 環境変数から作成（不正な値の場合はUTC、ミリ秒）
+    pub fn from_config(config: &config::Config) -> Self {
+        let timezone = parse_timezone(&config.log_timezone).unwrap_or_else(|err| {
+            println!("LOG_TIMEZONEの設定エラー: {}", err);
+            Tz::UTC
+        });
+        let precision = parse_precision(&config.log_timestamp_precision).unwrap_or_else(|err| {
+            println!("LOG_TIMESTAMP_PRECISIONの設定エラー: {}", err);
+            SecondsFormat::Millis
+        });
+        LogTimestamp {
+            timezone,
+            precision,
+        }
+    }
+
+    // RFC 3339形式の文字列を返す（UTCの場合は「Z」、それ以外は「+09:00」等のオフセット付き）
+    pub fn format(&self, now: chrono::DateTime<chrono::Utc>) -> String {
+        now.with_timezone(&self.timezone)
+            .to_rfc3339_opts(self.precision, true)
+    }
+}
+
+// タイムゾーンのIANA名（例：UTC、Asia/Tokyo、Europe/Berlin）を変換する関数
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.parse::<Tz>()
+        .map_err(|_| format!("不正なタイムゾーンです: {}", name))
+}
+
+// タイムスタンプの精度（seconds | millis | micros | nanos）を変換する関数
+pub fn parse_precision(name: &str) -> Result<SecondsFormat, String> {
+    match name {
+        "seconds" => Ok(SecondsFormat::Secs),
+        "millis" => Ok(SecondsFormat::Millis),
+        "micros" => Ok(SecondsFormat::Micros),
+        "nanos" => Ok(SecondsFormat::Nanos),
+        _ => Err(format!("不正なタイムスタンプの精度です: {}", name)),
+    }
+}
+
+// マスク処理のインスタンス（プロセス内で共有）
+static REDACTOR: OnceLock<Redactor> = OnceLock::new();
+
+// マスク処理を取得する関数
+pub fn redactor() -> &'static Redactor {
+    REDACTOR.get_or_init(|| Redactor::new(&RedactionSetting::from_config(config::get_config())))
+}
+
+// 項目を追加する関数（同じキーが既にある場合は上書き）
+fn insert_field(fields: &mut LogFields, key: &str, value: Value) {
+    match fields.iter_mut().find(|(k, _)| k == key) {
+        Some(field) => field.1 = value,
+        None => fields.push((key.to_string(), value)),
+    }
+}
+
+// tracingの項目をJSONの値に変換するビジター
+struct FieldVisitor<'a>(&'a mut LogFields);
+
+impl FieldVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: Value) {
+        // logクレートから転送された場合のメタ情報とOpenTelemetry用の項目は出力しない
+        if field.name().starts_with("log.") || field.name().starts_with("otel.") {
+            return;
+        }
+        insert_field(self.0, field.name(), value);
+    }
+}
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, json!(format!("{:?}", value)));
+    }
+}
+
+// spanに保存する項目
+struct SpanFields(LogFields);
+
+// リクエストのspanの開始時刻（システム時刻の変更に影響されない単調増加の時刻）
+struct RequestStart(Instant);
+
+// spanの項目をログに出力するため、span作成時と項目更新時に値を保存するレイヤー
+pub struct SpanFieldsLayer;
+
+impl<S> Layer<S> for SpanFieldsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: LayerContext<'_, S>) {
+        let mut fields = Vec::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            extensions.insert(SpanFields(fields));
+            if attrs.metadata().name() == REQUEST_SPAN_NAME {
+                extensions.insert(RequestStart(Instant::now()));
+            }
+        }
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: LayerContext<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+                values.record(&mut FieldVisitor(fields));
+            }
+        }
+    }
+}
+
+// ログの出力形式
+pub struct LogFormatter {
+    pub json: bool,
+    pub timestamp: LogTimestamp,
+    // 個人情報や認証情報のマスク処理（全ての出力形式に適用）
+    pub redactor: Redactor,
+}
+
+impl<S, N> FormatEvent<S, N> for LogFormatter
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        // logクレートから転送された場合は元のターゲットを使用
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+
+        // 親のspanから順番に項目を追加し、イベントの項目で上書きする
+        let mut fields: LogFields = Vec::new();
+        if let Some(scope) = ctx.event_scope() {
+            let mut span_name = None;
+            for span in scope.from_root() {
+                let extensions = span.extensions();
+                if let Some(SpanFields(span_fields)) = extensions.get::<SpanFields>() {
+                    for (key, value) in span_fields.iter() {
+                        insert_field(&mut fields, key, value.clone());
+                    }
+                }
+                // リクエスト開始からの経過時間
+                if let Some(RequestStart(start)) = extensions.get::<RequestStart>() {
+                    insert_field(
+                        &mut fields,
+                        "elapsed_us",
+                        json!(start.elapsed().as_micros() as u64),
+                    );
+                }
+                span_name = Some(span.name());
+            }
+            // リクエスト以外のspan内で出力された場合は処理名を追加
+            if let Some(name) = span_name.filter(|name| *name != REQUEST_SPAN_NAME) {
+                insert_field(&mut fields, "span", json!(name));
+            }
+        }
+        event.record(&mut FieldVisitor(&mut fields));
+
+        // メッセージは項目から取り出す
+        let message = match fields.iter().position(|(k, _)| k == "message") {
+            Some(index) => match fields.remove(index).1 {
+                Value::String(message) => message,
+                value => value.to_string(),
+            },
+            None => String::new(),
+        };
+
+        // 個人情報や認証情報をマスク
+        self.redactor.redact_fields(&mut fields);
+        let message = self.redactor.redact_text(&message);
+
+        let level = metadata.level().as_str();
+        let timestamp = self.timestamp.format(chrono::Utc::now());
+        if self.json {
+            writeln!(
+                writer,
+                "{}",
+                format_json(&timestamp, level, metadata.target(), &message, &fields)
+            )
+        } else {
             writeln!(
-                buf,
+                writer,
                 "{} {} {}",
-                jst.format("%Y-%m-%d %H:%M:%S"),
-                record.level(),
-                record.args()
+                timestamp,
+                level,
+                format_text(&message, &fields)
             )
-        })
-        .init();
+        }
+    }
 }
 
-// 共通コンテキストからログに追加する情報の文字列を取得する関数
-fn get_info_from_request(ctx: &Context) -> String {
-    // リクエストヘッダーから「X-Request-Id」を取得
-    let x_request_id = ctx.header.get("X-Request-Id");
-    let request_id = x_request_id.expect("-").to_str().unwrap();
+// JSON形式のログの文字列を返す関数
+pub fn format_json(
+    timestamp: &str,
+    level: &str,
+    target: &str,
+    message: &str,
+    fields: &LogFields,
+) -> String {
+    let mut line = serde_json::Map::new();
+    line.insert("timestamp".to_string(), json!(timestamp));
+    line.insert("level".to_string(), json!(level));
+    line.insert("target".to_string(), json!(target));
+    line.insert("message".to_string(), json!(message));
+    for (key, value) in fields.iter() {
+        line.insert(key.clone(), value.clone());
+    }
 
-    format!(
-        "request_id={} method={} uri={}",
-        request_id, ctx.method, ctx.uri
-    )
+    Value::Object(line).to_string()
+}
+
+// テキスト形式のログの文字列を返す関数（項目は「[key=value ...] メッセージ」の形式で出力）
+pub fn format_text(message: &str, fields: &LogFields) -> String {
+    if fields.is_empty() {
+        return message.to_string();
+    }
+
+    let mut text = String::from("[");
+    for (index, (key, value)) in fields.iter().enumerate() {
+        if index > 0 {
+            text.push(' ');
+        }
+        // 文字列は引用符なしで出力
+        let _ = match value {
+            Value::String(value) => write!(text, "{}={}", key, value),
+            value => write!(text, "{}={}", key, value),
+        };
+    }
+    let _ = write!(text, "] {}", message);
+    text
+}
+
+// 共通コンテキストから「X-Request-Id」を取得する関数
+fn get_request_id(ctx: &Context) -> &str {
+    match ctx.header.get("X-Request-Id") {
+        Some(value) => value.to_str().unwrap_or("-"),
+        None => "-",
+    }
 }
 
 // ログ出力用関数
+// リクエストのspan外（WebSocketやSSEの配信処理等）でも相関できるよう、共通コンテキストの情報も項目に追加する
 pub fn info(ctx: &Context, msg: &str) {
-    let info = get_info_from_request(ctx);
-    log::info!("[{}] {}", info, msg);
+    tracing::info!(
+        request_id = get_request_id(ctx),
+        trace_id = ctx.trace_id.as_deref(),
+        method = ctx.method.as_str(),
+        uri = ctx.uri.as_str(),
+        user_id = ctx.principal.as_deref(),
+        "{}",
+        msg
+    );
 }
 
-// TODO: 使用する場合にコメントアウトを外す
-// pub fn warn(ctx: &Context, msg: &str) {
-//     let info = get_info_from_request(ctx);
-//     log::warn!("[{}] {}", info, msg);
-// }
+pub fn warn(ctx: &Context, msg: &str) {
+    tracing::warn!(
+        request_id = get_request_id(ctx),
+        trace_id = ctx.trace_id.as_deref(),
+        method = ctx.method.as_str(),
+        uri = ctx.uri.as_str(),
+        user_id = ctx.principal.as_deref(),
+        "{}",
+        msg
+    );
+}
 
 pub fn error(ctx: &Context, msg: &str) {
-    let info = get_info_from_request(ctx);
-    log::error!("[{}] {}", info, msg);
+    tracing::error!(
+        request_id = get_request_id(ctx),
+        trace_id = ctx.trace_id.as_deref(),
+        method = ctx.method.as_str(),
+        uri = ctx.uri.as_str(),
+        user_id = ctx.principal.as_deref(),
+        "{}",
+        msg
+    );
+}
+
+// リクエスト開始時のログ出力用関数（許可リストのヘッダーのみ出力）
+pub fn start_request_log(ctx: &Context) {
+    tracing::info!(
+        request_id = get_request_id(ctx),
+        trace_id = ctx.trace_id.as_deref(),
+        method = ctx.method.as_str(),
+        uri = ctx.uri.as_str(),
+        client_ip = ctx.client_ip.map(|ip| ip.to_string()),
+        scheme = ctx.scheme.as_str(),
+        user_id = ctx.principal.as_deref(),
+        headers = %redactor().headers_for_log(&ctx.header),
+        "start request !!"
+    );
+}
+
+// ステータスコードからエラーコードを取得する関数（例：404 -> not_found）
+pub fn error_code_from_status(status: StatusCode) -> Option<String> {
+    if !(status.is_client_error() || status.is_server_error()) {
+        return None;
+    }
+    let reason = status.canonical_reason().unwrap_or("error");
+    Some(
+        reason
+            .to_lowercase()
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "_"),
+    )
+}
+
+// レスポンス単位のアクセスログ出力用関数（TraceLayerから呼び出す、処理時間は単調増加の時刻で計測）
+pub fn access_log(ctx: Option<&Context>, status: StatusCode, latency: Duration) {
+    let (request_id, trace_id, method, uri, user_id, client_ip) = match ctx {
+        Some(ctx) => (
+            get_request_id(ctx),
+            ctx.trace_id.as_deref(),
+            ctx.method.as_str(),
+            ctx.uri.as_str(),
+            ctx.principal.as_deref().unwrap_or("-"),
+            ctx.client_ip.map(|ip| ip.to_string()),
+        ),
+        None => ("-", None, "-", "-", "-", None),
+    };
+
+    tracing::info!(
+        target: ACCESS_LOG_TARGET,
+        request_id = request_id,
+        trace_id = trace_id,
+        method = method,
+        uri = uri,
+        status = status.as_u16(),
+        latency_us = latency.as_micros() as u64,
+        user_id = user_id,
+        client_ip = client_ip.as_deref().unwrap_or("-"),
+        error_code = error_code_from_status(status).as_deref(),
+        "finish request !!"
+    );
 }
@@ -0,0 +1,330 @@
+#[cfg(test)]
+// ログの出力形式のテスト
+mod logger_format_test {
+    use crate::api::contexts::context::Context;
+    use crate::api::loggers::logger::{
+        LogFormatter, LogTimestamp, REQUEST_SPAN_NAME, SpanFieldsLayer, access_log,
+        error_code_from_status, format_json, format_text, parse_precision, parse_timezone,
+    };
+    use crate::api::loggers::redaction::{RedactionSetting, Redactor};
+    use crate::api::middleware::common_middleware::{auth_middleware, request_middleware};
+    use axum::http::StatusCode;
+    use axum::response::Response;
+    use axum::routing::get;
+    use axum::{Router, middleware};
+    use chrono::{SecondsFormat, TimeZone};
+    use chrono_tz::Tz;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tower_http::trace::TraceLayer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    // ログの出力先
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // ログ出力を取得する関数
+    fn capture(json: bool, f: impl FnOnce()) -> Vec<String> {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(SpanFieldsLayer).with(
+            tracing_subscriber::fmt::layer()
+                .event_format(LogFormatter {
+                    json,
+                    timestamp: LogTimestamp {
+                        timezone: Tz::UTC,
+                        precision: SecondsFormat::Millis,
+                    },
+                    redactor: Redactor::new(&RedactionSetting {
+                        fields: vec!["email".to_string()],
+                        patterns: vec!["email".to_string(), "token".to_string()],
+                        header_allowlist: vec![],
+                    }),
+                })
+                .with_writer(move || writer.clone()),
+        );
+        tracing::subscriber::with_default(subscriber, f);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        output.lines().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn it_formats_json_with_fields() {
+        let fields = vec![
+            ("request_id".to_string(), json!("abc")),
+            ("status".to_string(), json!(404)),
+            ("latency_us".to_string(), json!(1234)),
+        ];
+
+        let line: serde_json::Value = serde_json::from_str(&format_json(
+            "2026-10-19T01:02:03.000000Z",
+            "INFO",
+            "rust_api::access",
+            "finish request !!",
+            &fields,
+        ))
+        .unwrap();
+        assert_eq!(line["timestamp"], "2026-10-19T01:02:03.000000Z");
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["target"], "rust_api::access");
+        assert_eq!(line["message"], "finish request !!");
+        assert_eq!(line["request_id"], "abc");
+        // 数値は数値のまま出力されること
+        assert_eq!(line["status"], 404);
+        assert_eq!(line["latency_us"], 1234);
+    }
+
+    #[test]
+    fn it_formats_text_with_fields() {
+        let fields = vec![
+            ("request_id".to_string(), json!("abc")),
+            ("status".to_string(), json!(200)),
+        ];
+        assert_eq!(
+            format_text("start request !!", &fields),
+            "[request_id=abc status=200] start request !!"
+        );
+
+        // 項目がない場合はメッセージのみ
+        assert_eq!(
+            format_text("Start rust_api !!", &vec![]),
+            "Start rust_api !!"
+        );
+    }
+
+    #[test]
+    fn it_adds_span_fields_to_events() {
+        let lines = capture(true, || {
+            let span = tracing::info_span!(
+                REQUEST_SPAN_NAME,
+                request_id = "abc",
+                method = "GET",
+                user_id = tracing::field::Empty
+            );
+            let _enter = span.enter();
+            span.record("user_id", "user-1");
+
+            // リクエストのspan内のログ
+            tracing::info!("start request !!");
+
+            // 処理単位のspan内のログはイベントの項目で上書きされる
+            let inner = tracing::info_span!("UsersRepository.get_users", uid = "xxx");
+            let _inner_enter = inner.enter();
+            tracing::error!(method = "POST", "DB接続エラー");
+        });
+
+        assert_eq!(lines.len(), 2);
+        let line: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(line["message"], "start request !!");
+        assert_eq!(line["request_id"], "abc");
+        assert_eq!(line["method"], "GET");
+        assert_eq!(line["user_id"], "user-1");
+        assert!(line.get("span").is_none());
+
+        let line: serde_json::Value = serde_json::from_str(&lines[1]).unwrap();
+        assert_eq!(line["level"], "ERROR");
+        assert_eq!(line["request_id"], "abc");
+        assert_eq!(line["method"], "POST");
+        assert_eq!(line["uid"], "xxx");
+        assert_eq!(line["span"], "UsersRepository.get_users");
+    }
+
+    #[test]
+    fn it_formats_text_lines() {
+        let lines = capture(false, || {
+            let span = tracing::info_span!(REQUEST_SPAN_NAME, request_id = "abc");
+            let _enter = span.enter();
+            tracing::warn!("slow request");
+        });
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains(" WARN [request_id=abc elapsed_us="));
+        assert!(lines[0].ends_with("] slow request"));
+    }
+
+    #[test]
+    fn it_redacts_json_and_text_lines() {
+        let emit = || {
+            let span = tracing::info_span!(
+                REQUEST_SPAN_NAME,
+                request_id = "abc",
+                uri = "/api/v1/users?email=t.tanaka%40example.com"
+            );
+            let _enter = span.enter();
+            tracing::error!(
+                email = "t.tanaka@example.com",
+                "認証エラー: Bearer abc.def.ghi (t.tanaka@example.com)"
+            );
+        };
+
+        // JSON形式
+        let lines = capture(true, emit);
+        let line: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(line["request_id"], "abc");
+        assert_eq!(line["uri"], "/api/v1/users?email=[REDACTED]");
+        assert_eq!(line["email"], "[REDACTED]");
+        assert_eq!(
+            line["message"],
+            "認証エラー: Bearer [REDACTED_TOKEN] ([REDACTED_EMAIL])"
+        );
+
+        // テキスト形式
+        let lines = capture(false, emit);
+        assert!(!lines[0].contains("example.com"));
+        assert!(!lines[0].contains("abc.def.ghi"));
+        assert!(lines[0].contains("uri=/api/v1/users?email=[REDACTED]"));
+    }
+
+    #[test]
+    fn it_formats_timestamp_with_timezone_and_precision() {
+        let now = chrono::Utc
+            .with_ymd_and_hms(2026, 10, 19, 1, 2, 3)
+            .unwrap()
+            .checked_add_signed(chrono::TimeDelta::microseconds(123456))
+            .unwrap();
+        let timestamp = |timezone: &str, precision: &str| {
+            LogTimestamp {
+                timezone: parse_timezone(timezone).unwrap(),
+                precision: parse_precision(precision).unwrap(),
+            }
+            .format(now)
+        };
+
+        assert_eq!(timestamp("UTC", "seconds"), "2026-10-19T01:02:03Z");
+        assert_eq!(timestamp("UTC", "millis"), "2026-10-19T01:02:03.123Z");
+        assert_eq!(timestamp("UTC", "micros"), "2026-10-19T01:02:03.123456Z");
+        assert_eq!(timestamp("UTC", "nanos"), "2026-10-19T01:02:03.123456000Z");
+        assert_eq!(
+            timestamp("Asia/Tokyo", "millis"),
+            "2026-10-19T10:02:03.123+09:00"
+        );
+        // 夏時間
+        assert_eq!(
+            timestamp("Europe/Berlin", "millis"),
+            "2026-10-19T03:02:03.123+02:00"
+        );
+
+        assert!(parse_timezone("JST+9").is_err());
+        assert!(parse_precision("minutes").is_err());
+    }
+
+    #[test]
+    fn it_uses_current_time_for_each_line() {
+        let lines = capture(true, || {
+            tracing::info!("first");
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            tracing::info!("second");
+        });
+
+        let first: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        let second: serde_json::Value = serde_json::from_str(&lines[1]).unwrap();
+        let parse = |line: &serde_json::Value| {
+            chrono::DateTime::parse_from_rfc3339(line["timestamp"].as_str().unwrap()).unwrap()
+        };
+        assert!(parse(&second) - parse(&first) >= chrono::TimeDelta::milliseconds(20));
+    }
+
+    #[test]
+    fn it_adds_elapsed_time_in_request_span() {
+        let lines = capture(true, || {
+            tracing::info!("outside request");
+
+            let span = tracing::info_span!(REQUEST_SPAN_NAME, request_id = "abc");
+            let _enter = span.enter();
+            tracing::info!("start request !!");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            tracing::info!("finish");
+        });
+
+        let outside: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert!(outside.get("elapsed_us").is_none());
+
+        let start: serde_json::Value = serde_json::from_str(&lines[1]).unwrap();
+        let finish: serde_json::Value = serde_json::from_str(&lines[2]).unwrap();
+        let start_us = start["elapsed_us"].as_u64().unwrap();
+        let finish_us = finish["elapsed_us"].as_u64().unwrap();
+        assert!(finish_us >= start_us + 5000);
+    }
+
+    #[test]
+    fn it_returns_error_code_from_status() {
+        assert_eq!(error_code_from_status(StatusCode::OK), None);
+        assert_eq!(
+            error_code_from_status(StatusCode::NOT_FOUND),
+            Some("not_found".to_string())
+        );
+        assert_eq!(
+            error_code_from_status(StatusCode::UNPROCESSABLE_ENTITY),
+            Some("unprocessable_entity".to_string())
+        );
+        assert_eq!(
+            error_code_from_status(StatusCode::INTERNAL_SERVER_ERROR),
+            Some("internal_server_error".to_string())
+        );
+    }
+
+    #[test]
+    fn it_adds_user_id_for_authenticated_request() {
+        let lines = capture(false, || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                // 共通ミドルウェアと認証用ミドルウェアを設定したルーター
+                let app = Router::new()
+                    .route(
+                        "/",
+                        get(|| async {
+                            tracing::info!("handler");
+                            "ok"
+                        }),
+                    )
+                    .layer(middleware::from_fn(auth_middleware))
+                    .layer(middleware::from_fn(request_middleware))
+                    .layer(TraceLayer::new_for_http().on_response(
+                        |res: &Response, latency: Duration, _span: &tracing::Span| {
+                            access_log(res.extensions().get::<Context>(), res.status(), latency)
+                        },
+                    ));
+                let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+                let url = format!("http://{}/", listener.local_addr().unwrap());
+                tokio::spawn(async move { axum::serve(listener, app).await });
+
+                // テスト環境のトークン「xxx」は test-user
+                let res = reqwest::Client::new()
+                    .get(url)
+                    .header("Authorization", "Bearer xxx")
+                    .send()
+                    .await
+                    .unwrap();
+                assert_eq!(res.status(), 200);
+            });
+        });
+
+        // リクエストのspan内のログとアクセスログに認証済みユーザーが出力されること
+        let handler = lines
+            .iter()
+            .find(|line| line.ends_with("] handler"))
+            .unwrap();
+        assert!(handler.contains(" user_id=test-user]"));
+        let access = lines
+            .iter()
+            .find(|line| line.ends_with("] finish request !!"))
+            .unwrap();
+        assert!(access.contains(" user_id=test-user "));
+    }
+}
@@ -1 +1,6 @@
 pub mod logger;
+pub mod redaction;
+
+// テストコード用のモジュール
+mod logger_1_test;
+mod redaction_1_test;
@@ -0,0 +1,210 @@
+// axum
+use axum::http::HeaderMap;
+
+// 正規表現
+use regex::Regex;
+
+// json変換用マクロ
+use serde_json::{Value, json};
+
+// configsモジュール
+use crate::api::configs::config::Config;
+
+// 伏せ字
+pub const REDACTED: &str = "[REDACTED]";
+
+// 組み込みの正規表現のルール名
+pub const PATTERN_EMAIL: &str = "email";
+pub const PATTERN_PHONE: &str = "phone";
+pub const PATTERN_TOKEN: &str = "token";
+
+// 許可リストに含まれていても出力しないヘッダー
+const DENIED_HEADERS: [&str; 4] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+// 正規表現でマスクしない項目（サーバーが生成する識別子等）
+const PATTERN_EXEMPT_FIELDS: [&str; 6] = [
+    "request_id",
+    "trace_id",
+    "method",
+    "span",
+    "error_code",
+    "user_id",
+];
+
+// マスク処理の設定
+#[derive(Clone, Debug)]
+pub struct RedactionSetting {
+    // 値をマスクする項目名
+    pub fields: Vec<String>,
+    // 有効にする正規表現のルール（email | phone | token）
+    pub patterns: Vec<String>,
+    // ログに出力するヘッダー
+    pub header_allowlist: Vec<String>,
+}
+
+impl RedactionSetting {
+    pub fn from_config(config: &Config) -> Self {
+        RedactionSetting {
+            fields: split_list(&config.log_redact_fields),
+            patterns: split_list(&config.log_redact_patterns),
+            header_allowlist: split_list(&config.log_header_allowlist),
+        }
+    }
+}
+
+// カンマ区切りの設定値を小文字のリストに変換する関数
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+// 組み込みの正規表現のルールを返す関数（正規表現と置換後の文字列）
+fn builtin_patterns(name: &str) -> Option<Vec<(Regex, &'static str)>> {
+    let patterns = match name {
+        PATTERN_EMAIL => vec![(
+            Regex::new(r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}").unwrap(),
+            "[REDACTED_EMAIL]",
+        )],
+        // 国番号付き、ハイフン・空白区切り、区切りなし（0から始まる10〜11桁）の電話番号
+        PATTERN_PHONE => vec![(
+            Regex::new(r"(?:\+\d{1,3}[\-\s]?)?\b(?:0\d{9,10}|\d{1,4}[\-\s]\d{1,4}[\-\s]\d{3,4})\b")
+                .unwrap(),
+            "[REDACTED_PHONE]",
+        )],
+        // Bearerトークン、JWT
+        PATTERN_TOKEN => vec![
+            (
+                Regex::new(r"(?i)\bbearer\s+[A-Za-z0-9\-._~+/]+=*").unwrap(),
+                "Bearer [REDACTED_TOKEN]",
+            ),
+            (
+                Regex::new(r"\beyJ[A-Za-z0-9_\-]+\.[A-Za-z0-9_\-]+\.[A-Za-z0-9_\-]*").unwrap(),
+                "[REDACTED_TOKEN]",
+            ),
+        ],
+        _ => return None,
+    };
+    Some(patterns)
+}
+
+// ログの項目とメッセージから個人情報や認証情報をマスクする処理
+#[derive(Clone, Debug)]
+pub struct Redactor {
+    fields: Vec<String>,
+    // 文字列内の「項目名=値」「"項目名":"値"」形式の値をマスクする正規表現
+    field_pattern: Option<Regex>,
+    patterns: Vec<(Regex, &'static str)>,
+    header_allowlist: Vec<String>,
+}
+
+impl Redactor {
+    pub fn new(setting: &RedactionSetting) -> Self {
+        let field_pattern = if setting.fields.is_empty() {
+            None
+        } else {
+            let names = setting
+                .fields
+                .iter()
+                .map(|field| regex::escape(field))
+                .collect::<Vec<String>>()
+                .join("|");
+            Some(
+                Regex::new(&format!(
+                    r#"(?i)(\b(?:{})"?\s*[:=]\s*"?)([^"&\s,;}}]+)"#,
+                    names
+                ))
+                .unwrap(),
+            )
+        };
+
+        let mut patterns = Vec::new();
+        for name in setting.patterns.iter() {
+            match builtin_patterns(name) {
+                Some(builtin) => patterns.extend(builtin),
+                None => println!("不正なマスク処理のルールのため無視します: {}", name),
+            }
+        }
+
+        Redactor {
+            fields: setting.fields.clone(),
+            field_pattern,
+            patterns,
+            header_allowlist: setting.header_allowlist.clone(),
+        }
+    }
+
+    fn is_redacted_field(&self, key: &str) -> bool {
+        let key = key.to_lowercase();
+        self.fields.contains(&key) || DENIED_HEADERS.contains(&key.as_str())
+    }
+
+    // 文字列をマスク
+    pub fn redact_text(&self, text: &str) -> String {
+        let mut text = match &self.field_pattern {
+            Some(pattern) => pattern
+                .replace_all(text, format!("${{1}}{}", REDACTED))
+                .into_owned(),
+            None => text.to_string(),
+        };
+        for (pattern, replacement) in self.patterns.iter() {
+            text = pattern.replace_all(&text, *replacement).into_owned();
+        }
+        text
+    }
+
+    // 値をマスク（オブジェクトは項目名でもマスクする）
+    pub fn redact_value(&self, value: &Value) -> Value {
+        match value {
+            Value::String(text) => json!(self.redact_text(text)),
+            Value::Array(values) => {
+                Value::Array(values.iter().map(|v| self.redact_value(v)).collect())
+            }
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, v)| {
+                        let v = if self.is_redacted_field(key) {
+                            json!(REDACTED)
+                        } else {
+                            self.redact_value(v)
+                        };
+                        (key.clone(), v)
+                    })
+                    .collect(),
+            ),
+            value => value.clone(),
+        }
+    }
+
+    // ログの項目をマスク
+    pub fn redact_fields(&self, fields: &mut [(String, Value)]) {
+        for (key, value) in fields.iter_mut() {
+            if self.is_redacted_field(key) {
+                *value = json!(REDACTED);
+            } else if !PATTERN_EXEMPT_FIELDS.contains(&key.as_str()) {
+                *value = self.redact_value(value);
+            }
+        }
+    }
+
+    // 許可リストのヘッダーのみをログ出力用に変換（認証情報のヘッダーは常に除外）
+    pub fn headers_for_log(&self, headers: &HeaderMap) -> Value {
+        let mut map = serde_json::Map::new();
+        for (name, value) in headers.iter() {
+            let name = name.as_str();
+            if DENIED_HEADERS.contains(&name) || !self.header_allowlist.iter().any(|h| h == name) {
+                continue;
+            }
+            let value = String::from_utf8_lossy(value.as_bytes());
+            map.insert(name.to_string(), json!(self.redact_text(&value)));
+        }
+        Value::Object(map)
+    }
+}
@@ -0,0 +1,353 @@
+// axum
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{MatchedPath, Request},
+    http::{HeaderMap, StatusCode, header},
+    middleware::Next,
+    response::Response,
+};
+
+// HTTPボディ
+use http_body::{Frame, SizeHint};
+
+// json変換用マクロ
+use serde_json::Value;
+
+// 標準ライブラリ
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context as TaskContext, Poll};
+
+// configsモジュール
+use crate::api::configs::config::Config;
+use crate::api::configs::config_reload::ReloadableSetting;
+use crate::api::configs::secret::Secret;
+
+// 共通コンテキストのモジュール
+use crate::api::contexts::context::Context;
+
+// Webhookの署名検証（デバッグ用ヘッダーの署名にも使用する）
+use crate::api::events::webhook_sender;
+
+// ロガー用のモジュール
+use crate::api::loggers::logger::redactor;
+use crate::api::loggers::redaction::Redactor;
+
+// ボディのログ出力を要求するヘッダー（署名対象は「{タイムスタンプ}.{メソッド} {パス}」）
+pub const HEADER_DEBUG_TIMESTAMP: &str = "X-Debug-Body-Log-Timestamp";
+pub const HEADER_DEBUG_SIGNATURE: &str = "X-Debug-Body-Log-Signature";
+
+// デバッグ用ヘッダーの署名の有効期間（秒）
+const DEBUG_SIGNATURE_TOLERANCE_SECS: i64 = 300;
+
+// 全てのルートを対象にする場合の設定値
+const ALL_ROUTES: &str = "*";
+
+// ボディのログ出力の設定
+#[derive(Clone, Debug)]
+pub struct BodyLogSetting {
+    pub enabled: bool,
+    // 常に出力するルートのテンプレート（例：/api/v1/user/{uid}）
+    pub routes: Vec<String>,
+    pub max_bytes: usize,
+    // 署名付きヘッダーの検証用の秘密鍵（Noneの場合はヘッダーによる指定を無効にする）
+    pub debug_secret: Option<Secret<String>>,
+}
+
+impl BodyLogSetting {
+    pub fn from_config(config: &Config) -> Self {
+        BodyLogSetting {
+            // 本番環境では設定に関わらず無効にする
+            enabled: config.body_log_enabled && !config.env.is_production(),
+            routes: config
+                .body_log_routes
+                .split(',')
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect(),
+            max_bytes: config.body_log_max_bytes,
+            debug_secret: config.body_log_debug_secret.clone(),
+        }
+    }
+
+    // 対象のルートか判定
+    pub fn is_route_enabled(&self, route: &str) -> bool {
+        self.routes.iter().any(|r| r == ALL_ROUTES || r == route)
+    }
+
+    // デバッグ用ヘッダーの署名を検証
+    pub fn verify_debug_headers(
+        &self,
+        headers: &HeaderMap,
+        method: &str,
+        path: &str,
+        now: i64,
+    ) -> bool {
+        let Some(secret) = &self.debug_secret else {
+            return false;
+        };
+        let timestamp = headers
+            .get(HEADER_DEBUG_TIMESTAMP)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<i64>().ok());
+        let signature = headers
+            .get(HEADER_DEBUG_SIGNATURE)
+            .and_then(|v| v.to_str().ok());
+        let (Some(timestamp), Some(signature)) = (timestamp, signature) else {
+            return false;
+        };
+
+        webhook_sender::verify(
+            secret.expose(),
+            timestamp,
+            debug_signature_message(method, path).as_bytes(),
+            signature,
+            now,
+            DEBUG_SIGNATURE_TOLERANCE_SECS,
+        )
+    }
+}
+
+// デバッグ用ヘッダーの署名対象のメッセージ
+pub fn debug_signature_message(method: &str, path: &str) -> String {
+    format!("{} {}", method, path)
+}
+
+// 設定のインスタンス（プロセス内で共有し、設定の再読み込み時に作り直す）
+static BODY_LOG_SETTING: ReloadableSetting<BodyLogSetting> =
+    ReloadableSetting::new(BodyLogSetting::from_config);
+
+// 設定を取得する関数
+pub fn body_log_setting() -> Arc<BodyLogSetting> {
+    BODY_LOG_SETTING.get()
+}
+
+// 読み取ったボディの内容（最大サイズまで保持する）
+#[derive(Clone, Debug, Default)]
+pub struct CapturedBody {
+    pub bytes: Vec<u8>,
+    pub total_bytes: usize,
+    pub truncated: bool,
+    // 最後まで読み取られたか（クライアントの切断等で中断された場合はfalse）
+    pub completed: bool,
+}
+
+type OnComplete = Box<dyn FnOnce(CapturedBody) + Send>;
+
+// ボディをそのまま転送しながら先頭を保持するラッパー（ストリーミングのレスポンスも逐次転送される）
+pub struct BodyTap {
+    inner: Body,
+    max_bytes: usize,
+    captured: CapturedBody,
+    on_complete: Option<OnComplete>,
+}
+
+impl BodyTap {
+    pub fn new(
+        inner: Body,
+        max_bytes: usize,
+        on_complete: impl FnOnce(CapturedBody) + Send + 'static,
+    ) -> Self {
+        BodyTap {
+            inner,
+            max_bytes,
+            captured: CapturedBody::default(),
+            on_complete: Some(Box::new(on_complete)),
+        }
+    }
+
+    fn capture(&mut self, data: &Bytes) {
+        self.captured.total_bytes += data.len();
+        let remaining = self.max_bytes.saturating_sub(self.captured.bytes.len());
+        if data.len() > remaining {
+            self.captured.truncated = true;
+        }
+        self.captured
+            .bytes
+            .extend_from_slice(&data[..data.len().min(remaining)]);
+    }
+
+    fn complete(&mut self) {
+        if let Some(on_complete) = self.on_complete.take() {
+            // 終端が分かっている場合は最後のフレームの後に読み取りが終了する
+            self.captured.completed |= self.inner.is_end_stream();
+            on_complete(std::mem::take(&mut self.captured));
+        }
+    }
+}
+
+impl HttpBody for BodyTap {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let poll = Pin::new(&mut self.inner).poll_frame(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(data) = frame.data_ref() {
+                    self.capture(data);
+                }
+            }
+            Poll::Ready(None) => {
+                self.captured.completed = true;
+                self.complete();
+            }
+            _ => {}
+        }
+        poll
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for BodyTap {
+    // 最後まで読み取られなかった場合も出力する
+    fn drop(&mut self) {
+        self.complete();
+    }
+}
+
+// テキストとして出力するContent-Typeか判定（未指定の場合はUTF-8として読める場合のみ出力）
+fn is_text_content(content_type: Option<&str>) -> bool {
+    match content_type {
+        Some(content_type) => {
+            let content_type = content_type.to_lowercase();
+            content_type.starts_with("text/")
+                || content_type.contains("json")
+                || content_type.contains("xml")
+                || content_type.starts_with("application/x-www-form-urlencoded")
+        }
+        None => true,
+    }
+}
+
+// ログ出力用にボディをマスクして文字列に変換する関数
+pub fn render_body(
+    redactor: &Redactor,
+    content_type: Option<&str>,
+    captured: &CapturedBody,
+) -> String {
+    if !is_text_content(content_type) {
+        return format!("[binary {} bytes]", captured.total_bytes);
+    }
+    let Ok(text) = std::str::from_utf8(&captured.bytes) else {
+        // 最大サイズで切り捨てた場合は末尾のマルチバイト文字が欠けることがある
+        if captured.truncated {
+            return redactor.redact_text(&String::from_utf8_lossy(&captured.bytes));
+        }
+        return format!("[binary {} bytes]", captured.total_bytes);
+    };
+
+    // JSONの場合は項目名でマスクし、切り捨て等で解析できない場合は文字列としてマスク
+    match serde_json::from_str::<Value>(text) {
+        Ok(value) if !captured.truncated => redactor.redact_value(&value).to_string(),
+        _ => redactor.redact_text(text),
+    }
+}
+
+// ボディをログ出力
+fn body_log(
+    request_id: &str,
+    kind: &'static str,
+    content_type: Option<&str>,
+    captured: CapturedBody,
+) {
+    // ボディが空の場合は出力しない
+    if captured.total_bytes == 0 {
+        return;
+    }
+    tracing::info!(
+        request_id = request_id,
+        kind = kind,
+        content_type = content_type,
+        size = captured.total_bytes,
+        truncated = captured.truncated,
+        completed = captured.completed,
+        body = %render_body(redactor(), content_type, &captured),
+        "body log"
+    );
+}
+
+fn content_type(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+// デバッグ用のリクエスト・レスポンスのボディのログ出力用ミドルウェア
+pub async fn body_log_middleware(req: Request, next: Next) -> Response {
+    let setting = body_log_setting();
+    if !setting.enabled {
+        return next.run(req).await;
+    }
+
+    // 対象のルート、または署名付きヘッダーで指定されたリクエストのみ出力する
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+    let enabled = setting.is_route_enabled(&route)
+        || setting.verify_debug_headers(
+            req.headers(),
+            req.method().as_str(),
+            req.uri().path(),
+            chrono::Utc::now().timestamp(),
+        );
+    if !enabled {
+        return next.run(req).await;
+    }
+
+    let request_id = req
+        .extensions()
+        .get::<Context>()
+        .and_then(|ctx| ctx.header.get("X-Request-Id"))
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("-")
+        .to_string();
+
+    // リクエストボディはハンドラーで読み取られた後に出力
+    let max_bytes = setting.max_bytes;
+    let req_content_type = content_type(req.headers());
+    let req_request_id = request_id.clone();
+    let req = req.map(|body| {
+        Body::new(BodyTap::new(body, max_bytes, move |captured| {
+            body_log(
+                &req_request_id,
+                "request",
+                req_content_type.as_deref(),
+                captured,
+            )
+        }))
+    });
+
+    let res = next.run(req).await;
+
+    // WebSocketへのアップグレードはボディが無いため対象外
+    if res.status() == StatusCode::SWITCHING_PROTOCOLS {
+        return res;
+    }
+
+    // レスポンスボディは送信完了後（中断時を含む）に出力
+    let res_content_type = content_type(res.headers());
+    res.map(|body| {
+        Body::new(BodyTap::new(body, max_bytes, move |captured| {
+            body_log(
+                &request_id,
+                "response",
+                res_content_type.as_deref(),
+                captured,
+            )
+        }))
+    })
+}
@@ -0,0 +1,186 @@
+#[cfg(test)]
+// ボディのログ出力のテスト
+mod body_log_test {
+    use crate::api::configs::config::{self, Environment};
+    use crate::api::configs::secret::Secret;
+    use crate::api::events::webhook_sender::sign;
+    use crate::api::loggers::redaction::{RedactionSetting, Redactor};
+    use crate::api::middleware::body_log_middleware::{
+        BodyLogSetting, BodyTap, CapturedBody, HEADER_DEBUG_SIGNATURE, HEADER_DEBUG_TIMESTAMP,
+        debug_signature_message, render_body,
+    };
+    use axum::body::{Body, Bytes};
+    use axum::http::HeaderMap;
+    use std::sync::mpsc;
+
+    const SECRET: &str = "debug-secret";
+
+    fn setting(routes: &[&str], debug_secret: Option<&str>) -> BodyLogSetting {
+        BodyLogSetting {
+            enabled: true,
+            routes: routes.iter().map(|v| v.to_string()).collect(),
+            max_bytes: 16,
+            debug_secret: debug_secret.map(|secret| Secret::new(secret.to_string())),
+        }
+    }
+
+    fn redactor() -> Redactor {
+        Redactor::new(&RedactionSetting {
+            fields: vec!["email".to_string(), "password".to_string()],
+            patterns: vec![],
+            header_allowlist: vec![],
+        })
+    }
+
+    fn captured(body: &str, truncated: bool) -> CapturedBody {
+        CapturedBody {
+            bytes: body.as_bytes().to_vec(),
+            total_bytes: body.len(),
+            truncated,
+            completed: true,
+        }
+    }
+
+    fn debug_headers(timestamp: i64, signature: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HEADER_DEBUG_TIMESTAMP,
+            timestamp.to_string().parse().unwrap(),
+        );
+        headers.insert(HEADER_DEBUG_SIGNATURE, signature.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_disabled_in_production() {
+        let mut config = config::get_config().clone();
+        config.body_log_enabled = true;
+        config.env = Environment::Local;
+        assert!(BodyLogSetting::from_config(&config).enabled);
+
+        config.env = Environment::Production;
+        assert!(!BodyLogSetting::from_config(&config).enabled);
+    }
+
+    #[test]
+    fn test_is_route_enabled() {
+        let setting = setting(&["/api/v1/user/{uid}"], None);
+        assert!(setting.is_route_enabled("/api/v1/user/{uid}"));
+        assert!(!setting.is_route_enabled("/api/v1/users"));
+
+        assert!(self::setting(&["*"], None).is_route_enabled("/api/v1/users"));
+        assert!(!self::setting(&[], None).is_route_enabled("/api/v1/users"));
+    }
+
+    #[test]
+    fn test_verify_debug_headers() {
+        let setting = setting(&[], Some(SECRET));
+        let now = 1_700_000_000;
+        let message = debug_signature_message("POST", "/api/v1/user");
+        let signature = sign(SECRET, now, message.as_bytes());
+
+        assert!(setting.verify_debug_headers(
+            &debug_headers(now, &signature),
+            "POST",
+            "/api/v1/user",
+            now
+        ));
+
+        // 別のパス、期限切れ、秘密鍵の不一致、ヘッダー無し
+        assert!(!setting.verify_debug_headers(
+            &debug_headers(now, &signature),
+            "POST",
+            "/api/v1/users",
+            now
+        ));
+        assert!(!setting.verify_debug_headers(
+            &debug_headers(now, &signature),
+            "POST",
+            "/api/v1/user",
+            now + 600
+        ));
+        let other = sign("other-secret", now, message.as_bytes());
+        assert!(!setting.verify_debug_headers(
+            &debug_headers(now, &other),
+            "POST",
+            "/api/v1/user",
+            now
+        ));
+        assert!(!setting.verify_debug_headers(&HeaderMap::new(), "POST", "/api/v1/user", now));
+
+        // 秘密鍵が未設定の場合はヘッダーによる指定を無効にする
+        assert!(!self::setting(&[], None).verify_debug_headers(
+            &debug_headers(now, &signature),
+            "POST",
+            "/api/v1/user",
+            now
+        ));
+    }
+
+    #[test]
+    fn test_render_body() {
+        let redactor = redactor();
+
+        // JSONは項目名でマスク
+        let body = r#"{"email":"a@b.jp","password":"p","name":"taro"}"#;
+        assert_eq!(
+            render_body(&redactor, Some("application/json"), &captured(body, false)),
+            r#"{"email":"[REDACTED]","name":"taro","password":"[REDACTED]"}"#
+        );
+
+        // 切り捨てられたJSONは文字列としてマスク
+        assert_eq!(
+            render_body(
+                &redactor,
+                Some("application/json"),
+                &captured(r#"{"email":"a@b.jp","na"#, true)
+            ),
+            r#"{"email":"[REDACTED]","na"#
+        );
+
+        // バイナリは出力しない
+        assert_eq!(
+            render_body(
+                &redactor,
+                Some("application/octet-stream"),
+                &captured("abc", false)
+            ),
+            "[binary 3 bytes]"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_body_tap_passes_through_and_truncates() {
+        let (tx, rx) = mpsc::channel();
+        let chunks: Vec<Result<Bytes, std::io::Error>> =
+            vec![Ok(Bytes::from("0123456789")), Ok(Bytes::from("abcdefghij"))];
+        let inner = Body::from_stream(futures_util::stream::iter(chunks));
+        let body = Body::new(BodyTap::new(inner, 16, move |captured| {
+            tx.send(captured).unwrap()
+        }));
+
+        // ボディは最大サイズに関わらずそのまま転送される
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        assert_eq!(bytes, "0123456789abcdefghij");
+
+        let captured = rx.recv().unwrap();
+        assert_eq!(captured.bytes, b"0123456789abcdef");
+        assert_eq!(captured.total_bytes, 20);
+        assert!(captured.truncated);
+        assert!(captured.completed);
+    }
+
+    #[tokio::test]
+    async fn test_body_tap_reports_on_drop() {
+        let (tx, rx) = mpsc::channel();
+        let body = BodyTap::new(Body::from("abc"), 16, move |captured| {
+            tx.send(captured).unwrap()
+        });
+
+        // 読み取られずに破棄された場合も出力する
+        drop(body);
+        let captured = rx.recv().unwrap();
+        assert_eq!(captured.total_bytes, 0);
+        assert!(!captured.completed);
+    }
+}
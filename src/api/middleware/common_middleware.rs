@@ -1,7 +1,7 @@
 // axum
 use axum::{
-    extract::{Json, Request},
-    http::StatusCode,
+    extract::{Json, MatchedPath, Request},
+    http::{HeaderName, HeaderValue, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
@@ -9,61 +9,151 @@ use axum::{
 // json変換用マクロ
 use serde_json::json;
 
-// UUID
-use uuid::Uuid;
+// tracing
+use tracing::Instrument;
+
+// 標準ライブラリ
+use std::time::Instant;
 
 // 共通コンテキストのモジュール
 use crate::api::contexts::context;
+use crate::api::contexts::principal::{auth_setting, bearer_token};
+use crate::api::contexts::request_id::{self, REQUEST_ID_HEADER, request_id_setting};
+use crate::api::contexts::trusted_proxies::peer_ip;
 
 // ロガー用のモジュール
-use crate::api::loggers::logger::info;
+use crate::api::loggers::logger::{REQUEST_SPAN_NAME, start_request_log};
+
+// メトリクス、OpenTelemetry用のモジュール
+use crate::api::telemetry::metrics::app_metrics;
+use crate::api::telemetry::otel;
 
 pub async fn request_middleware(mut req: Request, next: Next) -> Response {
-    // リクエストヘッダー「X-Request-Id」にUUIDを設定
-    let request_id = Uuid::new_v4().to_string();
+    // 信頼するプロキシから受信した正しい形式のリクエストIDは引き継ぎ、それ以外は新しく生成する
+    // 接続元はクライアントのIPアドレスの判定と同じアドレスを使用する
+    let setting = request_id_setting();
+    let request_id = setting.resolve(req.headers(), peer_ip(&req));
+    let request_id_value = HeaderValue::from_str(&request_id).unwrap();
+
+    // リクエストヘッダー「X-Request-Id」にリクエストIDを設定（共通コンテキストから参照する）
     req.headers_mut()
-        .insert("X-Request-Id", request_id.parse().unwrap());
+        .insert(REQUEST_ID_HEADER, request_id_value.clone());
+
+    // 共通コンテキストを作成
+    let mut ctx = context::create_context(&req);
+
+    // ルートのテンプレート（例：/api/v1/user/{uid}）
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+
+    // リクエスト単位のspanを作成（後続の処理のログに共通の項目が追加される）
+    let span = tracing::info_span!(
+        REQUEST_SPAN_NAME,
+        request_id = request_id.as_str(),
+        method = ctx.method.as_str(),
+        uri = ctx.uri.as_str(),
+        // 認証済みユーザーの識別子（認証用ミドルウェアで設定）
+        user_id = tracing::field::Empty,
+        // OpenTelemetryが有効な場合のトレースID
+        trace_id = tracing::field::Empty,
+        otel.name = format!("{} {}", ctx.method, route).trim_end(),
+        otel.kind = "server",
+        otel.status_code = tracing::field::Empty,
+        http.route = route.as_str(),
+        http.response.status_code = tracing::field::Empty,
+    );
+
+    // 呼び出し元のトレースを引き継ぎ、ログにトレースIDを追加
+    otel::set_parent_from_headers(&span, req.headers());
+    ctx.trace_id = otel::trace_id(&span);
+    if let Some(trace_id) = &ctx.trace_id {
+        span.record("trace_id", trace_id.as_str());
+    }
 
     // リクエストに共通コンテキストのExtentionを追加
-    let ctx = context::create_context(&req);
     req.extensions_mut().insert(ctx.clone());
 
-    // リクエスト単位でログ出力
-    info(&ctx, "start request !!");
+    // 処理中のリクエスト数を記録（レスポンス返却時または中断時に減らす）
+    let in_flight = app_metrics().track_in_flight();
+
+    let start = Instant::now();
+    let fut = async move {
+        // リクエスト単位でログ出力
+        start_request_log(&ctx);
+
+        let mut res = next.run(req).await;
+
+        // 処理結果をspanとメトリクスに記録
+        let span = tracing::Span::current();
+        span.record("http.response.status_code", res.status().as_u16());
+        if res.status().is_server_error() {
+            span.record("otel.status_code", "ERROR");
+        }
+        let elapsed = start.elapsed();
+        otel::record_http_server_duration(ctx.method.as_str(), &route, res.status(), elapsed);
+        app_metrics().observe_http_request(ctx.method.as_str(), &route, res.status(), elapsed);
+        drop(in_flight);
 
-    next.run(req).await
+        // エラーを含む全てのレスポンスにリクエストIDを設定
+        let header_name = HeaderName::from_bytes(setting.header_name.as_bytes())
+            .unwrap_or(HeaderName::from_static("x-request-id"));
+        res.headers_mut().insert(header_name, request_id_value);
+
+        // アクセスログ用にレスポンスにも共通コンテキストを追加（認証用ミドルウェアで追加済みの場合は認証済みユーザーを含むためそのまま使用）
+        if res.extensions().get::<context::Context>().is_none() {
+            res.extensions_mut().insert(ctx);
+        }
+        res
+    }
+    .instrument(span);
+
+    // 外部APIの呼び出し時に転送するため、処理中のリクエストIDを設定して実行
+    request_id::scope(request_id, fut).await
+}
+
+// 認証エラーのレスポンス（リクエストIDのヘッダーはrequest_middlewareで設定する）
+fn auth_error_response(status: StatusCode) -> Response {
+    let msg = Json(json!({ "message": status.canonical_reason().unwrap_or_default() }));
+    (status, msg).into_response()
 }
 
 // 認証用ミドルウェア
-pub async fn auth_middleware(req: Request, next: Next) -> Response {
-    // 共通コンテキストからX-Request-Idを取得
-    let request_id = match req.extensions().get::<context::Context>() {
-        Some(ctx) => {
-            let x_request_id = ctx.header.get("X-Request-Id");
-            x_request_id.expect("-").to_str().unwrap()
-        }
-        None => "-",
+pub async fn auth_middleware(mut req: Request, next: Next) -> Response {
+    // Authorizationヘッダーからトークン値を取得（ヘッダーが無い、ASCII以外の文字を含む場合は400）
+    // トークン値が空の場合も400
+    let Some(token) = bearer_token(req.headers()) else {
+        return auth_error_response(StatusCode::BAD_REQUEST);
     };
 
-    // Authorizationヘッダーからトークン値を取得
-    let token = match req.headers().get("Authorization") {
-        Some(value) => {
-            let bearer_token = value.to_str().unwrap();
-            bearer_token.replace("Bearer ", "")
-        }
-        None => {
-            let msg = Json(json!({ "message": "Bad Request"}));
-            return (StatusCode::BAD_REQUEST, [("X-Request-Id", request_id)], msg).into_response();
-        }
+    // トークンを検証し、認証済みユーザーを取得
+    let Some(principal) = auth_setting().authenticate(token) else {
+        return auth_error_response(StatusCode::UNAUTHORIZED);
     };
 
-    // トークン値の空文字チェック
-    if token.is_empty() {
-        let msg = Json(json!({ "message": "Bad Request"}));
-        return (StatusCode::BAD_REQUEST, [("X-Request-Id", request_id)], msg).into_response();
+    // 認証済みユーザーをリクエストのspanに記録
+    tracing::Span::current().record("user_id", principal);
+
+    // 共通コンテキストに認証済みユーザーを設定（監査ログの操作者、ログのuser_idに使用）
+    let ctx = req
+        .extensions()
+        .get::<context::Context>()
+        .cloned()
+        .map(|mut ctx| {
+            ctx.principal = Some(principal.to_string());
+            ctx
+        });
+    if let Some(ctx) = &ctx {
+        req.extensions_mut().insert(ctx.clone());
     }
 
-    // TODO: 認証チェック処理を追加する
+    let mut res = next.run(req).await;
 
-    next.run(req).await
+    // アクセスログに認証済みユーザーを出力するため、レスポンスにも共通コンテキストを追加
+    if let Some(ctx) = ctx {
+        res.extensions_mut().insert(ctx);
+    }
+    res
 }
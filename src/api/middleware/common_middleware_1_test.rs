@@ -0,0 +1,125 @@
+#[cfg(test)]
+// リクエストIDの引き継ぎと返却のテスト
+mod request_id_test {
+    // 呼び出し元（信頼するプロキシ）から渡されるリクエストID
+    const INCOMING_REQUEST_ID: &str = "gateway-0001:abc.DEF_9";
+
+    fn response_request_id(res: &reqwest::Response) -> String {
+        res.headers()
+            .get("X-Request-Id")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn it_echoes_incoming_request_id() {
+        let client = reqwest::Client::new();
+        let res = client
+            .get("http://localhost:8080/api/v1/users")
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .header("X-Request-Id", INCOMING_REQUEST_ID)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), 200);
+        assert_eq!(response_request_id(&res), INCOMING_REQUEST_ID);
+    }
+
+    #[tokio::test]
+    async fn it_replaces_invalid_request_id() {
+        let client = reqwest::Client::new();
+        let res = client
+            .get("http://localhost:8080/api/v1/users")
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .header("X-Request-Id", "invalid id <script>")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), 200);
+        let request_id = response_request_id(&res);
+        assert!(uuid::Uuid::parse_str(&request_id).is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_generates_request_id() {
+        let client = reqwest::Client::new();
+        let res = client
+            .get("http://localhost:8080/api/v1/sample/get")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), 200);
+        let request_id = response_request_id(&res);
+        assert!(uuid::Uuid::parse_str(&request_id).is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_echoes_request_id_on_auth_error() {
+        let client = reqwest::Client::new();
+        let res = client
+            .get("http://localhost:8080/api/v1/users")
+            .header("X-Request-Id", INCOMING_REQUEST_ID)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), 400);
+        assert_eq!(response_request_id(&res), INCOMING_REQUEST_ID);
+    }
+
+    #[tokio::test]
+    async fn it_echoes_request_id_on_rejection() {
+        // 不正なJSON（axumのJsonエクストラクターで拒否される）
+        let client = reqwest::Client::new();
+        let res = client
+            .post("http://localhost:8080/api/v1/user")
+            .header("Content-Type", "application/json")
+            .header("X-Request-Id", INCOMING_REQUEST_ID)
+            .body("{invalid")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), 400);
+        assert_eq!(response_request_id(&res), INCOMING_REQUEST_ID);
+    }
+
+    #[tokio::test]
+    async fn it_echoes_request_id_on_not_found() {
+        let client = reqwest::Client::new();
+        let res = client
+            .get("http://localhost:8080/api/v1/not-found")
+            .header("X-Request-Id", INCOMING_REQUEST_ID)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), 404);
+        assert_eq!(response_request_id(&res), INCOMING_REQUEST_ID);
+    }
+
+    #[tokio::test]
+    async fn it_rejects_non_ascii_authorization() {
+        // ASCII以外の文字を含むAuthorizationヘッダーは400を返す（リクエストIDは1つのみ設定される）
+        let value = reqwest::header::HeaderValue::from_bytes("Bearer トークン".as_bytes()).unwrap();
+        let client = reqwest::Client::new();
+        let res = client
+            .get("http://localhost:8080/api/v1/users")
+            .header("Authorization", value)
+            .header("X-Request-Id", INCOMING_REQUEST_ID)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), 400);
+        assert_eq!(res.headers().get_all("X-Request-Id").iter().count(), 1);
+        assert_eq!(response_request_id(&res), INCOMING_REQUEST_ID);
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["message"], "Bad Request");
+    }
+}
@@ -0,0 +1,107 @@
+// axum
+use axum::http::{Response, header};
+
+// HTTPボディ
+use http_body::Body;
+
+// tower_http
+use tower_http::compression::{
+    CompressionLayer,
+    predicate::{And, Predicate, SizeAbove},
+};
+use tower_http::decompression::RequestDecompressionLayer;
+
+// 標準ライブラリ
+use std::sync::Arc;
+
+// configsモジュール
+use crate::api::configs::config::Config;
+
+// レスポンスの圧縮方式
+pub const COMPRESSION_GZIP: &str = "gzip";
+pub const COMPRESSION_BR: &str = "br";
+pub const COMPRESSION_ZSTD: &str = "zstd";
+pub const COMPRESSION_ALGORITHMS: [&str; 3] = [COMPRESSION_GZIP, COMPRESSION_BR, COMPRESSION_ZSTD];
+
+// カンマ区切りの設定値を小文字のリストに変換する関数
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+// 圧縮するレスポンスのContent-Type（パラメーターを除いた値で比較する）
+#[derive(Clone, Debug)]
+pub struct ContentTypeAllowlist(Arc<Vec<String>>);
+
+impl ContentTypeAllowlist {
+    pub fn new(value: &str) -> Self {
+        ContentTypeAllowlist(Arc::new(split_list(value)))
+    }
+
+    pub fn is_allowed(&self, content_type: &str) -> bool {
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        self.0.contains(&mime)
+    }
+}
+
+impl Predicate for ContentTypeAllowlist {
+    fn should_compress<B>(&self, response: &Response<B>) -> bool
+    where
+        B: Body,
+    {
+        response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|content_type| self.is_allowed(content_type))
+    }
+}
+
+// レスポンスの圧縮用のレイヤーを作成する関数
+// クライアントのAccept-Encodingから方式を選択し、最小サイズ以上かつ対象のContent-Typeの場合のみ圧縮する
+pub fn compression_layer(
+    config: &Config,
+) -> CompressionLayer<And<SizeAbove, ContentTypeAllowlist>> {
+    let algorithms = split_list(&config.compression_algorithms);
+    let enabled = |name: &str| algorithms.iter().any(|algorithm| algorithm == name);
+    CompressionLayer::new()
+        .gzip(enabled(COMPRESSION_GZIP))
+        .br(enabled(COMPRESSION_BR))
+        .zstd(enabled(COMPRESSION_ZSTD))
+        .compress_when(
+            SizeAbove::new(config.compression_min_size)
+                .and(ContentTypeAllowlist::new(&config.compression_content_types)),
+        )
+}
+
+// 圧縮されたリクエストボディ（Content-Encoding: gzip）の展開用のレイヤーを作成する関数
+// 展開後のサイズはリクエストボディの最大サイズで制限する
+pub fn request_decompression_layer(config: &Config) -> RequestDecompressionLayer {
+    // 無効な場合は展開せずにそのまま渡す
+    RequestDecompressionLayer::new()
+        .gzip(config.request_decompression_enabled)
+        .pass_through_unaccepted(!config.request_decompression_enabled)
+}
+
+// 圧縮方式の設定値をチェックする関数
+pub fn validate_algorithms(value: &str) -> Result<(), String> {
+    match split_list(value)
+        .into_iter()
+        .find(|name| !COMPRESSION_ALGORITHMS.contains(&name.as_str()))
+    {
+        Some(name) => Err(format!(
+            "{} のいずれかを指定してください: {}",
+            COMPRESSION_ALGORITHMS.join(" | "),
+            name
+        )),
+        None => Ok(()),
+    }
+}
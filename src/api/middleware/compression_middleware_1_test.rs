@@ -0,0 +1,107 @@
+#[cfg(test)]
+// レスポンスの圧縮とリクエストボディの展開のテスト
+mod compression_test {
+    use crate::api::middleware::compression_middleware::{
+        ContentTypeAllowlist, validate_algorithms,
+    };
+    use flate2::{Compression, write::GzEncoder};
+    use std::io::Write;
+
+    #[test]
+    fn test_content_type_allowlist() {
+        let allowlist = ContentTypeAllowlist::new("application/json, Text/Plain");
+        assert!(allowlist.is_allowed("application/json"));
+        assert!(allowlist.is_allowed("text/plain; charset=utf-8"));
+        assert!(!allowlist.is_allowed("text/event-stream"));
+        assert!(!allowlist.is_allowed("image/png"));
+    }
+
+    #[test]
+    fn test_validate_algorithms() {
+        assert!(validate_algorithms("gzip, br,zstd").is_ok());
+        assert!(validate_algorithms("").is_ok());
+        assert!(validate_algorithms("gzip,deflate").is_err());
+    }
+
+    #[tokio::test]
+    async fn it_compresses_large_response() {
+        // 最小サイズ以上のレスポンスはAccept-Encodingで選択した方式で圧縮する
+        let url = "http://localhost:8080/api/v1/sample/post";
+        let name = "a".repeat(2048);
+        let client = reqwest::Client::new();
+        for encoding in ["gzip", "br", "zstd"] {
+            let res = client
+                .post(url)
+                .header("Accept-Encoding", encoding)
+                .json(&serde_json::json!({ "name": name }))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), 200);
+            assert_eq!(res.headers()["Content-Encoding"], encoding);
+            // CORSのVaryと合わせてaccept-encodingが含まれること
+            let vary: Vec<_> = res.headers().get_all("Vary").iter().collect();
+            assert!(vary.iter().any(|value| *value == "accept-encoding"));
+        }
+
+        // Accept-Encodingが無い場合は圧縮しない
+        let res = client
+            .post(url)
+            .json(&serde_json::json!({ "name": name }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        assert!(!res.headers().contains_key("Content-Encoding"));
+    }
+
+    #[tokio::test]
+    async fn it_does_not_compress_small_response() {
+        let url = "http://localhost:8080/api/v1/sample/get";
+        let client = reqwest::Client::new();
+        let res = client
+            .get(url)
+            .header("Accept-Encoding", "gzip")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        assert!(!res.headers().contains_key("Content-Encoding"));
+    }
+
+    #[tokio::test]
+    async fn it_decompresses_gzip_request_body() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(br#"{"name":"gzip"}"#).unwrap();
+        let body = encoder.finish().unwrap();
+
+        let url = "http://localhost:8080/api/v1/sample/post";
+        let client = reqwest::Client::new();
+        let res = client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("Content-Encoding", "gzip")
+            .body(body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["message"], "name: gzip");
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unsupported_content_encoding() {
+        let url = "http://localhost:8080/api/v1/sample/post";
+        let client = reqwest::Client::new();
+        let res = client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("Content-Encoding", "br")
+            .body(r#"{"name":"br"}"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 415);
+    }
+}
@@ -0,0 +1,276 @@
+// axum
+use axum::http::{HeaderName, HeaderValue, Method};
+
+// tower_http
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer, ExposeHeaders};
+
+// configsモジュール
+use crate::api::configs::config::Config;
+use crate::api::configs::config_reload::ReloadableSetting;
+
+// 全てを許可する場合の設定値
+const WILDCARD: &str = "*";
+
+// サブドメインのワイルドカード（https://*.example.com の「*.」の部分）
+const SUBDOMAIN_WILDCARD: &str = "*.";
+
+// CORSの設定を上書きできるルートのグループ
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CorsGroup {
+    // 認証無しのAPI
+    Public,
+    // 認証有りのAPI
+    Auth,
+    // 管理用のAPI
+    Admin,
+}
+
+impl CorsGroup {
+    pub const ALL: [CorsGroup; 3] = [CorsGroup::Public, CorsGroup::Auth, CorsGroup::Admin];
+
+    // 設定の項目名に使うグループ名
+    pub fn name(&self) -> &'static str {
+        match self {
+            CorsGroup::Public => "public",
+            CorsGroup::Auth => "auth",
+            CorsGroup::Admin => "admin",
+        }
+    }
+
+    // グループの許可するオリジンの設定値
+    pub fn origins_override<'a>(&self, config: &'a Config) -> Option<&'a str> {
+        match self {
+            CorsGroup::Public => config.cors_public_allow_origins.as_deref(),
+            CorsGroup::Auth => config.cors_auth_allow_origins.as_deref(),
+            CorsGroup::Admin => config.cors_admin_allow_origins.as_deref(),
+        }
+    }
+
+    // グループの許可するメソッドの設定値
+    pub fn methods_override<'a>(&self, config: &'a Config) -> Option<&'a str> {
+        match self {
+            CorsGroup::Public => config.cors_public_allow_methods.as_deref(),
+            CorsGroup::Auth => config.cors_auth_allow_methods.as_deref(),
+            CorsGroup::Admin => config.cors_admin_allow_methods.as_deref(),
+        }
+    }
+
+    // 許可するオリジンの設定値（グループの設定が無い場合は共通の設定）
+    pub fn allow_origins<'a>(&self, config: &'a Config) -> &'a str {
+        self.origins_override(config)
+            .unwrap_or(&config.cors_allow_origins)
+    }
+
+    // 許可するメソッドの設定値（グループの設定が無い場合は共通の設定）
+    pub fn allow_methods<'a>(&self, config: &'a Config) -> &'a str {
+        self.methods_override(config)
+            .unwrap_or(&config.cors_allow_methods)
+    }
+}
+
+// 許可するオリジン
+#[derive(Clone, Debug, PartialEq)]
+pub enum OriginPattern {
+    // 全てのオリジン
+    Any,
+    // 完全一致（例：https://app.example.com）
+    Exact(String),
+    // サブドメインのワイルドカード（例：https://*.example.com は prefix「https://」、suffix「.example.com」）
+    Subdomain { prefix: String, suffix: String },
+}
+
+impl OriginPattern {
+    // 設定値を変換する関数（スキーム・ホスト・ポート以外を含む場合はエラー）
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        if value == WILDCARD {
+            return Ok(OriginPattern::Any);
+        }
+
+        let (scheme, rest) = value
+            .split_once("://")
+            .ok_or_else(|| format!("不正なオリジンです: {}", value))?;
+        let wildcard = rest.starts_with(SUBDOMAIN_WILDCARD);
+        let host = if wildcard {
+            &rest[SUBDOMAIN_WILDCARD.len()..]
+        } else {
+            rest
+        };
+        if host.contains('*') {
+            return Err(format!(
+                "ワイルドカードは先頭のサブドメインにのみ指定できます: {}",
+                value
+            ));
+        }
+
+        // ワイルドカードの部分は仮のサブドメインに置き換えて形式をチェック
+        let placeholder = if wildcard {
+            format!("{}://x.{}", scheme, host)
+        } else {
+            value.to_string()
+        };
+        let url = url::Url::parse(&placeholder)
+            .map_err(|err| format!("不正なオリジンです（{}）: {}", err, value))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!(
+                "スキームはhttpまたはhttpsを指定してください: {}",
+                value
+            ));
+        }
+        if url.host().is_none()
+            || url.path() != "/"
+            || url.query().is_some()
+            || url.fragment().is_some()
+            || !url.username().is_empty()
+            || url.password().is_some()
+        {
+            return Err(format!(
+                "スキーム・ホスト・ポート以外は指定できません: {}",
+                value
+            ));
+        }
+
+        // ブラウザが送るOriginヘッダーと同じ形式（小文字、既定のポートは省略）にする
+        let origin = url.origin().ascii_serialization();
+        if !wildcard {
+            return Ok(OriginPattern::Exact(origin));
+        }
+        let prefix = format!("{}://", url.scheme());
+        let suffix = origin[prefix.len() + 1..].to_string();
+        Ok(OriginPattern::Subdomain { prefix, suffix })
+    }
+
+    // オリジンが一致するか判定（ワイルドカードは1階層以上のサブドメインに一致）
+    pub fn matches(&self, origin: &str) -> bool {
+        match self {
+            OriginPattern::Any => true,
+            OriginPattern::Exact(exact) => exact.eq_ignore_ascii_case(origin),
+            OriginPattern::Subdomain { prefix, suffix } => {
+                let origin = origin.to_ascii_lowercase();
+                let Some(subdomain) = origin
+                    .strip_prefix(prefix.as_str())
+                    .and_then(|rest| rest.strip_suffix(suffix.as_str()))
+                else {
+                    return false;
+                };
+                !subdomain.is_empty()
+                    && subdomain.split('.').all(|label| {
+                        !label.is_empty()
+                            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                    })
+            }
+        }
+    }
+}
+
+// カンマ区切りの設定値をリストに変換する関数
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(|v| v.trim()).filter(|v| !v.is_empty())
+}
+
+// 許可するオリジンの設定値を変換する関数
+pub fn parse_origins(value: &str) -> Result<Vec<OriginPattern>, String> {
+    split_list(value).map(OriginPattern::parse).collect()
+}
+
+// 許可するメソッドの設定値を変換する関数（*の場合は全て許可）
+pub fn parse_methods(value: &str) -> Result<AllowMethods, String> {
+    if value.trim() == WILDCARD {
+        return Ok(AllowMethods::any());
+    }
+    split_list(value)
+        .map(|v| {
+            Method::from_bytes(v.to_uppercase().as_bytes())
+                .map_err(|_| format!("不正なメソッドです: {}", v))
+        })
+        .collect::<Result<Vec<Method>, String>>()
+        .map(AllowMethods::list)
+}
+
+// ヘッダー名の設定値を変換する関数
+fn parse_header_names(value: &str) -> Result<Vec<HeaderName>, String> {
+    split_list(value)
+        .map(|v| {
+            HeaderName::from_bytes(v.as_bytes()).map_err(|_| format!("不正なヘッダー名です: {}", v))
+        })
+        .collect()
+}
+
+// 許可するヘッダーの設定値を変換する関数（*の場合は全て許可）
+pub fn parse_allow_headers(value: &str) -> Result<AllowHeaders, String> {
+    if value.trim() == WILDCARD {
+        return Ok(AllowHeaders::any());
+    }
+    parse_header_names(value).map(AllowHeaders::list)
+}
+
+// 公開するヘッダーの設定値を変換する関数（*の場合は全て公開）
+pub fn parse_expose_headers(value: &str) -> Result<ExposeHeaders, String> {
+    if value.trim() == WILDCARD {
+        return Ok(ExposeHeaders::any());
+    }
+    parse_header_names(value).map(ExposeHeaders::list)
+}
+
+// 許可するオリジンの設定（設定の再読み込み時に作り直す）
+#[derive(Clone, Debug, Default)]
+pub struct CorsOriginSetting {
+    pub public: Vec<OriginPattern>,
+    pub auth: Vec<OriginPattern>,
+    pub admin: Vec<OriginPattern>,
+}
+
+impl CorsOriginSetting {
+    pub fn from_config(config: &Config) -> Self {
+        // 起動時と再読み込み時に検証済みのため、不正な値は無視する
+        let origins =
+            |group: CorsGroup| parse_origins(group.allow_origins(config)).unwrap_or_default();
+        CorsOriginSetting {
+            public: origins(CorsGroup::Public),
+            auth: origins(CorsGroup::Auth),
+            admin: origins(CorsGroup::Admin),
+        }
+    }
+
+    // 許可するオリジンか判定
+    pub fn is_allowed(&self, group: CorsGroup, origin: &HeaderValue) -> bool {
+        let Ok(origin) = origin.to_str() else {
+            return false;
+        };
+        let patterns = match group {
+            CorsGroup::Public => &self.public,
+            CorsGroup::Auth => &self.auth,
+            CorsGroup::Admin => &self.admin,
+        };
+        patterns.iter().any(|pattern| pattern.matches(origin))
+    }
+}
+
+// 設定のインスタンス（プロセス内で共有し、設定の再読み込み時に作り直す）
+static CORS_ORIGIN_SETTING: ReloadableSetting<CorsOriginSetting> =
+    ReloadableSetting::new(CorsOriginSetting::from_config);
+
+// ルートのグループ用のCORSのレイヤーを作成する関数
+// 許可するオリジンは設定の再読み込み時に反映し、それ以外の項目の変更は再起動が必要
+pub fn cors_layer(config: &Config, group: CorsGroup) -> CorsLayer {
+    let layer = CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(move |origin, _| {
+            CORS_ORIGIN_SETTING.get().is_allowed(group, origin)
+        }))
+        .max_age(config.cors_max_age)
+        .allow_credentials(config.cors_allow_credentials);
+
+    // 起動時に検証済みのため、不正な値の場合は設定しない
+    let layer = match parse_methods(group.allow_methods(config)) {
+        Ok(methods) => layer.allow_methods(methods),
+        Err(_) => layer,
+    };
+    let layer = match parse_allow_headers(&config.cors_allow_headers) {
+        Ok(headers) => layer.allow_headers(headers),
+        Err(_) => layer,
+    };
+    match parse_expose_headers(&config.cors_expose_headers) {
+        Ok(headers) => layer.expose_headers(headers),
+        Err(_) => layer,
+    }
+}
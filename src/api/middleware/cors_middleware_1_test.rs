@@ -0,0 +1,151 @@
+#[cfg(test)]
+// CORSのテスト
+mod cors_test {
+    use crate::api::configs::config;
+    use crate::api::middleware::cors_middleware::{CorsGroup, CorsOriginSetting, OriginPattern};
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_parse_origin() {
+        // ブラウザが送るOriginヘッダーと同じ形式にする
+        assert_eq!(
+            OriginPattern::parse("HTTPS://App.Example.com:443/").unwrap(),
+            OriginPattern::Exact("https://app.example.com".to_string())
+        );
+        assert_eq!(
+            OriginPattern::parse("http://localhost:3000").unwrap(),
+            OriginPattern::Exact("http://localhost:3000".to_string())
+        );
+        assert_eq!(
+            OriginPattern::parse("https://*.example.com:8443").unwrap(),
+            OriginPattern::Subdomain {
+                prefix: "https://".to_string(),
+                suffix: ".example.com:8443".to_string(),
+            }
+        );
+        assert_eq!(OriginPattern::parse("*").unwrap(), OriginPattern::Any);
+
+        // スキーム・ホスト・ポート以外やワイルドカードの位置が不正な場合はエラー
+        for value in [
+            "localhost:3000",
+            "ftp://example.com",
+            "https://example.com/app",
+            "https://example.com?a=1",
+            "https://user@example.com",
+            "https://api.*.example.com",
+            "https://*example.com",
+            "https://exa mple.com",
+        ] {
+            assert!(OriginPattern::parse(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_match_origin() {
+        let exact = OriginPattern::parse("https://app.example.com").unwrap();
+        assert!(exact.matches("https://app.example.com"));
+        assert!(exact.matches("https://APP.example.com"));
+        assert!(!exact.matches("http://app.example.com"));
+        assert!(!exact.matches("https://app.example.com:8443"));
+
+        // ワイルドカードは1階層以上のサブドメインに一致
+        let wildcard = OriginPattern::parse("https://*.example.com").unwrap();
+        assert!(wildcard.matches("https://app.example.com"));
+        assert!(wildcard.matches("https://a.b.example.com"));
+        assert!(!wildcard.matches("https://example.com"));
+        assert!(!wildcard.matches("https://.example.com"));
+        assert!(!wildcard.matches("https://evil-example.com"));
+        assert!(!wildcard.matches("https://app.example.com.evil.com"));
+        assert!(!wildcard.matches("https://a/b.example.com"));
+        assert!(!wildcard.matches("http://app.example.com"));
+    }
+
+    #[test]
+    fn test_group_overrides() {
+        let mut config = config::get_config().clone();
+        config.cors_allow_origins = "https://app.example.com, https://*.example.net".to_string();
+        config.cors_admin_allow_origins = Some("https://admin.example.com".to_string());
+        let setting = CorsOriginSetting::from_config(&config);
+
+        let app = HeaderValue::from_static("https://app.example.com");
+        let sub = HeaderValue::from_static("https://x.example.net");
+        let admin = HeaderValue::from_static("https://admin.example.com");
+
+        // グループの設定が無い場合は共通の設定を使用する
+        for group in [CorsGroup::Public, CorsGroup::Auth] {
+            assert!(setting.is_allowed(group, &app));
+            assert!(setting.is_allowed(group, &sub));
+            assert!(!setting.is_allowed(group, &admin));
+        }
+        assert!(setting.is_allowed(CorsGroup::Admin, &admin));
+        assert!(!setting.is_allowed(CorsGroup::Admin, &app));
+    }
+
+    #[tokio::test]
+    async fn it_responds_to_preflight_request() {
+        // 認証有りのAPIでもプリフライトリクエストは認証せずに応答する
+        let url = "http://localhost:8080/api/v1/users";
+        let client = reqwest::Client::new();
+        let res = client
+            .request(reqwest::Method::OPTIONS, url)
+            .header("Origin", "http://localhost:3000")
+            .header("Access-Control-Request-Method", "GET")
+            .header("Access-Control-Request-Headers", "authorization")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        let headers = res.headers();
+        assert_eq!(
+            headers["access-control-allow-origin"],
+            "http://localhost:3000"
+        );
+        assert_eq!(headers["access-control-allow-credentials"], "true");
+        assert!(
+            headers["access-control-allow-methods"]
+                .to_str()
+                .unwrap()
+                .contains("GET")
+        );
+        assert!(headers.contains_key("access-control-max-age"));
+    }
+
+    #[tokio::test]
+    async fn it_exposes_request_id_header() {
+        let url = "http://localhost:8080/api/v1/sample/get";
+        let client = reqwest::Client::new();
+        let res = client
+            .get(url)
+            .header("Origin", "http://localhost:3000")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        let headers = res.headers();
+        assert_eq!(
+            headers["access-control-allow-origin"],
+            "http://localhost:3000"
+        );
+        assert!(
+            headers["access-control-expose-headers"]
+                .to_str()
+                .unwrap()
+                .to_lowercase()
+                .contains("x-request-id")
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unknown_origin() {
+        let url = "http://localhost:8080/api/v1/sample/get";
+        let client = reqwest::Client::new();
+        let res = client
+            .get(url)
+            .header("Origin", "http://evil.example.com")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        assert!(!res.headers().contains_key("access-control-allow-origin"));
+    }
+}
@@ -0,0 +1,173 @@
+// axum
+use axum::{
+    extract::{Json, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+// CIDR
+use ipnet::IpNet;
+
+// json変換用マクロ
+use serde_json::json;
+
+// 標準ライブラリ
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+// configsモジュール
+use crate::api::configs::config::Config;
+use crate::api::configs::config_reload::ReloadableSetting;
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// 接続元のIPアドレスで制限するルートのグループ（router.rsで指定する）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IpFilterGroup {
+    // 認証無しのAPI
+    Public,
+    // 認証有りのAPI
+    Auth,
+    // 管理用のAPI（ユーザーの削除等の管理者向けの操作を含む）
+    Admin,
+}
+
+impl IpFilterGroup {
+    pub const ALL: [IpFilterGroup; 3] = [
+        IpFilterGroup::Public,
+        IpFilterGroup::Auth,
+        IpFilterGroup::Admin,
+    ];
+
+    // 設定の項目名に使うグループ名
+    pub fn name(&self) -> &'static str {
+        match self {
+            IpFilterGroup::Public => "public",
+            IpFilterGroup::Auth => "auth",
+            IpFilterGroup::Admin => "admin",
+        }
+    }
+
+    // グループの許可するIPアドレスの設定値
+    pub fn allow<'a>(&self, config: &'a Config) -> Option<&'a str> {
+        match self {
+            IpFilterGroup::Public => config.ip_filter_public_allow.as_deref(),
+            IpFilterGroup::Auth => config.ip_filter_auth_allow.as_deref(),
+            IpFilterGroup::Admin => config.ip_filter_admin_allow.as_deref(),
+        }
+    }
+
+    // グループの拒否するIPアドレスの設定値
+    pub fn deny<'a>(&self, config: &'a Config) -> Option<&'a str> {
+        match self {
+            IpFilterGroup::Public => config.ip_filter_public_deny.as_deref(),
+            IpFilterGroup::Auth => config.ip_filter_auth_deny.as_deref(),
+            IpFilterGroup::Admin => config.ip_filter_admin_deny.as_deref(),
+        }
+    }
+}
+
+// CIDRまたはIPアドレスのカンマ区切りの設定値を変換する関数
+pub fn parse_cidrs(value: &str) -> Result<Vec<IpNet>, String> {
+    value
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|item| {
+            item.parse::<IpNet>()
+                .or_else(|_| item.parse::<IpAddr>().map(IpNet::from))
+                .map_err(|_| format!("不正なCIDRまたはIPアドレスです: {}", item))
+        })
+        .collect()
+}
+
+// グループの許可・拒否するIPアドレスの範囲
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IpFilter {
+    // 空の場合は拒否する範囲以外を全て許可する
+    pub allow: Vec<IpNet>,
+    pub deny: Vec<IpNet>,
+}
+
+impl IpFilter {
+    // 拒否する範囲を優先し、許可する範囲がある場合は該当するIPアドレスのみ許可する
+    // IPアドレスが不明な場合は許可する範囲が無い場合のみ許可する
+    pub fn is_allowed(&self, ip: Option<IpAddr>) -> bool {
+        let Some(ip) = ip else {
+            return self.allow.is_empty();
+        };
+        if self.deny.iter().any(|net| net.contains(&ip)) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(|net| net.contains(&ip))
+    }
+}
+
+// IPアドレスの制限の設定
+#[derive(Clone, Debug)]
+pub struct IpFilterSetting {
+    pub groups: HashMap<IpFilterGroup, IpFilter>,
+}
+
+impl IpFilterSetting {
+    pub fn from_config(config: &Config) -> Self {
+        // 起動時と再読み込み時に検証済みのため、不正な値は無視する
+        let parse =
+            |value: Option<&str>| parse_cidrs(value.unwrap_or_default()).unwrap_or_default();
+        let groups = IpFilterGroup::ALL
+            .iter()
+            .map(|group| {
+                let filter = IpFilter {
+                    allow: parse(group.allow(config)),
+                    deny: parse(group.deny(config)),
+                };
+                (*group, filter)
+            })
+            .collect();
+        IpFilterSetting { groups }
+    }
+
+    pub fn is_allowed(&self, group: IpFilterGroup, ip: Option<IpAddr>) -> bool {
+        self.groups
+            .get(&group)
+            .is_none_or(|filter| filter.is_allowed(ip))
+    }
+}
+
+// 設定のインスタンス（プロセス内で共有し、設定の再読み込み時に作り直す）
+static IP_FILTER_SETTING: ReloadableSetting<IpFilterSetting> =
+    ReloadableSetting::new(IpFilterSetting::from_config);
+
+// 許可しない場合のレスポンス（リクエストIDのヘッダーはrequest_middlewareで設定する）
+pub fn forbidden_response() -> Response {
+    let msg = Json(json!({ "message": "Forbidden"}));
+    (StatusCode::FORBIDDEN, msg).into_response()
+}
+
+// クライアントのIPアドレスで制限するミドルウェア（許可しない場合は403を返す）
+pub async fn ip_filter_middleware(
+    State(group): State<IpFilterGroup>,
+    req: Request,
+    next: Next,
+) -> Response {
+    // 共通コンテキストからX-Request-Id、クライアントのIPアドレスを取得
+    let ctx = req.extensions().get::<Context>();
+    let client_ip = ctx.and_then(|ctx| ctx.client_ip);
+    if IP_FILTER_SETTING.get().is_allowed(group, client_ip) {
+        return next.run(req).await;
+    }
+
+    let request_id = ctx
+        .and_then(|ctx| ctx.header.get("X-Request-Id"))
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("-");
+    tracing::warn!(
+        request_id = request_id,
+        client_ip = client_ip.map(|ip| ip.to_string()).as_deref().unwrap_or("-"),
+        group = group.name(),
+        "許可されていないIPアドレスからのリクエストを拒否しました"
+    );
+    forbidden_response()
+}
@@ -0,0 +1,127 @@
+#[cfg(test)]
+// 接続元のIPアドレスの制限のテスト
+mod ip_filter_test {
+    use crate::api::configs::config;
+    use crate::api::middleware::ip_filter_middleware::{
+        IpFilter, IpFilterGroup, IpFilterSetting, forbidden_response, parse_cidrs,
+    };
+    use axum::http::StatusCode;
+    use std::net::IpAddr;
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    #[test]
+    fn test_parse_cidrs() {
+        let cidrs = parse_cidrs(" 10.0.0.0/8, 192.0.2.1 ,2001:db8::/32,").unwrap();
+        assert_eq!(cidrs.len(), 3);
+        assert_eq!(cidrs[1].to_string(), "192.0.2.1/32");
+        assert!(parse_cidrs("").unwrap().is_empty());
+        assert!(parse_cidrs("10.0.0.0/33").is_err());
+        assert!(parse_cidrs("*").is_err());
+    }
+
+    #[test]
+    fn test_is_allowed() {
+        // 設定が無い場合は全て許可する
+        let filter = IpFilter::default();
+        assert!(filter.is_allowed(ip("192.0.2.1")));
+        assert!(filter.is_allowed(None));
+
+        // 拒否する範囲を優先する
+        let filter = IpFilter {
+            allow: parse_cidrs("10.0.0.0/8").unwrap(),
+            deny: parse_cidrs("10.0.1.0/24").unwrap(),
+        };
+        assert!(filter.is_allowed(ip("10.0.0.1")));
+        assert!(!filter.is_allowed(ip("10.0.1.1")));
+        assert!(!filter.is_allowed(ip("192.0.2.1")));
+        // IPアドレスが不明な場合は許可する範囲がある場合は拒否する
+        assert!(!filter.is_allowed(None));
+
+        // 拒否する範囲のみの場合はそれ以外を許可する
+        let filter = IpFilter {
+            allow: Vec::new(),
+            deny: parse_cidrs("2001:db8::/32").unwrap(),
+        };
+        assert!(!filter.is_allowed(ip("2001:db8::1")));
+        assert!(filter.is_allowed(ip("192.0.2.1")));
+        assert!(filter.is_allowed(None));
+    }
+
+    #[test]
+    fn test_group_settings() {
+        let mut config = config::get_config().clone();
+        config.ip_filter_admin_allow = Some("10.0.0.0/8".to_string());
+        config.ip_filter_admin_deny = None;
+        config.ip_filter_public_deny = Some("192.0.2.0/24".to_string());
+        let setting = IpFilterSetting::from_config(&config);
+
+        // グループごとに判定する
+        assert!(setting.is_allowed(IpFilterGroup::Admin, ip("10.0.0.1")));
+        assert!(!setting.is_allowed(IpFilterGroup::Admin, ip("192.0.2.1")));
+        assert!(!setting.is_allowed(IpFilterGroup::Public, ip("192.0.2.1")));
+        assert!(setting.is_allowed(IpFilterGroup::Auth, ip("192.0.2.1")));
+    }
+
+    #[test]
+    fn test_forbidden_response() {
+        // リクエストIDは設定されたヘッダー名でrequest_middlewareが設定するため含めない
+        let res = forbidden_response();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        assert!(!res.headers().contains_key("X-Request-Id"));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_denied_ip() {
+        // テスト環境は管理用のAPIで198.51.100.0/24を拒否する（localhostは信頼するプロキシ）
+        let client = reqwest::Client::new();
+        let url = "http://localhost:8080/api/v1/admin/config";
+        let res = client
+            .get(url)
+            .header("Authorization", "Bearer xxx")
+            .header("X-Forwarded-For", "198.51.100.7")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 403);
+        assert!(res.headers().contains_key("X-Request-Id"));
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["message"], "Forbidden");
+
+        // ユーザーの削除も管理用のAPIと同じ制限を適用する
+        let url = "http://localhost:8080/api/v1/user/ip-filter-test";
+        let res = client
+            .delete(url)
+            .header("Authorization", "Bearer xxx")
+            .header("X-Forwarded-For", "198.51.100.7")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 403);
+
+        // 他のグループのAPIは制限しない
+        let url = "http://localhost:8080/api/v1/sample/get";
+        let res = client
+            .get(url)
+            .header("X-Forwarded-For", "198.51.100.7")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn it_allows_other_ip() {
+        let url = "http://localhost:8080/api/v1/admin/config";
+        let res = reqwest::Client::new()
+            .get(url)
+            .header("Authorization", "Bearer xxx")
+            .header("X-Forwarded-For", "203.0.113.7")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+    }
+}
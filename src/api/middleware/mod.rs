@@ -1 +1,20 @@
+pub mod body_log_middleware;
 pub mod common_middleware;
+pub mod compression_middleware;
+pub mod cors_middleware;
+pub mod ip_filter_middleware;
+pub mod rate_limit_middleware;
+pub mod rate_limit_store;
+pub mod request_limit_middleware;
+pub mod security_headers_middleware;
+
+// テストコード用のモジュール
+mod body_log_middleware_1_test;
+mod common_middleware_1_test;
+mod compression_middleware_1_test;
+mod cors_middleware_1_test;
+mod ip_filter_middleware_1_test;
+mod rate_limit_middleware_1_test;
+mod rate_limit_store_1_test;
+mod request_limit_middleware_1_test;
+mod security_headers_middleware_1_test;
@@ -0,0 +1,198 @@
+// axum
+use axum::{
+    extract::{Json, Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+// json変換用マクロ
+use serde_json::json;
+
+// 標準ライブラリ
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+// configsモジュール
+use crate::api::configs::config::Config;
+use crate::api::configs::config_reload::ReloadableSetting;
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// 認証用のモジュール
+use crate::api::contexts::principal::{Credential, auth_setting, bearer_token};
+
+// レート制限の状態の保存先
+use super::rate_limit_store::{Quota, RateLimitDecision, rate_limit_store};
+
+// レスポンスヘッダー（IETFのRateLimitヘッダーのドラフト）
+pub const HEADER_RATE_LIMIT_LIMIT: &str = "RateLimit-Limit";
+pub const HEADER_RATE_LIMIT_REMAINING: &str = "RateLimit-Remaining";
+pub const HEADER_RATE_LIMIT_RESET: &str = "RateLimit-Reset";
+pub const HEADER_RATE_LIMIT_POLICY: &str = "RateLimit-Policy";
+
+// ポリシー名（上限は設定の「RATE_LIMIT_{ポリシー名}」で指定する）
+pub const POLICY_CREATE_USER: &str = "create_user";
+pub const POLICY_AUTH: &str = "auth";
+pub const POLICY_ADMIN: &str = "admin";
+pub const RATE_LIMIT_POLICIES: [&str; 3] = [POLICY_CREATE_USER, POLICY_AUTH, POLICY_ADMIN];
+
+// ポリシーの上限の設定値を取得する関数
+pub fn quota_setting<'a>(config: &'a Config, policy: &str) -> Option<&'a str> {
+    match policy {
+        POLICY_CREATE_USER => Some(&config.rate_limit_create_user),
+        POLICY_AUTH => Some(&config.rate_limit_auth),
+        POLICY_ADMIN => Some(&config.rate_limit_admin),
+        _ => None,
+    }
+}
+
+// リクエスト数を数える単位
+// 登録されていないトークンで数えると、トークンを変えるだけで制限を回避できるため、
+// 検証できないトークンのリクエストはIPアドレスで数える
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitKey {
+    // クライアントのIPアドレス（信頼するプロキシを経由した場合は転送元のアドレス）
+    Ip,
+    // 検証済みのAPIキー（トークンごと、検証できない場合はIPアドレス）
+    ApiKey,
+    // 認証済みユーザー（ユーザーの全てのトークンの合計、検証できない場合はIPアドレス）
+    Principal,
+}
+
+// ルートに適用するポリシー（router.rsで指定する）
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimitPolicy {
+    pub name: &'static str,
+    pub key: RateLimitKey,
+}
+
+impl RateLimitPolicy {
+    pub const fn new(name: &'static str, key: RateLimitKey) -> Self {
+        RateLimitPolicy { name, key }
+    }
+
+    // リクエスト数を数える単位の識別子（APIキーはトークンのハッシュ値にして保存する）
+    pub fn client_key(
+        &self,
+        credential: Option<Credential<'_>>,
+        client_ip: Option<IpAddr>,
+    ) -> String {
+        match (self.key, credential) {
+            (RateLimitKey::Principal, Some(credential)) => {
+                return format!("{}:principal:{}", self.name, credential.principal);
+            }
+            (RateLimitKey::ApiKey, Some(credential)) => {
+                return format!("{}:api_key:{}", self.name, credential.key_id);
+            }
+            _ => {}
+        }
+        match client_ip {
+            Some(ip) => format!("{}:ip:{}", self.name, ip),
+            None => format!("{}:ip:unknown", self.name),
+        }
+    }
+}
+
+// レート制限の設定
+#[derive(Clone, Debug)]
+pub struct RateLimitSetting {
+    pub enabled: bool,
+    // ポリシー名ごとの上限
+    pub quotas: HashMap<&'static str, Quota>,
+}
+
+impl RateLimitSetting {
+    pub fn from_config(config: &Config) -> Self {
+        // 起動時と再読み込み時に検証済みのため、不正な値のポリシーは制限しない
+        let quotas = RATE_LIMIT_POLICIES
+            .iter()
+            .filter_map(|name| {
+                let quota = Quota::parse(quota_setting(config, name)?).ok()?;
+                Some((*name, quota))
+            })
+            .collect();
+        RateLimitSetting {
+            enabled: config.rate_limit_enabled,
+            quotas,
+        }
+    }
+}
+
+// 設定のインスタンス（プロセス内で共有し、設定の再読み込み時に作り直す）
+static RATE_LIMIT_SETTING: ReloadableSetting<RateLimitSetting> =
+    ReloadableSetting::new(RateLimitSetting::from_config);
+
+// 判定結果をレスポンスヘッダーに追加する関数
+pub fn insert_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    let values = [
+        (HEADER_RATE_LIMIT_LIMIT, decision.limit.to_string()),
+        (HEADER_RATE_LIMIT_REMAINING, decision.remaining.to_string()),
+        (HEADER_RATE_LIMIT_RESET, decision.reset_secs.to_string()),
+        (
+            HEADER_RATE_LIMIT_POLICY,
+            format!("{};w={}", decision.limit, decision.window_secs),
+        ),
+    ];
+    for (name, value) in values {
+        headers.insert(name, HeaderValue::from_str(&value).unwrap());
+    }
+    if let Some(retry_after) = decision.retry_after_secs {
+        headers.insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+    }
+}
+
+// 上限を超えた場合のレスポンス（リクエストIDのヘッダーはrequest_middlewareで設定する）
+pub fn too_many_requests_response(decision: &RateLimitDecision) -> Response {
+    let msg = Json(json!({ "message": "Too Many Requests"}));
+    let mut res = (StatusCode::TOO_MANY_REQUESTS, msg).into_response();
+    insert_rate_limit_headers(res.headers_mut(), decision);
+    res
+}
+
+// レート制限用のミドルウェア（ポリシーごとにリクエスト数を数え、上限を超えた場合は429を返す）
+pub async fn rate_limit_middleware(
+    State(policy): State<RateLimitPolicy>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let setting = RATE_LIMIT_SETTING.get();
+    let Some(quota) = setting.quotas.get(policy.name).filter(|_| setting.enabled) else {
+        return next.run(req).await;
+    };
+
+    // トークンを検証し、共通コンテキストからクライアントのIPアドレスを取得
+    // （認証前に数えるため、認証エラーになるリクエストもIPアドレスごとに制限する）
+    let credential = match policy.key {
+        RateLimitKey::Ip => None,
+        _ => bearer_token(req.headers()).and_then(|token| auth_setting().verify(token)),
+    };
+    let client_ip = req
+        .extensions()
+        .get::<Context>()
+        .and_then(|ctx| ctx.client_ip);
+    let key = policy.client_key(credential, client_ip);
+
+    // 保存先のエラー時はリクエストを止めないよう制限せずに処理する
+    let state = match rate_limit_store().acquire(&key, quota).await {
+        Ok(state) => state,
+        Err(err) => {
+            tracing::error!("レート制限の判定エラー: {}", err);
+            return next.run(req).await;
+        }
+    };
+    let decision = RateLimitDecision::from_state(quota, &state);
+    if !decision.allowed {
+        tracing::warn!(
+            policy = policy.name,
+            retry_after = decision.retry_after_secs,
+            "リクエスト数が上限を超えました"
+        );
+        return too_many_requests_response(&decision);
+    }
+
+    let mut res = next.run(req).await;
+    insert_rate_limit_headers(res.headers_mut(), &decision);
+    res
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+// レート制限のミドルウェアのテスト
+mod rate_limit_middleware_test {
+    use crate::api::contexts::principal::Credential;
+    use crate::api::middleware::rate_limit_middleware::{
+        RateLimitKey, RateLimitPolicy, too_many_requests_response,
+    };
+    use crate::api::middleware::rate_limit_store::RateLimitDecision;
+    use axum::http::StatusCode;
+    use std::net::IpAddr;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_client_key() {
+        let client_ip: IpAddr = "192.0.2.1".parse().unwrap();
+        let credential = Credential {
+            principal: "user-1",
+            key_id: "key-1",
+        };
+
+        // IPアドレスで数える場合はトークンを使用しない
+        let policy = RateLimitPolicy::new("test", RateLimitKey::Ip);
+        assert_eq!(
+            policy.client_key(Some(credential), Some(client_ip)),
+            "test:ip:192.0.2.1"
+        );
+
+        // 検証済みのAPIキーはトークンの識別子で数える
+        let policy = RateLimitPolicy::new("test", RateLimitKey::ApiKey);
+        assert_eq!(
+            policy.client_key(Some(credential), Some(client_ip)),
+            "test:api_key:key-1"
+        );
+
+        // 認証済みユーザーはユーザーの識別子で数える
+        let policy = RateLimitPolicy::new("test", RateLimitKey::Principal);
+        assert_eq!(
+            policy.client_key(Some(credential), Some(client_ip)),
+            "test:principal:user-1"
+        );
+
+        // 検証できない場合はIPアドレスで数える
+        assert_eq!(
+            policy.client_key(None, Some(client_ip)),
+            "test:ip:192.0.2.1"
+        );
+        assert_eq!(policy.client_key(None, None), "test:ip:unknown");
+    }
+
+    #[test]
+    fn test_too_many_requests_response() {
+        let decision = RateLimitDecision {
+            allowed: false,
+            limit: 10,
+            window_secs: 60,
+            remaining: 0,
+            reset_secs: 60,
+            retry_after_secs: Some(6),
+        };
+        let res = too_many_requests_response(&decision);
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        let headers = res.headers();
+        assert_eq!(headers["Retry-After"], "6");
+        assert_eq!(headers["RateLimit-Limit"], "10");
+        assert_eq!(headers["RateLimit-Remaining"], "0");
+        assert_eq!(headers["RateLimit-Reset"], "60");
+        assert_eq!(headers["RateLimit-Policy"], "10;w=60");
+    }
+
+    #[tokio::test]
+    async fn it_returns_rate_limit_headers() {
+        let url = "http://localhost:8080/api/v1/users";
+        let client = reqwest::Client::new();
+        let res = client
+            .get(url)
+            .header("Authorization", format!("Bearer {}", "xxx"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        let headers = res.headers();
+        assert!(headers.contains_key("RateLimit-Limit"));
+        assert!(headers.contains_key("RateLimit-Remaining"));
+        assert!(headers.contains_key("RateLimit-Reset"));
+        assert!(!headers.contains_key("Retry-After"));
+    }
+
+    #[tokio::test]
+    async fn it_returns_too_many_requests() {
+        // 他のテストと数が混ざらないようにテスト用のIPアドレスから実行する（localhostは信頼するプロキシ）
+        let url = "http://localhost:8080/api/v1/admin/config";
+        let client_ip = format!("2001:db8::{:x}", Uuid::new_v4().as_u128() as u16);
+        let client = reqwest::Client::new();
+        let mut limit = None;
+        for _ in 0..1000 {
+            // 登録されていないトークンを毎回変えても同じIPアドレスとして数える
+            let res = client
+                .get(url)
+                .header("Authorization", format!("Bearer {}", Uuid::new_v4()))
+                .header("X-Forwarded-For", &client_ip)
+                .send()
+                .await
+                .unwrap();
+            if res.status() == 401 {
+                limit = res.headers()["RateLimit-Limit"]
+                    .to_str()
+                    .ok()
+                    .map(String::from);
+                continue;
+            }
+
+            // 上限を超えた場合は429と再試行までの秒数を返す
+            assert_eq!(res.status(), 429);
+            let headers = res.headers();
+            assert!(headers.contains_key("X-Request-Id"));
+            assert!(
+                headers["Retry-After"]
+                    .to_str()
+                    .unwrap()
+                    .parse::<u64>()
+                    .unwrap()
+                    >= 1
+            );
+            assert_eq!(headers["RateLimit-Remaining"], "0");
+            assert_eq!(headers["RateLimit-Limit"].to_str().ok(), limit.as_deref());
+            let body: serde_json::Value = res.json().await.unwrap();
+            assert_eq!(body["message"], "Too Many Requests");
+            return;
+        }
+        panic!("上限を超えても429が返されませんでした");
+    }
+}
@@ -0,0 +1,268 @@
+// axum
+use axum::http::StatusCode;
+
+// 標準ライブラリ
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+// configsモジュール
+use crate::api::configs::config::{self, RateLimitBackend, parse_duration};
+
+// 共通エラー用モジュール
+use crate::api::errors::error::CommonError;
+
+// DB接続用のモジュール
+use crate::api::databases::database::db_connection;
+
+// レート制限のリポジトリ
+use crate::api::repositories::rate_limits::rate_limits_repository::{
+    acquire_rate_limit, delete_expired_rate_limits, get_rate_limit,
+};
+
+// シャットダウン用のモジュール
+use crate::api::lifecycle::shutdown::ShutdownListener;
+
+// 期限切れの状態を削除する間隔
+const PURGE_INTERVAL: Duration = Duration::from_secs(60);
+
+// 期間内に許可するリクエスト数（例：10/1m は1分間に10回まで、最大10回まで連続して許可）
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quota {
+    pub limit: u32,
+    pub window: Duration,
+}
+
+impl Quota {
+    // 「回数/期間」の形式の設定値を変換する関数
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (limit, window) = value
+            .split_once('/')
+            .ok_or_else(|| format!("「回数/期間」の形式で指定してください: {}", value))?;
+        let limit = limit
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|limit| *limit > 0)
+            .ok_or_else(|| format!("回数は1以上を指定してください: {}", value))?;
+        let window = parse_duration(window.trim())
+            .ok()
+            .filter(|window| window.as_millis() >= limit as u128)
+            .ok_or_else(|| format!("期間は回数のミリ秒以上を指定してください: {}", value))?;
+        Ok(Quota { limit, window })
+    }
+
+    // 1回あたりの間隔（ミリ秒）
+    pub fn emission_interval_ms(&self) -> i64 {
+        (self.window.as_millis() / self.limit as u128) as i64
+    }
+
+    // 連続して許可する量（ミリ秒）
+    pub fn tolerance_ms(&self) -> i64 {
+        self.emission_interval_ms() * self.limit as i64
+    }
+}
+
+// GCRAの状態（理論上の到着時刻）と判定結果
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GcraState {
+    pub allowed: bool,
+    // 判定時の現在時刻（UNIX時間のミリ秒）
+    pub now_ms: i64,
+    // 判定後の理論上の到着時刻（UNIX時間のミリ秒）
+    pub tat_ms: i64,
+}
+
+// GCRAの判定を行う関数（許可する場合のみ理論上の到着時刻を進める）
+pub fn gcra(stored_tat_ms: Option<i64>, now_ms: i64, quota: &Quota) -> GcraState {
+    let tat_ms = stored_tat_ms.unwrap_or(now_ms).max(now_ms);
+    let new_tat_ms = tat_ms + quota.emission_interval_ms();
+    if new_tat_ms - now_ms <= quota.tolerance_ms() {
+        GcraState {
+            allowed: true,
+            now_ms,
+            tat_ms: new_tat_ms,
+        }
+    } else {
+        GcraState {
+            allowed: false,
+            now_ms,
+            tat_ms,
+        }
+    }
+}
+
+// レート制限の判定結果（レスポンスヘッダーの値）
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub window_secs: u64,
+    // 残りのリクエスト数
+    pub remaining: u32,
+    // 上限まで回復するまでの秒数
+    pub reset_secs: u64,
+    // 次に許可されるまでの秒数（許可しない場合のみ）
+    pub retry_after_secs: Option<u64>,
+}
+
+// ミリ秒を秒に切り上げる関数
+fn ceil_secs(ms: i64) -> u64 {
+    (ms.max(0) as u64).div_ceil(1000)
+}
+
+impl RateLimitDecision {
+    pub fn from_state(quota: &Quota, state: &GcraState) -> Self {
+        let interval_ms = quota.emission_interval_ms().max(1);
+        let remaining = (quota.tolerance_ms() - (state.tat_ms - state.now_ms)) / interval_ms;
+        let retry_after_ms =
+            state.tat_ms + quota.emission_interval_ms() - quota.tolerance_ms() - state.now_ms;
+        RateLimitDecision {
+            allowed: state.allowed,
+            limit: quota.limit,
+            window_secs: quota.window.as_secs(),
+            remaining: remaining.clamp(0, quota.limit as i64) as u32,
+            reset_secs: ceil_secs(state.tat_ms - state.now_ms),
+            retry_after_secs: (!state.allowed).then(|| ceil_secs(retry_after_ms).max(1)),
+        }
+    }
+}
+
+// レート制限の状態の保存先用のトレイト（モック化もできるように定義）
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait RateLimitStore {
+    // 判定と状態の更新を不可分に行う
+    async fn acquire(&self, key: &str, quota: &Quota) -> Result<GcraState, CommonError>;
+
+    // 期限切れの状態を削除し、削除した件数を返す
+    async fn purge_expired(&self) -> Result<u64, CommonError>;
+}
+
+// 現在時刻（UNIX時間のミリ秒）
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+// メモリ上に状態を保持する構造体（レプリカごとに独立して制限する）
+#[derive(Default)]
+pub struct MemoryRateLimitStore {
+    tats: Mutex<HashMap<String, i64>>,
+}
+
+impl MemoryRateLimitStore {
+    // 初期化用メソッド
+    pub fn new() -> Self {
+        MemoryRateLimitStore::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimitStore for MemoryRateLimitStore {
+    async fn acquire(&self, key: &str, quota: &Quota) -> Result<GcraState, CommonError> {
+        let mut tats = self.tats.lock().unwrap();
+        let state = gcra(tats.get(key).copied(), now_ms(), quota);
+        if state.allowed {
+            tats.insert(key.to_string(), state.tat_ms);
+        }
+        Ok(state)
+    }
+
+    async fn purge_expired(&self) -> Result<u64, CommonError> {
+        let now = now_ms();
+        let mut tats = self.tats.lock().unwrap();
+        let before = tats.len();
+        tats.retain(|_, tat| *tat >= now);
+        Ok((before - tats.len()) as u64)
+    }
+}
+
+// Postgresに状態を保持する構造体（全てのレプリカで共通の制限にする）
+pub struct PostgresRateLimitStore;
+
+impl PostgresRateLimitStore {
+    // 初期化用メソッド
+    pub fn new() -> Self {
+        PostgresRateLimitStore
+    }
+}
+
+// DBエラーを共通エラーに変換する関数
+fn db_error(method: &str, err: impl std::fmt::Display) -> CommonError {
+    CommonError::CustomError {
+        status_code: StatusCode::INTERNAL_SERVER_ERROR,
+        message: format!("[PostgresRateLimitStore.{}] DBエラー: {}", method, err),
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimitStore for PostgresRateLimitStore {
+    async fn acquire(&self, key: &str, quota: &Quota) -> Result<GcraState, CommonError> {
+        let db = db_connection()
+            .await
+            .map_err(|err| db_error("acquire", err))?;
+        let acquired =
+            acquire_rate_limit(&db, key, quota.emission_interval_ms(), quota.tolerance_ms())
+                .await
+                .map_err(|err| db_error("acquire", err))?;
+        if let Some((tat_ms, now_ms)) = acquired {
+            return Ok(GcraState {
+                allowed: true,
+                now_ms,
+                tat_ms,
+            });
+        }
+
+        // 許可しない場合は次に許可されるまでの時間を求めるため現在の状態を取得
+        let current = get_rate_limit(&db, key)
+            .await
+            .map_err(|err| db_error("acquire", err))?;
+        let (tat_ms, now_ms) = current.unwrap_or((now_ms(), now_ms()));
+        Ok(GcraState {
+            allowed: false,
+            now_ms,
+            tat_ms,
+        })
+    }
+
+    async fn purge_expired(&self) -> Result<u64, CommonError> {
+        let db = db_connection()
+            .await
+            .map_err(|err| db_error("purge_expired", err))?;
+        delete_expired_rate_limits(&db)
+            .await
+            .map_err(|err| db_error("purge_expired", err))
+    }
+}
+
+// 状態の保存先のインスタンス（設定の保存先から作成し、プロセス内で共有）
+static RATE_LIMIT_STORE: OnceLock<Arc<dyn RateLimitStore + Send + Sync>> = OnceLock::new();
+
+// 状態の保存先を取得する関数
+pub fn rate_limit_store() -> Arc<dyn RateLimitStore + Send + Sync> {
+    RATE_LIMIT_STORE
+        .get_or_init(|| match config::get_config().rate_limit_backend {
+            RateLimitBackend::Postgres => Arc::new(PostgresRateLimitStore::new()),
+            RateLimitBackend::Memory => Arc::new(MemoryRateLimitStore::new()),
+        })
+        .clone()
+}
+
+// 期限切れの状態を定期的に削除する処理
+pub async fn run_rate_limit_purger(mut shutdown: ShutdownListener) {
+    let mut ticker = tokio::time::interval(PURGE_INTERVAL);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = ticker.tick() => {
+                if let Err(err) = rate_limit_store().purge_expired().await {
+                    tracing::error!("[RateLimitPurger] 期限切れの状態の削除エラー: {}", err);
+                }
+            }
+        }
+    }
+
+    tracing::info!("[RateLimitPurger] stop !!");
+}
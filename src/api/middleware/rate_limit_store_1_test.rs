@@ -0,0 +1,140 @@
+#[cfg(test)]
+// レート制限の判定と状態の保存先のテスト
+mod rate_limit_store_test {
+    use crate::api::databases::database::db_connection;
+    use crate::api::middleware::rate_limit_store::{
+        GcraState, MemoryRateLimitStore, PostgresRateLimitStore, Quota, RateLimitDecision,
+        RateLimitStore, gcra,
+    };
+    use sea_orm::{ConnectionTrait, DbBackend, Statement};
+    use std::time::Duration;
+
+    fn quota(limit: u32, secs: u64) -> Quota {
+        Quota {
+            limit,
+            window: Duration::from_secs(secs),
+        }
+    }
+
+    #[test]
+    fn test_parse_quota() {
+        assert_eq!(Quota::parse("10/1m").unwrap(), quota(10, 60));
+        assert_eq!(Quota::parse(" 600 / 1h ").unwrap(), quota(600, 3600));
+        for value in ["10", "0/1m", "-1/1m", "10/abc", "10/0s", "2000/1s"] {
+            assert!(Quota::parse(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_gcra() {
+        // 2秒間に2回まで（1秒ごとに1回分回復する）
+        let quota = quota(2, 2);
+        let first = gcra(None, 0, &quota);
+        assert!(first.allowed);
+        let second = gcra(Some(first.tat_ms), 0, &quota);
+        assert!(second.allowed);
+        let third = gcra(Some(second.tat_ms), 0, &quota);
+        assert_eq!(
+            third,
+            GcraState {
+                allowed: false,
+                now_ms: 0,
+                tat_ms: 2000,
+            }
+        );
+
+        // 1秒後に1回分回復する
+        assert!(!gcra(Some(second.tat_ms), 999, &quota).allowed);
+        assert!(gcra(Some(second.tat_ms), 1000, &quota).allowed);
+
+        // 時間が経過した状態は現在時刻から数え直す
+        assert_eq!(gcra(Some(0), 10_000, &quota).tat_ms, 11_000);
+    }
+
+    #[test]
+    fn test_decision() {
+        let quota = quota(2, 2);
+        let first = gcra(None, 0, &quota);
+        assert_eq!(
+            RateLimitDecision::from_state(&quota, &first),
+            RateLimitDecision {
+                allowed: true,
+                limit: 2,
+                window_secs: 2,
+                remaining: 1,
+                reset_secs: 1,
+                retry_after_secs: None,
+            }
+        );
+
+        let second = gcra(Some(first.tat_ms), 0, &quota);
+        let third = gcra(Some(second.tat_ms), 500, &quota);
+        assert_eq!(
+            RateLimitDecision::from_state(&quota, &third),
+            RateLimitDecision {
+                allowed: false,
+                limit: 2,
+                window_secs: 2,
+                remaining: 0,
+                reset_secs: 2,
+                retry_after_secs: Some(1),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_store() {
+        let store = MemoryRateLimitStore::new();
+        let quota = quota(2, 3600);
+        assert!(store.acquire("a", &quota).await.unwrap().allowed);
+        assert!(store.acquire("a", &quota).await.unwrap().allowed);
+        assert!(!store.acquire("a", &quota).await.unwrap().allowed);
+
+        // キーごとに数える
+        assert!(store.acquire("b", &quota).await.unwrap().allowed);
+
+        // 期限切れでない状態は削除しない
+        assert_eq!(store.purge_expired().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_postgres_store() {
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => panic!("DB接続エラー: {}", err),
+        };
+        let key = format!("test:{}", uuid::Uuid::new_v4());
+        let store = PostgresRateLimitStore::new();
+        let quota = quota(2, 3600);
+
+        let first = store.acquire(&key, &quota).await.unwrap();
+        assert!(first.allowed);
+        assert_eq!(first.tat_ms - first.now_ms, 1_800_000);
+        assert!(store.acquire(&key, &quota).await.unwrap().allowed);
+
+        // 上限を超えた場合は状態を更新しない
+        let third = store.acquire(&key, &quota).await.unwrap();
+        assert!(!third.allowed);
+        let decision = RateLimitDecision::from_state(&quota, &third);
+        assert_eq!(decision.remaining, 0);
+        assert!(decision.retry_after_secs.unwrap() > 1700);
+
+        // 期限切れの状態を削除する
+        db.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "UPDATE rate_limits SET tat = 0 WHERE key = $1",
+            [key.clone().into()],
+        ))
+        .await
+        .unwrap();
+        assert!(store.purge_expired().await.unwrap() >= 1);
+        assert!(store.acquire(&key, &quota).await.unwrap().allowed);
+        db.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "DELETE FROM rate_limits WHERE key = $1",
+            [key.into()],
+        ))
+        .await
+        .unwrap();
+    }
+}
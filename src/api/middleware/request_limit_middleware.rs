@@ -0,0 +1,183 @@
+// axum
+use axum::{
+    extract::{Json, MatchedPath, Request},
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+// json変換用マクロ
+use serde_json::json;
+
+// 標準ライブラリ
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+// tokio
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+// configsモジュール
+use crate::api::configs::config::{self, Config, parse_duration};
+use crate::api::configs::config_reload::ReloadableSetting;
+
+// 負荷が高い場合に再試行を促す秒数
+const LOAD_SHED_RETRY_AFTER_SECS: u64 = 1;
+
+// 共通のエラー形式のレスポンス（リクエストIDのヘッダーはrequest_middlewareで設定する）
+fn error_response(status_code: StatusCode) -> Response {
+    let message = status_code.canonical_reason().unwrap_or_default();
+    let msg = Json(json!({ "message": message }));
+    (status_code, msg).into_response()
+}
+
+// ルートごとの処理時間の上限の設定値を変換する関数（例：/api/v1/webhook/{uid}/test=60s のカンマ区切り）
+pub fn parse_timeout_routes(value: &str) -> Result<HashMap<String, Duration>, String> {
+    value
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|item| {
+            let (route, timeout) = item
+                .rsplit_once('=')
+                .filter(|(route, _)| route.trim().starts_with('/'))
+                .ok_or_else(|| format!("「ルート=時間」の形式で指定してください: {}", item))?;
+            let timeout = parse_duration(timeout.trim())
+                .map_err(|err| format!("不正な時間です（{}）: {}", err, item))?;
+            Ok((route.trim().to_string(), timeout))
+        })
+        .collect()
+}
+
+// リクエストの処理時間の上限の設定
+#[derive(Clone, Debug)]
+pub struct RequestTimeoutSetting {
+    pub default: Duration,
+    // ルートのテンプレート（例：/api/v1/user/{uid}）ごとの上書き
+    pub routes: HashMap<String, Duration>,
+}
+
+impl RequestTimeoutSetting {
+    pub fn from_config(config: &Config) -> Self {
+        RequestTimeoutSetting {
+            default: config.request_timeout,
+            // 起動時と再読み込み時に検証済みのため、不正な値は無視する
+            routes: parse_timeout_routes(&config.request_timeout_routes).unwrap_or_default(),
+        }
+    }
+
+    // ルートの処理時間の上限（0の場合は無効のためNone）
+    pub fn timeout_for(&self, route: &str) -> Option<Duration> {
+        let timeout = self.routes.get(route).copied().unwrap_or(self.default);
+        (!timeout.is_zero()).then_some(timeout)
+    }
+}
+
+// 設定のインスタンス（プロセス内で共有し、設定の再読み込み時に作り直す）
+static REQUEST_TIMEOUT_SETTING: ReloadableSetting<RequestTimeoutSetting> =
+    ReloadableSetting::new(RequestTimeoutSetting::from_config);
+
+// 処理時間の上限を超えた場合は処理を中断して504を返す関数
+pub async fn with_timeout(timeout: Duration, future: impl Future<Output = Response>) -> Response {
+    match tokio::time::timeout(timeout, future).await {
+        Ok(res) => res,
+        Err(_) => {
+            tracing::warn!(
+                timeout_ms = timeout.as_millis() as u64,
+                "リクエストの処理時間が上限を超えたため中断しました"
+            );
+            error_response(StatusCode::GATEWAY_TIMEOUT)
+        }
+    }
+}
+
+// リクエストの処理時間を制限するミドルウェア（SSE・WebSocketはレスポンスヘッダーを返すまでが対象）
+pub async fn timeout_middleware(req: Request, next: Next) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+    let Some(timeout) = REQUEST_TIMEOUT_SETTING.get().timeout_for(&route) else {
+        return next.run(req).await;
+    };
+
+    with_timeout(timeout, next.run(req)).await
+}
+
+// リクエストボディのサイズを制限するミドルウェア
+// Content-Lengthで上限を超える場合はボディを読まずに413を返し、それ以外はボディの読み込み時（DefaultBodyLimit）に制限する
+pub async fn body_limit_middleware(req: Request, next: Next) -> Response {
+    let limit = config::get_config().request_body_limit;
+    let content_length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    if content_length.is_some_and(|length| length > limit) {
+        return error_response(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    // ボディの読み込み時に上限を超えた場合も共通のエラー形式にする
+    let res = next.run(req).await;
+    let is_json = res
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if res.status() == StatusCode::PAYLOAD_TOO_LARGE && !is_json {
+        return error_response(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+    res
+}
+
+// 同時に処理するリクエスト数を制限する構造体（上限を超えた場合は待たずに拒否する）
+pub struct LoadShedder {
+    semaphore: Arc<Semaphore>,
+    max: usize,
+}
+
+impl LoadShedder {
+    pub fn new(max: usize) -> Self {
+        LoadShedder {
+            semaphore: Arc::new(Semaphore::new(max)),
+            max,
+        }
+    }
+
+    // 処理中の数を1つ増やす（上限の場合はNone、戻り値の破棄時に減らす）
+    pub fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.semaphore.clone().try_acquire_owned().ok()
+    }
+
+    // 処理中のリクエスト数
+    pub fn in_flight(&self) -> usize {
+        self.max - self.semaphore.available_permits()
+    }
+}
+
+// 同時に処理するリクエスト数の制限のインスタンス（プロセス内で共有）
+static LOAD_SHEDDER: OnceLock<LoadShedder> = OnceLock::new();
+
+pub fn load_shedder() -> &'static LoadShedder {
+    LOAD_SHEDDER.get_or_init(|| LoadShedder::new(config::get_config().max_in_flight_requests))
+}
+
+// 同時に処理するリクエスト数が上限を超えた場合に503を返すミドルウェア
+pub async fn load_shed_middleware(req: Request, next: Next) -> Response {
+    let shedder = load_shedder();
+    let Some(_permit) = shedder.try_acquire() else {
+        tracing::warn!(
+            in_flight = shedder.in_flight(),
+            "同時に処理するリクエスト数が上限を超えたため拒否しました"
+        );
+        let mut res = error_response(StatusCode::SERVICE_UNAVAILABLE);
+        res.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(LOAD_SHED_RETRY_AFTER_SECS),
+        );
+        return res;
+    };
+
+    next.run(req).await
+}
@@ -0,0 +1,121 @@
+#[cfg(test)]
+// リクエストの制限のテスト
+mod request_limit_test {
+    use crate::api::configs::config;
+    use crate::api::databases::database::db_connection;
+    use crate::api::middleware::request_limit_middleware::{
+        LoadShedder, RequestTimeoutSetting, parse_timeout_routes, with_timeout,
+    };
+    use axum::http::StatusCode;
+    use axum::response::{IntoResponse, Response};
+    use sea_orm::{ConnectionTrait, DbBackend, Statement};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_timeout_routes() {
+        let routes =
+            parse_timeout_routes("/api/v1/webhook/{uid}/test=60s, /api/v1/users=500ms").unwrap();
+        assert_eq!(routes.len(), 2);
+        assert_eq!(
+            routes["/api/v1/webhook/{uid}/test"],
+            Duration::from_secs(60)
+        );
+        assert_eq!(routes["/api/v1/users"], Duration::from_millis(500));
+        assert!(parse_timeout_routes("").unwrap().is_empty());
+
+        for value in ["/api/v1/users", "api/v1/users=1s", "/api/v1/users=1x"] {
+            assert!(parse_timeout_routes(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_timeout_for_route() {
+        let mut config = config::get_config().clone();
+        config.request_timeout = Duration::from_secs(30);
+        config.request_timeout_routes = "/api/v1/slow=2m,/api/v1/stream=0s".to_string();
+        let setting = RequestTimeoutSetting::from_config(&config);
+
+        // ルートの設定が無い場合は全体の設定を使用し、0の場合は制限しない
+        assert_eq!(
+            setting.timeout_for("/api/v1/users"),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            setting.timeout_for("/api/v1/slow"),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(setting.timeout_for("/api/v1/stream"), None);
+    }
+
+    #[tokio::test]
+    async fn test_with_timeout() {
+        let fast = async { StatusCode::OK.into_response() };
+        let res = with_timeout(Duration::from_secs(1), fast).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // 上限を超えた場合は処理を中断して504を返す
+        let slow = async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            StatusCode::OK.into_response()
+        };
+        let res: Response = with_timeout(Duration::from_millis(50), slow).await;
+        assert_eq!(res.status(), StatusCode::GATEWAY_TIMEOUT);
+        // リクエストIDはrequest_middlewareで設定するため含めない
+        assert!(!res.headers().contains_key("X-Request-Id"));
+    }
+
+    #[test]
+    fn test_load_shedder() {
+        let shedder = LoadShedder::new(2);
+        let first = shedder.try_acquire().unwrap();
+        let _second = shedder.try_acquire().unwrap();
+        assert_eq!(shedder.in_flight(), 2);
+
+        // 上限の場合は待たずに拒否し、処理が終わると受け付ける
+        assert!(shedder.try_acquire().is_none());
+        drop(first);
+        assert_eq!(shedder.in_flight(), 1);
+        assert!(shedder.try_acquire().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_statement_timeout() {
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => panic!("DB接続エラー: {}", err),
+        };
+        // 接続時に渡した設定がセッションに反映されている
+        let row = db
+            .query_one(Statement::from_string(
+                DbBackend::Postgres,
+                "SELECT (EXTRACT(EPOCH FROM current_setting('statement_timeout')::interval) * 1000)::bigint AS ms",
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        let timeout_ms: i64 = row.try_get("", "ms").unwrap();
+        assert_eq!(
+            timeout_ms as u128,
+            config::get_config().database_statement_timeout.as_millis()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_too_large_body() {
+        // Content-Lengthで上限を超える場合はボディを読まずに413を返す
+        let url = "http://localhost:8080/api/v1/sample/post";
+        let body = vec![b'a'; config::get_config().request_body_limit + 1];
+        let client = reqwest::Client::new();
+        let res = client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 413);
+        assert!(res.headers().contains_key("X-Request-Id"));
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["message"], "Payload Too Large");
+    }
+}
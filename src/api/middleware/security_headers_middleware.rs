@@ -0,0 +1,202 @@
+// axum
+use axum::{
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+
+// 標準ライブラリ
+use std::sync::OnceLock;
+
+// configsモジュール
+use crate::api::configs::config::{self, Config};
+
+// Referrer-Policyの設定値の候補
+pub const REFERRER_POLICIES: [&str; 8] = [
+    "no-referrer",
+    "no-referrer-when-downgrade",
+    "origin",
+    "origin-when-cross-origin",
+    "same-origin",
+    "strict-origin",
+    "strict-origin-when-cross-origin",
+    "unsafe-url",
+];
+
+// Cross-Origin-Resource-Policyの設定値の候補
+pub const CROSS_ORIGIN_RESOURCE_POLICIES: [&str; 3] = ["same-site", "same-origin", "cross-origin"];
+
+// Content-Security-Policyの種類（router.rsでルートのグループごとに指定する）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CspProfile {
+    // JSONを返すAPI（リソースの読み込みを全て禁止する）
+    Api,
+    // Swagger UI（同一オリジンのスクリプト・スタイル・画像の読み込みを許可する）
+    SwaggerUi,
+}
+
+// ヘッダー値に変換する関数（空の場合は設定しないためNone）
+fn header_value(name: &str, value: &str) -> Result<Option<HeaderValue>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    HeaderValue::from_str(value)
+        .map(Some)
+        .map_err(|_| format!("{}: ヘッダーに設定できない値です: {}", name, value))
+}
+
+// 候補のいずれかのヘッダー値に変換する関数（空の場合は設定しないためNone）
+fn header_value_one_of(
+    name: &str,
+    value: &str,
+    candidates: &[&str],
+) -> Result<Option<HeaderValue>, String> {
+    let value = value.trim();
+    if !value.is_empty() && !candidates.contains(&value) {
+        return Err(format!(
+            "{}: {} のいずれかを指定してください: {}",
+            name,
+            candidates.join(" | "),
+            value
+        ));
+    }
+    header_value(name, value)
+}
+
+// 削除するレスポンスヘッダー名の設定値を変換する関数（カンマ区切り）
+pub fn parse_remove_headers(value: &str) -> Result<Vec<HeaderName>, String> {
+    value
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|name| {
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("不正なヘッダー名です: {}", name))
+        })
+        .collect()
+}
+
+// セキュリティ関連のレスポンスヘッダーの設定
+#[derive(Clone, Debug, Default)]
+pub struct SecurityHeaders {
+    // 全てのレスポンスに設定するヘッダー
+    pub headers: Vec<(HeaderName, HeaderValue)>,
+    // Content-Security-Policy（種類ごと）
+    pub api_csp: Option<HeaderValue>,
+    pub swagger_ui_csp: Option<HeaderValue>,
+    // 削除するヘッダー（サーバーの識別情報など）
+    pub remove: Vec<HeaderName>,
+}
+
+impl SecurityHeaders {
+    // 設定値から作成する関数（全ての項目のエラーを返す）
+    pub fn from_config(config: &Config) -> Result<Self, Vec<String>> {
+        let mut errors = Vec::new();
+        let mut ok = |result: Result<Option<HeaderValue>, String>| {
+            result.unwrap_or_else(|err| {
+                errors.push(err);
+                None
+            })
+        };
+
+        let headers = [
+            (
+                header::STRICT_TRANSPORT_SECURITY,
+                ok(header_value("SECURITY_HSTS", &config.security_hsts)),
+            ),
+            (
+                header::X_CONTENT_TYPE_OPTIONS,
+                ok(header_value(
+                    "SECURITY_CONTENT_TYPE_OPTIONS",
+                    &config.security_content_type_options,
+                )),
+            ),
+            (
+                header::REFERRER_POLICY,
+                ok(header_value_one_of(
+                    "SECURITY_REFERRER_POLICY",
+                    &config.security_referrer_policy,
+                    &REFERRER_POLICIES,
+                )),
+            ),
+            (
+                HeaderName::from_static("cross-origin-resource-policy"),
+                ok(header_value_one_of(
+                    "SECURITY_CROSS_ORIGIN_RESOURCE_POLICY",
+                    &config.security_cross_origin_resource_policy,
+                    &CROSS_ORIGIN_RESOURCE_POLICIES,
+                )),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+        .collect();
+        let api_csp = ok(header_value(
+            "SECURITY_CONTENT_SECURITY_POLICY",
+            &config.security_content_security_policy,
+        ));
+        let swagger_ui_csp = ok(header_value(
+            "SECURITY_SWAGGER_UI_CONTENT_SECURITY_POLICY",
+            &config.security_swagger_ui_content_security_policy,
+        ));
+        let remove = parse_remove_headers(&config.security_remove_headers).unwrap_or_else(|err| {
+            errors.push(format!("SECURITY_REMOVE_HEADERS: {}", err));
+            Vec::new()
+        });
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(SecurityHeaders {
+            headers,
+            api_csp,
+            swagger_ui_csp,
+            remove,
+        })
+    }
+
+    // 種類ごとのContent-Security-Policy
+    pub fn csp(&self, profile: CspProfile) -> Option<&HeaderValue> {
+        match profile {
+            CspProfile::Api => self.api_csp.as_ref(),
+            CspProfile::SwaggerUi => self.swagger_ui_csp.as_ref(),
+        }
+    }
+
+    // レスポンスヘッダーを設定する関数
+    pub fn apply(&self, profile: CspProfile, res: &mut Response) {
+        let headers = res.headers_mut();
+        for name in &self.remove {
+            headers.remove(name);
+        }
+        for (name, value) in &self.headers {
+            headers.insert(name.clone(), value.clone());
+        }
+        if let Some(csp) = self.csp(profile) {
+            headers.insert(header::CONTENT_SECURITY_POLICY, csp.clone());
+        }
+    }
+}
+
+// 設定のインスタンス（起動時の設定から作成し、プロセス内で共有）
+static SECURITY_HEADERS: OnceLock<SecurityHeaders> = OnceLock::new();
+
+pub fn security_headers() -> &'static SecurityHeaders {
+    SECURITY_HEADERS.get_or_init(|| {
+        // 起動時に検証済みのため、不正な値の場合は設定しない
+        SecurityHeaders::from_config(config::get_config()).unwrap_or_default()
+    })
+}
+
+// セキュリティ関連のレスポンスヘッダーを設定するミドルウェア
+pub async fn security_headers_middleware(
+    State(profile): State<CspProfile>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let mut res = next.run(req).await;
+    security_headers().apply(profile, &mut res);
+    res
+}
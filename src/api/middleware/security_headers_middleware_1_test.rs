@@ -0,0 +1,85 @@
+#[cfg(test)]
+// セキュリティ関連のレスポンスヘッダーのテスト
+mod security_headers_test {
+    use crate::api::configs::config;
+    use crate::api::middleware::security_headers_middleware::{CspProfile, SecurityHeaders};
+    use axum::body::Body;
+    use axum::response::Response;
+
+    #[test]
+    fn test_apply_headers() {
+        let mut config = config::get_config().clone();
+        config.security_hsts = "max-age=60".to_string();
+        config.security_cross_origin_resource_policy = "".to_string();
+        let security_headers = SecurityHeaders::from_config(&config).unwrap();
+
+        let mut res = Response::builder()
+            .header("Server", "axum")
+            .header("X-Powered-By", "rust")
+            .body(Body::empty())
+            .unwrap();
+        security_headers.apply(CspProfile::Api, &mut res);
+        let headers = res.headers();
+        assert_eq!(headers["Strict-Transport-Security"], "max-age=60");
+        assert_eq!(headers["X-Content-Type-Options"], "nosniff");
+        assert_eq!(
+            headers["Content-Security-Policy"],
+            config.security_content_security_policy
+        );
+        // 空の場合は設定しない
+        assert!(!headers.contains_key("Cross-Origin-Resource-Policy"));
+        // サーバーの識別情報は削除する
+        assert!(!headers.contains_key("Server"));
+        assert!(!headers.contains_key("X-Powered-By"));
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        let mut config = config::get_config().clone();
+        config.security_referrer_policy = "none".to_string();
+        config.security_cross_origin_resource_policy = "any".to_string();
+        config.security_content_security_policy = "default-src\n'none'".to_string();
+        config.security_remove_headers = "server,x powered by".to_string();
+        let errors = SecurityHeaders::from_config(&config).unwrap_err();
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].starts_with("SECURITY_REFERRER_POLICY:"));
+        assert!(errors[1].starts_with("SECURITY_CROSS_ORIGIN_RESOURCE_POLICY:"));
+        assert!(errors[2].starts_with("SECURITY_CONTENT_SECURITY_POLICY:"));
+        assert!(errors[3].starts_with("SECURITY_REMOVE_HEADERS:"));
+    }
+
+    #[tokio::test]
+    async fn it_sets_security_headers() {
+        // 正常なレスポンスとエラーのレスポンス（存在しないパス）
+        let client = reqwest::Client::new();
+        for (url, status) in [
+            ("http://localhost:8080/api/v1/sample/get", 200),
+            ("http://localhost:8080/api/v1/not-found", 404),
+        ] {
+            let res = client.get(url).send().await.unwrap();
+            assert_eq!(res.status(), status);
+            let headers = res.headers();
+            assert_eq!(headers["X-Content-Type-Options"], "nosniff");
+            assert_eq!(headers["Referrer-Policy"], "no-referrer");
+            assert_eq!(headers["Cross-Origin-Resource-Policy"], "same-origin");
+            assert_eq!(
+                headers["Content-Security-Policy"],
+                "default-src 'none'; frame-ancestors 'none'"
+            );
+            assert!(headers.contains_key("Strict-Transport-Security"));
+            assert!(!headers.contains_key("Server"));
+        }
+    }
+
+    #[tokio::test]
+    async fn it_relaxes_csp_for_swagger_ui() {
+        let url = "http://localhost:8080/swagger-ui/";
+        let client = reqwest::Client::new();
+        let res = client.get(url).send().await.unwrap();
+        assert_eq!(res.status(), 200);
+        let csp = res.headers()["Content-Security-Policy"].to_str().unwrap();
+        assert!(csp.contains("script-src 'self'"));
+        assert!(csp.contains("style-src 'self' 'unsafe-inline'"));
+        assert_eq!(res.headers()["X-Content-Type-Options"], "nosniff");
+    }
+}
@@ -3,10 +3,15 @@ pub mod contexts;
 pub mod databases;
 pub mod entities;
 pub mod errors;
+pub mod events;
 pub mod handlers;
+pub mod health;
+pub mod lifecycle;
+pub mod listeners;
 pub mod loggers;
 pub mod middleware;
 pub mod repositories;
 pub mod router;
 pub mod services;
+pub mod telemetry;
 pub mod usecases;
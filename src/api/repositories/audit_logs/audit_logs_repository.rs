@@ -0,0 +1,200 @@
+// SeaORM
+use sea_orm::{
+    ColumnTrait, DatabaseTransaction, DbErr, PaginatorTrait, QueryFilter, QueryOrder, Set,
+    entity::EntityTrait,
+};
+
+// axum
+use axum::http::StatusCode;
+
+// json変換用
+use serde_json::{Map, Value};
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// 共通エラー用モジュール
+use crate::api::errors::error::CommonError;
+
+// ロガー用のモジュール
+use crate::api::loggers::logger::error;
+
+// DB接続用のモジュール
+use crate::api::databases::database::db_connection;
+
+// エンティティのモジュール
+use crate::api::entities::prelude::{
+    AuditLogs, AuditLogsActiveModel, AuditLogsColumn, AuditLogsModel, UsersModel,
+};
+
+// 監査ログのアクション
+pub const AUDIT_ACTION_CREATE: &str = "create";
+pub const AUDIT_ACTION_UPDATE: &str = "update";
+pub const AUDIT_ACTION_DELETE: &str = "delete";
+
+// 監査ログリポジトリーの構造体
+pub struct AuditLogsRepository;
+
+impl AuditLogsRepository {
+    // 初期化用メソッド
+    pub fn new() -> Self {
+        AuditLogsRepository
+    }
+}
+
+// 監査ログリポジトリー用のトレイト（モック化もできるように定義）
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait AuditLogsRepositoryTrait {
+    async fn get_audit_logs_from_uid(
+        &self,
+        ctx: &Context,
+        uid: String,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<AuditLogsModel>, u64), CommonError>;
+}
+
+#[async_trait::async_trait]
+impl AuditLogsRepositoryTrait for AuditLogsRepository {
+    // Uidから対象ユーザーの監査ログを新しい順に取得（ページネーション有り）
+    #[tracing::instrument(name = "AuditLogsRepository.get_audit_logs_from_uid", skip_all, fields(uid = %uid))]
+    async fn get_audit_logs_from_uid(
+        &self,
+        ctx: &Context,
+        uid: String,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<AuditLogsModel>, u64), CommonError> {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                let msg = format!(
+                    "[AuditLogsRepository.get_audit_logs_from_uid] DB接続エラー: {}",
+                    err
+                );
+                error(ctx, &msg);
+                return Err(CommonError::CustomError {
+                    status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                    message: msg,
+                });
+            }
+        };
+
+        let paginator = AuditLogs::find()
+            .filter(AuditLogsColumn::UserUid.eq(uid))
+            .order_by_desc(AuditLogsColumn::Id)
+            .paginate(&db, per_page);
+
+        // 総件数の取得
+        let total = match paginator.num_items().await {
+            Ok(total) => total,
+            Err(err) => {
+                let msg = format!(
+                    "[AuditLogsRepository.get_audit_logs_from_uid] 監査ログ件数取得エラー: {}",
+                    err
+                );
+                error(ctx, &msg);
+                return Err(CommonError::CustomError {
+                    status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                    message: msg,
+                });
+            }
+        };
+
+        // 対象ページの取得（ページ番号は1始まり）
+        let audit_logs = match paginator.fetch_page(page - 1).await {
+            Ok(audit_logs) => audit_logs,
+            Err(err) => {
+                let msg = format!(
+                    "[AuditLogsRepository.get_audit_logs_from_uid] 監査ログ取得エラー: {}",
+                    err
+                );
+                error(ctx, &msg);
+                return Err(CommonError::CustomError {
+                    status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                    message: msg,
+                });
+            }
+        };
+
+        Ok((audit_logs, total))
+    }
+}
+
+// 監査ログの登録（呼び出し元のトランザクション内で実行する）
+pub async fn create_audit_log(
+    tx: &DatabaseTransaction,
+    ctx: &Context,
+    action: &str,
+    before: Option<&UsersModel>,
+    after: Option<&UsersModel>,
+) -> Result<(), DbErr> {
+    // 対象ユーザーのUid
+    let user_uid = match (before, after) {
+        (Some(user), _) | (None, Some(user)) => user.uid.clone(),
+        (None, None) => return Err(DbErr::Custom("監査対象のユーザーがありません".to_string())),
+    };
+
+    // 操作者（未認証の場合は「anonymous」）
+    let actor = ctx
+        .principal
+        .clone()
+        .unwrap_or_else(|| "anonymous".to_string());
+
+    // リクエストID
+    let request_id = match ctx.header.get("X-Request-Id") {
+        Some(value) => value.to_str().unwrap_or("-").to_string(),
+        None => "-".to_string(),
+    };
+
+    // クライアントのIPアドレス（信頼するプロキシを経由した場合は転送元のアドレス）
+    let client_ip = ctx.client_ip.map(|ip| ip.to_string());
+
+    // 変更前後の差分
+    let (before, after) = diff_users(before, after);
+
+    AuditLogs::insert(AuditLogsActiveModel {
+        user_uid: Set(user_uid),
+        actor: Set(actor),
+        request_id: Set(request_id),
+        action: Set(action.to_string()),
+        before: Set(before),
+        after: Set(after),
+        client_ip: Set(client_ip),
+        ..Default::default()
+    })
+    .exec(tx)
+    .await?;
+
+    Ok(())
+}
+
+// 変更前後のユーザー情報から、値が変わった項目のみのJSONを作成する関数
+fn diff_users(
+    before: Option<&UsersModel>,
+    after: Option<&UsersModel>,
+) -> (Option<Value>, Option<Value>) {
+    let before = before.map(|user| serde_json::to_value(user).unwrap_or_default());
+    let after = after.map(|user| serde_json::to_value(user).unwrap_or_default());
+
+    match (before, after) {
+        (Some(Value::Object(before)), Some(Value::Object(after))) => {
+            let mut before_diff = Map::new();
+            let mut after_diff = Map::new();
+            for (key, after_value) in after.iter() {
+                let before_value = before.get(key).cloned().unwrap_or(Value::Null);
+                if &before_value != after_value {
+                    before_diff.insert(key.clone(), before_value);
+                    after_diff.insert(key.clone(), after_value.clone());
+                }
+            }
+            (
+                Some(Value::Object(before_diff)),
+                Some(Value::Object(after_diff)),
+            )
+        }
+        (before, after) => (before, after),
+    }
+}
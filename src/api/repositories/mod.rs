@@ -1,2 +1,6 @@
+pub mod audit_logs;
+pub mod outbox;
+pub mod rate_limits;
 pub mod sample;
 pub mod users;
+pub mod webhooks;
@@ -0,0 +1,166 @@
+// SeaORM
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseTransaction, DbBackend, DbErr, QueryFilter, QueryOrder,
+    QuerySelect, Set, Statement, TransactionTrait,
+    entity::EntityTrait,
+    sea_query::{Expr, LockBehavior, LockType},
+};
+
+// chrono
+use chrono::{DateTime, FixedOffset, SubsecRound, Utc};
+
+// 標準ライブラリ
+use std::time::Duration;
+
+// CloudEvents用のモジュール
+use crate::api::events::cloud_event::CloudEvent;
+
+// Outboxエンティティのモジュール
+use crate::api::entities::prelude::{Outbox, OutboxActiveModel, OutboxColumn, OutboxModel};
+
+// アウトボックスのステータス
+pub const OUTBOX_STATUS_PENDING: &str = "pending";
+pub const OUTBOX_STATUS_PUBLISHED: &str = "published";
+pub const OUTBOX_STATUS_FAILED: &str = "failed";
+
+// イベント登録を通知するPostgresのチャンネル名（ペイロードはアウトボックスのID）
+pub const OUTBOX_NOTIFY_CHANNEL: &str = "outbox_events";
+
+// イベントの登録（呼び出し元のトランザクション内で実行する）
+// NOTIFYはコミット時に配信されるため、ロールバックされたイベントは通知されない
+pub async fn create_outbox_event(
+    tx: &DatabaseTransaction,
+    event: &CloudEvent,
+) -> Result<(), DbErr> {
+    let result = Outbox::insert(OutboxActiveModel {
+        event_id: Set(event.id.clone()),
+        event_type: Set(event.event_type.clone()),
+        aggregate_id: Set(event.subject.clone().unwrap_or_default()),
+        payload: Set(serde_json::to_value(event).unwrap_or_default()),
+        status: Set(OUTBOX_STATUS_PENDING.to_string()),
+        ..Default::default()
+    })
+    .exec(tx)
+    .await?;
+
+    tx.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT pg_notify($1, $2)",
+        [
+            OUTBOX_NOTIFY_CHANNEL.into(),
+            result.last_insert_id.to_string().into(),
+        ],
+    ))
+    .await?;
+
+    Ok(())
+}
+
+// IDからイベントを取得
+pub async fn get_outbox_event_from_id<C: ConnectionTrait>(
+    db: &C,
+    id: i64,
+) -> Result<Option<OutboxModel>, DbErr> {
+    Outbox::find_by_id(id).one(db).await
+}
+
+// 送信対象のイベントを古い順に取得
+// 複数レプリカで同じイベントを重複して取得しないよう、行ロックを取得してロック中の行はスキップする
+pub async fn fetch_pending_events(
+    tx: &DatabaseTransaction,
+    limit: u64,
+) -> Result<Vec<OutboxModel>, DbErr> {
+    let now: DateTime<FixedOffset> = Utc::now().into();
+
+    Outbox::find()
+        .filter(OutboxColumn::Status.eq(OUTBOX_STATUS_PENDING))
+        .filter(OutboxColumn::NextAttemptAt.lte(now))
+        .order_by_asc(OutboxColumn::Id)
+        .limit(limit)
+        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+        .all(tx)
+        .await
+}
+
+// 送信対象のイベントを取得し、送信中として次回送信日時を期限まで延ばしてコミットする
+// 送信中は行ロックやDB接続を保持せず、期限までに結果を記録できなかった場合は再送する
+// 返すイベントの次回送信日時は期限の値（結果の記録時に他のレプリカが取得し直していないか確認する）
+pub async fn claim_pending_events<C: TransactionTrait>(
+    db: &C,
+    limit: u64,
+    lease: Duration,
+) -> Result<Vec<OutboxModel>, DbErr> {
+    let tx = db.begin().await?;
+    let events = fetch_pending_events(&tx, limit).await?;
+    if events.is_empty() {
+        tx.commit().await?;
+        return Ok(events);
+    }
+
+    // 取得時の値と比較するため、DBの精度（マイクロ秒）に合わせる
+    let lease = chrono::Duration::from_std(lease).unwrap_or(chrono::Duration::MAX);
+    let lease_until: DateTime<FixedOffset> = (Utc::now() + lease).trunc_subsecs(6).into();
+    let ids: Vec<i64> = events.iter().map(|event| event.id).collect();
+    Outbox::update_many()
+        .col_expr(OutboxColumn::NextAttemptAt, Expr::value(lease_until))
+        .filter(OutboxColumn::Id.is_in(ids))
+        .exec(&tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(events
+        .into_iter()
+        .map(|event| OutboxModel {
+            next_attempt_at: lease_until,
+            ..event
+        })
+        .collect())
+}
+
+// 取得したイベントの結果を記録（期限切れで他のレプリカが取得し直した場合は更新せずにfalseを返す）
+async fn update_claimed_event(
+    tx: &DatabaseTransaction,
+    event: &OutboxModel,
+    update: OutboxActiveModel,
+) -> Result<bool, DbErr> {
+    let result = Outbox::update_many()
+        .set(update)
+        .filter(OutboxColumn::Id.eq(event.id))
+        .filter(OutboxColumn::Status.eq(OUTBOX_STATUS_PENDING))
+        .filter(OutboxColumn::NextAttemptAt.eq(event.next_attempt_at))
+        .exec(tx)
+        .await?;
+
+    Ok(result.rows_affected > 0)
+}
+
+// 送信済みに更新（取得し直された場合はfalse）
+pub async fn mark_published(tx: &DatabaseTransaction, event: &OutboxModel) -> Result<bool, DbErr> {
+    let update = OutboxActiveModel {
+        status: Set(OUTBOX_STATUS_PUBLISHED.to_string()),
+        attempts: Set(event.attempts + 1),
+        last_error: Set(None),
+        published_at: Set(Some(Utc::now().into())),
+        ..Default::default()
+    };
+    update_claimed_event(tx, event, update).await
+}
+
+// 送信失敗を記録（次回送信日時がNoneの場合はリトライしない、取得し直された場合はfalse）
+pub async fn mark_failed(
+    tx: &DatabaseTransaction,
+    event: &OutboxModel,
+    error: String,
+    next_attempt_at: Option<DateTime<FixedOffset>>,
+) -> Result<bool, DbErr> {
+    let mut update = OutboxActiveModel {
+        attempts: Set(event.attempts + 1),
+        last_error: Set(Some(error)),
+        ..Default::default()
+    };
+    match next_attempt_at {
+        Some(next_attempt_at) => update.next_attempt_at = Set(next_attempt_at),
+        None => update.status = Set(OUTBOX_STATUS_FAILED.to_string()),
+    }
+    update_claimed_event(tx, event, update).await
+}
@@ -0,0 +1 @@
+pub mod rate_limits_repository;
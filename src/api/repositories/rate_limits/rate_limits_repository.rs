@@ -0,0 +1,69 @@
+// SeaORM
+use sea_orm::{ConnectionTrait, DbBackend, DbErr, QueryResult, Statement};
+
+// DBの現在時刻（UNIX時間のミリ秒、複数レプリカで同じ時刻を基準にする）
+const NOW_MS: &str = "(EXTRACT(EPOCH FROM clock_timestamp()) * 1000)::bigint";
+
+// 理論上の到着時刻と判定時の現在時刻
+fn tat_and_now(row: QueryResult) -> Result<(i64, i64), DbErr> {
+    Ok((row.try_get("", "tat")?, row.try_get("", "now_ms")?))
+}
+
+// 許可できる場合のみ理論上の到着時刻を進める（判定と更新を1つのSQLで行う）
+// 許可した場合は更新後の到着時刻と現在時刻を返し、許可しない場合はNoneを返す
+pub async fn acquire_rate_limit<C: ConnectionTrait>(
+    db: &C,
+    key: &str,
+    emission_interval_ms: i64,
+    tolerance_ms: i64,
+) -> Result<Option<(i64, i64)>, DbErr> {
+    let sql = format!(
+        "WITH clock AS (SELECT {} AS now_ms) \
+         INSERT INTO rate_limits (key, tat) \
+         SELECT $1, now_ms + $2 FROM clock \
+         ON CONFLICT (key) DO UPDATE \
+         SET tat = GREATEST(rate_limits.tat, EXCLUDED.tat - $2) + $2 \
+         WHERE GREATEST(rate_limits.tat, EXCLUDED.tat - $2) + $2 - (EXCLUDED.tat - $2) <= $3 \
+         RETURNING tat, (SELECT now_ms FROM clock) AS now_ms",
+        NOW_MS
+    );
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            [key.into(), emission_interval_ms.into(), tolerance_ms.into()],
+        ))
+        .await?;
+
+    row.map(tat_and_now).transpose()
+}
+
+// 現在の理論上の到着時刻と現在時刻の取得（状態が無い場合はNone）
+pub async fn get_rate_limit<C: ConnectionTrait>(
+    db: &C,
+    key: &str,
+) -> Result<Option<(i64, i64)>, DbErr> {
+    let sql = format!(
+        "SELECT tat, {} AS now_ms FROM rate_limits WHERE key = $1",
+        NOW_MS
+    );
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            [key.into()],
+        ))
+        .await?;
+
+    row.map(tat_and_now).transpose()
+}
+
+// 期限切れ（理論上の到着時刻を過ぎた）の状態の削除
+pub async fn delete_expired_rate_limits<C: ConnectionTrait>(db: &C) -> Result<u64, DbErr> {
+    let sql = format!("DELETE FROM rate_limits WHERE tat < {}", NOW_MS);
+    let result = db
+        .execute(Statement::from_string(DbBackend::Postgres, sql))
+        .await?;
+
+    Ok(result.rows_affected())
+}
@@ -27,6 +27,7 @@ pub trait SampleRepositoryTrait {
 #[async_trait::async_trait]
 impl SampleRepositoryTrait for SampleRepository {
     // 文字列「Sample Hello !!」を返す関数
+    #[tracing::instrument(name = "SampleRepository.sample_hello", skip_all)]
     async fn sample_hello(&self, ctx: &Context) -> Result<String, CommonError> {
         let text = "Sample Hello !!".to_string();
 
@@ -24,6 +24,19 @@ use crate::api::databases::database::db_connection;
 // Usersエンティティのモジュール
 use crate::api::entities::prelude::{Users, UsersActiveModel, UsersColumn, UsersModel};
 
+// イベント用のモジュール
+use crate::api::events::cloud_event::{
+    EVENT_TYPE_USER_CREATED, EVENT_TYPE_USER_DELETED, EVENT_TYPE_USER_UPDATED, new_user_event,
+};
+
+// アウトボックス用のモジュール
+use crate::api::repositories::outbox::outbox_repository::create_outbox_event;
+
+// 監査ログ用のモジュール
+use crate::api::repositories::audit_logs::audit_logs_repository::{
+    AUDIT_ACTION_CREATE, AUDIT_ACTION_DELETE, AUDIT_ACTION_UPDATE, create_audit_log,
+};
+
 // サンプルリポジトリーの構造体
 pub struct UsersRepository;
 
@@ -66,6 +79,7 @@ pub trait UsersRepositoryTrait {
 #[async_trait::async_trait]
 impl UsersRepositoryTrait for UsersRepository {
     // ユーザー作成
+    #[tracing::instrument(name = "UsersRepository.create_user", skip_all, fields(uid = %uid))]
     async fn create_user(
         &self,
         ctx: &Context,
@@ -137,6 +151,35 @@ impl UsersRepositoryTrait for UsersRepository {
                 // 必ずSomeの想定のためunwrap_or_default()を使う。
                 let user = result.unwrap_or_default();
 
+                // 監査ログの登録
+                if let Err(err) =
+                    create_audit_log(&tx, ctx, AUDIT_ACTION_CREATE, None, Some(&user)).await
+                {
+                    let msg = format!(
+                        "[UsersRepository.create_user] 監査ログの登録に失敗しました。: {}",
+                        err
+                    );
+                    error(ctx, &msg);
+                    return Err(CommonError::CustomError {
+                        status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: msg,
+                    });
+                }
+
+                // ユーザーのライフサイクルイベントをアウトボックスに登録
+                let event = new_user_event(EVENT_TYPE_USER_CREATED, &user);
+                if let Err(err) = create_outbox_event(&tx, &event).await {
+                    let msg = format!(
+                        "[UsersRepository.create_user] イベントの登録に失敗しました。: {}",
+                        err
+                    );
+                    error(ctx, &msg);
+                    return Err(CommonError::CustomError {
+                        status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: msg,
+                    });
+                }
+
                 // コミット
                 match tx.commit().await {
                     Ok(_) => {}
@@ -167,6 +210,7 @@ impl UsersRepositoryTrait for UsersRepository {
     }
 
     // 全ての有効なユーザーを取得
+    #[tracing::instrument(name = "UsersRepository.get_users", skip_all)]
     async fn get_users(&self, ctx: &Context) -> Result<Vec<UsersModel>, CommonError> {
         // DB接続
         let db = match db_connection().await {
@@ -194,6 +238,7 @@ impl UsersRepositoryTrait for UsersRepository {
     }
 
     // Uidから対象ユーザーを取得
+    #[tracing::instrument(name = "UsersRepository.get_user_from_uid", skip_all, fields(uid = %uid))]
     async fn get_user_from_uid(
         &self,
         ctx: &Context,
@@ -241,6 +286,7 @@ impl UsersRepositoryTrait for UsersRepository {
     }
 
     // 対象ユーザー更新
+    #[tracing::instrument(name = "UsersRepository.update_user", skip_all, fields(uid = %uid))]
     async fn update_user(
         &self,
         ctx: &Context,
@@ -285,8 +331,8 @@ impl UsersRepositoryTrait for UsersRepository {
             .one(&tx)
             .await;
 
-        let mut update_user: UsersActiveModel = match select_user {
-            Ok(user) => user.unwrap().into(),
+        let before_user: UsersModel = match select_user {
+            Ok(user) => user.unwrap(),
             Err(err) => {
                 let msg = format!(
                     "[UsersRepository.update_user] 対象ユーザー取得エラー: {}",
@@ -300,6 +346,8 @@ impl UsersRepositoryTrait for UsersRepository {
             }
         };
 
+        let mut update_user: UsersActiveModel = before_user.clone().into();
+
         // 更新する値の設定
         if !last_name.is_empty() {
             update_user.last_name = Set(last_name);
@@ -334,6 +382,41 @@ impl UsersRepositoryTrait for UsersRepository {
             }
         };
 
+        // 監査ログの登録
+        if let Err(err) = create_audit_log(
+            &tx,
+            ctx,
+            AUDIT_ACTION_UPDATE,
+            Some(&before_user),
+            Some(&user),
+        )
+        .await
+        {
+            let msg = format!(
+                "[UsersRepository.update_user] 監査ログの登録に失敗しました。: {}",
+                err
+            );
+            error(ctx, &msg);
+            return Err(CommonError::CustomError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: msg,
+            });
+        }
+
+        // ユーザーのライフサイクルイベントをアウトボックスに登録
+        let event = new_user_event(EVENT_TYPE_USER_UPDATED, &user);
+        if let Err(err) = create_outbox_event(&tx, &event).await {
+            let msg = format!(
+                "[UsersRepository.update_user] イベントの登録に失敗しました。: {}",
+                err
+            );
+            error(ctx, &msg);
+            return Err(CommonError::CustomError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: msg,
+            });
+        }
+
         // コミット
         match tx.commit().await {
             Ok(_) => {}
@@ -351,6 +434,7 @@ impl UsersRepositoryTrait for UsersRepository {
     }
 
     // 対象ユーザー削除（論理削除）
+    #[tracing::instrument(name = "UsersRepository.delete_user", skip_all, fields(uid = %uid))]
     async fn delete_user(&self, ctx: &Context, uid: String) -> Result<UsersModel, CommonError> {
         // DB接続
         let db = match db_connection().await {
@@ -388,8 +472,8 @@ impl UsersRepositoryTrait for UsersRepository {
             .one(&tx)
             .await;
 
-        let mut delete_user: UsersActiveModel = match select_user {
-            Ok(user) => user.unwrap().into(),
+        let before_user: UsersModel = match select_user {
+            Ok(user) => user.unwrap(),
             Err(err) => {
                 let msg = format!(
                     "[UsersRepository.delete_user] 対象ユーザー取得エラー: {}",
@@ -403,6 +487,8 @@ impl UsersRepositoryTrait for UsersRepository {
             }
         };
 
+        let mut delete_user: UsersActiveModel = before_user.clone().into();
+
         // 現在日時を取得
         let current_date: DateTime<FixedOffset> =
             DateTime::<Utc>::from_naive_utc_and_offset(chrono::Utc::now().naive_utc(), Utc).into();
@@ -439,6 +525,41 @@ impl UsersRepositoryTrait for UsersRepository {
             }
         };
 
+        // 監査ログの登録
+        if let Err(err) = create_audit_log(
+            &tx,
+            ctx,
+            AUDIT_ACTION_DELETE,
+            Some(&before_user),
+            Some(&user),
+        )
+        .await
+        {
+            let msg = format!(
+                "[UsersRepository.delete_user] 監査ログの登録に失敗しました。: {}",
+                err
+            );
+            error(ctx, &msg);
+            return Err(CommonError::CustomError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: msg,
+            });
+        }
+
+        // ユーザーのライフサイクルイベントをアウトボックスに登録
+        let event = new_user_event(EVENT_TYPE_USER_DELETED, &user);
+        if let Err(err) = create_outbox_event(&tx, &event).await {
+            let msg = format!(
+                "[UsersRepository.delete_user] イベントの登録に失敗しました。: {}",
+                err
+            );
+            error(ctx, &msg);
+            return Err(CommonError::CustomError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: msg,
+            });
+        }
+
         // コミット
         match tx.commit().await {
             Ok(_) => {}
@@ -0,0 +1 @@
+pub mod webhooks_repository;
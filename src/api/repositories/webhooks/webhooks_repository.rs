@@ -0,0 +1,530 @@
+// SeaORM
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseTransaction, DbErr, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+    entity::EntityTrait,
+    sea_query::{Expr, LockBehavior, LockType},
+};
+
+// axum
+use axum::http::StatusCode;
+
+// chrono
+use chrono::{DateTime, FixedOffset, SubsecRound, Utc};
+
+// 標準ライブラリ
+use std::time::Duration;
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// 共通エラー用モジュール
+use crate::api::errors::error::CommonError;
+
+// ロガー用のモジュール
+use crate::api::loggers::logger::error;
+
+// DB接続用のモジュール
+use crate::api::databases::database::db_connection;
+
+// CloudEvents用のモジュール
+use crate::api::events::cloud_event::CloudEvent;
+
+// Webhook送信用のモジュール
+use crate::api::events::webhook_sender::WebhookSendResult;
+
+// エンティティのモジュール
+use crate::api::entities::prelude::{
+    WebhookDeliveries, WebhookDeliveriesActiveModel, WebhookDeliveriesColumn,
+    WebhookDeliveriesModel, WebhookSubscriptions, WebhookSubscriptionsActiveModel,
+    WebhookSubscriptionsColumn, WebhookSubscriptionsModel,
+};
+
+// 配信ステータス
+pub const DELIVERY_STATUS_PENDING: &str = "pending";
+pub const DELIVERY_STATUS_SUCCEEDED: &str = "succeeded";
+pub const DELIVERY_STATUS_FAILED: &str = "failed";
+pub const DELIVERY_STATUS_DEAD: &str = "dead";
+
+// 全てのイベント種別を購読する場合の指定
+pub const WEBHOOK_EVENT_TYPE_ALL: &str = "*";
+
+// Webhookリポジトリーの構造体
+pub struct WebhooksRepository;
+
+impl WebhooksRepository {
+    // 初期化用メソッド
+    pub fn new() -> Self {
+        WebhooksRepository
+    }
+}
+
+// Webhookリポジトリー用のトレイト（モック化もできるように定義）
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait WebhooksRepositoryTrait {
+    async fn create_webhook(
+        &self,
+        ctx: &Context,
+        uid: String,
+        url: String,
+        event_types: Vec<String>,
+        secret: String,
+    ) -> Result<WebhookSubscriptionsModel, CommonError>;
+    async fn get_webhooks(
+        &self,
+        ctx: &Context,
+    ) -> Result<Vec<WebhookSubscriptionsModel>, CommonError>;
+    async fn get_webhook_from_uid(
+        &self,
+        ctx: &Context,
+        uid: String,
+    ) -> Result<Option<WebhookSubscriptionsModel>, CommonError>;
+    async fn delete_webhook(
+        &self,
+        ctx: &Context,
+        uid: String,
+    ) -> Result<Option<WebhookSubscriptionsModel>, CommonError>;
+    async fn get_webhook_deliveries(
+        &self,
+        ctx: &Context,
+        uid: String,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<WebhookDeliveriesModel>, u64), CommonError>;
+    async fn create_webhook_delivery(
+        &self,
+        ctx: &Context,
+        subscription_uid: String,
+        event: CloudEvent,
+    ) -> Result<WebhookDeliveriesModel, CommonError>;
+    async fn update_webhook_delivery_result(
+        &self,
+        ctx: &Context,
+        delivery: WebhookDeliveriesModel,
+        result: WebhookSendResult,
+    ) -> Result<WebhookDeliveriesModel, CommonError>;
+}
+
+// DBエラーを共通エラーに変換する関数
+fn db_error(ctx: &Context, method: &str, label: &str, err: DbErr) -> CommonError {
+    let msg = format!("[WebhooksRepository.{}] {}: {}", method, label, err);
+    error(ctx, &msg);
+    CommonError::CustomError {
+        status_code: StatusCode::INTERNAL_SERVER_ERROR,
+        message: msg,
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhooksRepositoryTrait for WebhooksRepository {
+    // Webhookの購読作成
+    #[tracing::instrument(name = "WebhooksRepository.create_webhook", skip_all, fields(uid = %uid))]
+    async fn create_webhook(
+        &self,
+        ctx: &Context,
+        uid: String,
+        url: String,
+        event_types: Vec<String>,
+        secret: String,
+    ) -> Result<WebhookSubscriptionsModel, CommonError> {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => return Err(db_error(ctx, "create_webhook", "DB接続エラー", err)),
+        };
+
+        let webhook = WebhookSubscriptionsActiveModel {
+            uid: Set(uid),
+            url: Set(url),
+            event_types: Set(serde_json::json!(event_types)),
+            secret: Set(secret),
+            ..Default::default()
+        };
+
+        match webhook.insert(&db).await {
+            Ok(webhook) => Ok(webhook),
+            Err(err) => Err(db_error(
+                ctx,
+                "create_webhook",
+                "Webhookの購読登録に失敗しました。",
+                err,
+            )),
+        }
+    }
+
+    // 全ての有効なWebhookの購読を取得
+    #[tracing::instrument(name = "WebhooksRepository.get_webhooks", skip_all)]
+    async fn get_webhooks(
+        &self,
+        ctx: &Context,
+    ) -> Result<Vec<WebhookSubscriptionsModel>, CommonError> {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => return Err(db_error(ctx, "get_webhooks", "DB接続エラー", err)),
+        };
+
+        match find_active_webhooks(&db).await {
+            Ok(webhooks) => Ok(webhooks),
+            Err(err) => Err(db_error(
+                ctx,
+                "get_webhooks",
+                "Webhookの購読取得エラー",
+                err,
+            )),
+        }
+    }
+
+    // Uidから有効なWebhookの購読を取得
+    #[tracing::instrument(name = "WebhooksRepository.get_webhook_from_uid", skip_all, fields(uid = %uid))]
+    async fn get_webhook_from_uid(
+        &self,
+        ctx: &Context,
+        uid: String,
+    ) -> Result<Option<WebhookSubscriptionsModel>, CommonError> {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => return Err(db_error(ctx, "get_webhook_from_uid", "DB接続エラー", err)),
+        };
+
+        let select_result = WebhookSubscriptions::find()
+            .filter(WebhookSubscriptionsColumn::Uid.eq(uid))
+            .filter(WebhookSubscriptionsColumn::DeletedAt.is_null())
+            .one(&db)
+            .await;
+
+        match select_result {
+            Ok(webhook) => Ok(webhook),
+            Err(err) => Err(db_error(
+                ctx,
+                "get_webhook_from_uid",
+                "Webhookの購読取得エラー",
+                err,
+            )),
+        }
+    }
+
+    // Webhookの購読削除（論理削除）
+    #[tracing::instrument(name = "WebhooksRepository.delete_webhook", skip_all, fields(uid = %uid))]
+    async fn delete_webhook(
+        &self,
+        ctx: &Context,
+        uid: String,
+    ) -> Result<Option<WebhookSubscriptionsModel>, CommonError> {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => return Err(db_error(ctx, "delete_webhook", "DB接続エラー", err)),
+        };
+
+        let select_result = WebhookSubscriptions::find()
+            .filter(WebhookSubscriptionsColumn::Uid.eq(uid))
+            .filter(WebhookSubscriptionsColumn::DeletedAt.is_null())
+            .one(&db)
+            .await;
+
+        let webhook = match select_result {
+            Ok(Some(webhook)) => webhook,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                return Err(db_error(
+                    ctx,
+                    "delete_webhook",
+                    "Webhookの購読取得エラー",
+                    err,
+                ));
+            }
+        };
+
+        // updated_atとdeleted_atの設定
+        let current_date: DateTime<FixedOffset> = Utc::now().into();
+        let mut delete_webhook: WebhookSubscriptionsActiveModel = webhook.into();
+        delete_webhook.updated_at = Set(current_date);
+        delete_webhook.deleted_at = Set(Some(current_date));
+
+        match delete_webhook.update(&db).await {
+            Ok(webhook) => Ok(Some(webhook)),
+            Err(err) => Err(db_error(
+                ctx,
+                "delete_webhook",
+                "Webhookの購読削除エラー",
+                err,
+            )),
+        }
+    }
+
+    // Webhookの配信ログを新しい順に取得（ページネーション有り）
+    #[tracing::instrument(name = "WebhooksRepository.get_webhook_deliveries", skip_all, fields(uid = %uid))]
+    async fn get_webhook_deliveries(
+        &self,
+        ctx: &Context,
+        uid: String,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<WebhookDeliveriesModel>, u64), CommonError> {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => return Err(db_error(ctx, "get_webhook_deliveries", "DB接続エラー", err)),
+        };
+
+        let paginator = WebhookDeliveries::find()
+            .filter(WebhookDeliveriesColumn::SubscriptionUid.eq(uid))
+            .order_by_desc(WebhookDeliveriesColumn::Id)
+            .paginate(&db, per_page);
+
+        // 総件数の取得
+        let total = match paginator.num_items().await {
+            Ok(total) => total,
+            Err(err) => {
+                return Err(db_error(
+                    ctx,
+                    "get_webhook_deliveries",
+                    "配信ログ件数取得エラー",
+                    err,
+                ));
+            }
+        };
+
+        // 対象ページの取得（ページ番号は1始まり）
+        match paginator.fetch_page(page - 1).await {
+            Ok(deliveries) => Ok((deliveries, total)),
+            Err(err) => Err(db_error(
+                ctx,
+                "get_webhook_deliveries",
+                "配信ログ取得エラー",
+                err,
+            )),
+        }
+    }
+
+    // Webhookの配信作成
+    #[tracing::instrument(name = "WebhooksRepository.create_webhook_delivery", skip_all)]
+    async fn create_webhook_delivery(
+        &self,
+        ctx: &Context,
+        subscription_uid: String,
+        event: CloudEvent,
+    ) -> Result<WebhookDeliveriesModel, CommonError> {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                return Err(db_error(
+                    ctx,
+                    "create_webhook_delivery",
+                    "DB接続エラー",
+                    err,
+                ));
+            }
+        };
+
+        match new_delivery(subscription_uid, &event).insert(&db).await {
+            Ok(delivery) => Ok(delivery),
+            Err(err) => Err(db_error(
+                ctx,
+                "create_webhook_delivery",
+                "配信の登録に失敗しました。",
+                err,
+            )),
+        }
+    }
+
+    // Webhookの配信結果を記録（リトライはしない）
+    #[tracing::instrument(name = "WebhooksRepository.update_webhook_delivery_result", skip_all)]
+    async fn update_webhook_delivery_result(
+        &self,
+        ctx: &Context,
+        delivery: WebhookDeliveriesModel,
+        result: WebhookSendResult,
+    ) -> Result<WebhookDeliveriesModel, CommonError> {
+        // DB接続
+        let db = match db_connection().await {
+            Ok(db) => db,
+            Err(err) => {
+                return Err(db_error(
+                    ctx,
+                    "update_webhook_delivery_result",
+                    "DB接続エラー",
+                    err,
+                ));
+            }
+        };
+
+        let status = if result.is_success() {
+            DELIVERY_STATUS_SUCCEEDED
+        } else {
+            DELIVERY_STATUS_FAILED
+        };
+
+        match apply_delivery_result(delivery, result, status, None)
+            .update(&db)
+            .await
+        {
+            Ok(delivery) => Ok(delivery),
+            Err(err) => Err(db_error(
+                ctx,
+                "update_webhook_delivery_result",
+                "配信結果の更新に失敗しました。",
+                err,
+            )),
+        }
+    }
+}
+
+// 全ての有効なWebhookの購読を取得
+pub async fn find_active_webhooks<C: ConnectionTrait>(
+    db: &C,
+) -> Result<Vec<WebhookSubscriptionsModel>, DbErr> {
+    WebhookSubscriptions::find()
+        .filter(WebhookSubscriptionsColumn::DeletedAt.is_null())
+        .order_by_asc(WebhookSubscriptionsColumn::Id)
+        .all(db)
+        .await
+}
+
+// 購読がイベント種別に一致するかを判定する関数
+pub fn is_subscribed(webhook: &WebhookSubscriptionsModel, event_type: &str) -> bool {
+    match webhook.event_types.as_array() {
+        Some(event_types) => event_types
+            .iter()
+            .filter_map(|value| value.as_str())
+            .any(|value| value == event_type || value == WEBHOOK_EVENT_TYPE_ALL),
+        None => false,
+    }
+}
+
+// 配信のActiveModelを作成する関数
+fn new_delivery(subscription_uid: String, event: &CloudEvent) -> WebhookDeliveriesActiveModel {
+    WebhookDeliveriesActiveModel {
+        subscription_uid: Set(subscription_uid),
+        event_id: Set(event.id.clone()),
+        event_type: Set(event.event_type.clone()),
+        payload: Set(serde_json::to_value(event).unwrap_or_default()),
+        status: Set(DELIVERY_STATUS_PENDING.to_string()),
+        ..Default::default()
+    }
+}
+
+// 配信結果をActiveModelに反映する関数
+fn apply_delivery_result(
+    delivery: WebhookDeliveriesModel,
+    result: WebhookSendResult,
+    status: &str,
+    next_attempt_at: Option<DateTime<FixedOffset>>,
+) -> WebhookDeliveriesActiveModel {
+    let attempts = delivery.attempts + 1;
+    let mut delivery: WebhookDeliveriesActiveModel = delivery.into();
+    delivery.attempts = Set(attempts);
+    delivery.status = Set(status.to_string());
+    delivery.response_status = Set(result.response_status);
+    if result.is_success() {
+        delivery.delivered_at = Set(Some(Utc::now().into()));
+    }
+    delivery.last_error = Set(result.error);
+    if let Some(next_attempt_at) = next_attempt_at {
+        delivery.next_attempt_at = Set(next_attempt_at);
+    }
+    delivery
+}
+
+// イベント種別を購読している全てのWebhookに配信を登録し、登録件数を返す
+pub async fn create_webhook_deliveries_for_event<C: ConnectionTrait>(
+    db: &C,
+    event: &CloudEvent,
+) -> Result<usize, DbErr> {
+    let webhooks = find_active_webhooks(db).await?;
+    let mut count = 0;
+    for webhook in webhooks
+        .into_iter()
+        .filter(|webhook| is_subscribed(webhook, &event.event_type))
+    {
+        new_delivery(webhook.uid, event).insert(db).await?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+// 送信対象の配信を古い順に取得
+// 複数レプリカで同じ配信を重複して取得しないよう、行ロックを取得してロック中の行はスキップする
+pub async fn fetch_pending_deliveries(
+    tx: &DatabaseTransaction,
+    limit: u64,
+) -> Result<Vec<WebhookDeliveriesModel>, DbErr> {
+    let now: DateTime<FixedOffset> = Utc::now().into();
+
+    WebhookDeliveries::find()
+        .filter(WebhookDeliveriesColumn::Status.eq(DELIVERY_STATUS_PENDING))
+        .filter(WebhookDeliveriesColumn::NextAttemptAt.lte(now))
+        .order_by_asc(WebhookDeliveriesColumn::Id)
+        .limit(limit)
+        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+        .all(tx)
+        .await
+}
+
+// 送信対象の配信を取得し、送信中として次回送信日時を期限まで延ばしてコミットする
+// 送信中は行ロックやDB接続を保持せず、期限までに結果を記録できなかった場合は再送する
+// 返す配信の次回送信日時は期限の値（結果の記録時に他のレプリカが取得し直していないか確認する）
+pub async fn claim_pending_deliveries<C: TransactionTrait>(
+    db: &C,
+    limit: u64,
+    lease: Duration,
+) -> Result<Vec<WebhookDeliveriesModel>, DbErr> {
+    let tx = db.begin().await?;
+    let deliveries = fetch_pending_deliveries(&tx, limit).await?;
+    if deliveries.is_empty() {
+        tx.commit().await?;
+        return Ok(deliveries);
+    }
+
+    // 取得時の値と比較するため、DBの精度（マイクロ秒）に合わせる
+    let lease = chrono::Duration::from_std(lease).unwrap_or(chrono::Duration::MAX);
+    let lease_until: DateTime<FixedOffset> = (Utc::now() + lease).trunc_subsecs(6).into();
+    let ids: Vec<i64> = deliveries.iter().map(|delivery| delivery.id).collect();
+    WebhookDeliveries::update_many()
+        .col_expr(
+            WebhookDeliveriesColumn::NextAttemptAt,
+            Expr::value(lease_until),
+        )
+        .filter(WebhookDeliveriesColumn::Id.is_in(ids))
+        .exec(&tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(deliveries
+        .into_iter()
+        .map(|delivery| WebhookDeliveriesModel {
+            next_attempt_at: lease_until,
+            ..delivery
+        })
+        .collect())
+}
+
+// 配信結果を記録（失敗時に次回送信日時がNoneの場合はデッドレターにする）
+// 期限切れで他のレプリカが取得し直した場合は更新せずにfalseを返す
+pub async fn mark_delivery_result(
+    tx: &DatabaseTransaction,
+    delivery: &WebhookDeliveriesModel,
+    result: WebhookSendResult,
+    next_attempt_at: Option<DateTime<FixedOffset>>,
+) -> Result<bool, DbErr> {
+    let status = match (result.is_success(), next_attempt_at) {
+        (true, _) => DELIVERY_STATUS_SUCCEEDED,
+        (false, Some(_)) => DELIVERY_STATUS_PENDING,
+        (false, None) => DELIVERY_STATUS_DEAD,
+    };
+    let update = apply_delivery_result(delivery.clone(), result, status, next_attempt_at);
+    let result = WebhookDeliveries::update_many()
+        .set(update)
+        .filter(WebhookDeliveriesColumn::Id.eq(delivery.id))
+        .filter(WebhookDeliveriesColumn::Status.eq(DELIVERY_STATUS_PENDING))
+        .filter(WebhookDeliveriesColumn::NextAttemptAt.eq(delivery.next_attempt_at))
+        .exec(tx)
+        .await?;
+
+    Ok(result.rows_affected > 0)
+}
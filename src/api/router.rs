@@ -1,11 +1,14 @@
 // axum
 use axum::{
-    Router, middleware,
+    Router,
+    extract::DefaultBodyLimit,
+    http::StatusCode,
+    middleware,
     routing::{delete, get, post, put},
 };
 
 // tower_http
-use tower_http::{cors::CorsLayer, trace::TraceLayer};
+use tower_http::trace::TraceLayer;
 
 // OpenAPI用
 use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
@@ -16,11 +19,30 @@ use utoipa_swagger_ui::SwaggerUi;
 use super::configs::config;
 
 // ハンドラー用のモジュール
+use super::handlers::admin::admin_handler;
+use super::handlers::health::health_handler;
+use super::handlers::metrics::metrics_handler;
 use super::handlers::sample::sample_handler;
 use super::handlers::users::users_handler;
+use super::handlers::webhooks::webhooks_handler;
+use super::handlers::ws::ws_handler;
 
 // ミドルウェア用のモジュール
-use super::middleware::common_middleware;
+use super::middleware::compression_middleware::{compression_layer, request_decompression_layer};
+use super::middleware::cors_middleware::{CorsGroup, cors_layer};
+use super::middleware::ip_filter_middleware::{IpFilterGroup, ip_filter_middleware};
+use super::middleware::rate_limit_middleware::{
+    POLICY_ADMIN, POLICY_AUTH, POLICY_CREATE_USER, RateLimitKey, RateLimitPolicy,
+    rate_limit_middleware,
+};
+use super::middleware::security_headers_middleware::{CspProfile, security_headers_middleware};
+use super::middleware::{body_log_middleware, common_middleware, request_limit_middleware};
+
+// 共通コンテキストの構造体
+use super::contexts::context::Context;
+
+// ロガー用のモジュール
+use super::loggers::logger;
 
 // OpenAPIの認証定義
 struct SecurityAddon;
@@ -39,14 +61,27 @@ impl Modify for SecurityAddon {
 #[derive(OpenApi)]
 #[openapi(
     paths(
+        health_handler::healthz,
+        health_handler::readyz,
+        health_handler::startupz,
         sample_handler::sample_get,
         sample_handler::sample_get_path_query,
         sample_handler::sample_post,
         users_handler::create_user,
         users_handler::get_users,
+        users_handler::get_user_events,
         users_handler::get_user_from_uid,
         users_handler::update_user,
         users_handler::delete_user,
+        users_handler::get_user_history,
+        webhooks_handler::create_webhook,
+        webhooks_handler::get_webhooks,
+        webhooks_handler::get_webhook_from_uid,
+        webhooks_handler::delete_webhook,
+        webhooks_handler::get_webhook_deliveries,
+        webhooks_handler::send_test_webhook,
+        ws_handler::ws,
+        admin_handler::get_config_version,
     ),
     components(),
     modifiers(&SecurityAddon),
@@ -62,23 +97,6 @@ pub fn router() -> Router {
     // 環境変数取得
     let config = config::get_config();
 
-    // CORS設定
-    let origin = config.allow_origin;
-    let cors = CorsLayer::new()
-        .allow_origin(vec![origin.parse().unwrap()])
-        .allow_methods(vec![
-            "GET".parse().unwrap(),
-            "POST".parse().unwrap(),
-            "PUT".parse().unwrap(),
-            "DELETE".parse().unwrap(),
-            "OPTIONS".parse().unwrap(),
-        ])
-        .allow_headers(vec![
-            "Content-Type".parse().unwrap(),
-            "Authorization".parse().unwrap(),
-        ])
-        .allow_credentials(true);
-
     // APIのグループ「v1」
     let v1 = Router::new()
         .route("/sample/get", get(sample_handler::sample_get))
@@ -87,49 +105,152 @@ pub fn router() -> Router {
             get(sample_handler::sample_get_path_query),
         )
         .route("/sample/post", post(sample_handler::sample_post))
-        .route("/user", post(users_handler::create_user));
+        .route(
+            "/user",
+            post(users_handler::create_user).layer(middleware::from_fn_with_state(
+                RateLimitPolicy::new(POLICY_CREATE_USER, RateLimitKey::Ip),
+                rate_limit_middleware,
+            )),
+        )
+        // 接続元のIPアドレスの制限（ルートのグループごとに設定する）
+        .layer(middleware::from_fn_with_state(
+            IpFilterGroup::Public,
+            ip_filter_middleware,
+        ))
+        // CORS設定（ルートのグループごとに設定を上書きできる）
+        .layer(cors_layer(config, CorsGroup::Public));
 
     // 認証有りのAPIのグループ「v1_auth」
     let v1_auth = Router::new()
         .route("/users", get(users_handler::get_users))
+        .route("/users/events", get(users_handler::get_user_events))
         .route("/user/{uid}", get(users_handler::get_user_from_uid))
         .route("/user/{uid}", put(users_handler::update_user))
-        .route("/user/{uid}", delete(users_handler::delete_user))
+        // ユーザーの削除は管理用のAPIと同じIPアドレスの制限を追加
+        .route(
+            "/user/{uid}",
+            delete(users_handler::delete_user).layer(middleware::from_fn_with_state(
+                IpFilterGroup::Admin,
+                ip_filter_middleware,
+            )),
+        )
+        .route("/user/{uid}/history", get(users_handler::get_user_history))
+        .route("/webhook", post(webhooks_handler::create_webhook))
+        .route("/webhooks", get(webhooks_handler::get_webhooks))
+        .route(
+            "/webhook/{uid}",
+            get(webhooks_handler::get_webhook_from_uid),
+        )
+        .route("/webhook/{uid}", delete(webhooks_handler::delete_webhook))
+        .route(
+            "/webhook/{uid}/deliveries",
+            get(webhooks_handler::get_webhook_deliveries),
+        )
+        .route(
+            "/webhook/{uid}/test",
+            post(webhooks_handler::send_test_webhook),
+        )
+        .route("/ws", get(ws_handler::ws))
         // 認証用ミドルウェア設定
-        .layer(middleware::from_fn(common_middleware::auth_middleware));
+        .layer(middleware::from_fn(common_middleware::auth_middleware))
+        // レート制限（認証エラーになるリクエストも数えるため認証用ミドルウェアの外側に設定）
+        .layer(middleware::from_fn_with_state(
+            RateLimitPolicy::new(POLICY_AUTH, RateLimitKey::Principal),
+            rate_limit_middleware,
+        ))
+        // 接続元のIPアドレスの制限（許可しない場合は認証前に拒否する）
+        .layer(middleware::from_fn_with_state(
+            IpFilterGroup::Auth,
+            ip_filter_middleware,
+        ))
+        // CORS設定（プリフライトリクエストは認証せずに応答するため認証用ミドルウェアの外側に設定）
+        .layer(cors_layer(config, CorsGroup::Auth));
+
+    // 管理用のAPIのグループ「admin」（CORSの設定を他のグループと分けるため別のグループにする）
+    let admin = Router::new()
+        .route("/admin/config", get(admin_handler::get_config_version))
+        // 認証用ミドルウェア設定
+        .layer(middleware::from_fn(common_middleware::auth_middleware))
+        // レート制限（認証エラーになるリクエストも数えるため認証用ミドルウェアの外側に設定）
+        .layer(middleware::from_fn_with_state(
+            RateLimitPolicy::new(POLICY_ADMIN, RateLimitKey::ApiKey),
+            rate_limit_middleware,
+        ))
+        // 接続元のIPアドレスの制限（事務所・VPNの範囲のみ許可する場合に設定）
+        .layer(middleware::from_fn_with_state(
+            IpFilterGroup::Admin,
+            ip_filter_middleware,
+        ))
+        // CORS設定
+        .layer(cors_layer(config, CorsGroup::Admin));
 
     // ルーター設定
     let router = Router::new()
         .nest("/api/v1", v1)
         .nest("/api/v1", v1_auth)
+        .nest("/api/v1", admin)
+        // 存在しないパスにも共通ミドルウェアを適用するためフォールバックを設定
+        .fallback(|| async { StatusCode::NOT_FOUND })
         // 共通ミドルウェアの設定（下から順番に読み込み）
+        .layer(middleware::from_fn(
+            body_log_middleware::body_log_middleware,
+        ))
+        // 圧縮されたリクエストボディの展開とレスポンスの圧縮（ボディのログは圧縮前の内容を出力する）
+        .layer(request_decompression_layer(config))
+        .layer(compression_layer(config))
+        // リクエストボディのサイズ、処理時間、同時に処理するリクエスト数の制限
+        .layer(DefaultBodyLimit::max(config.request_body_limit))
+        .layer(middleware::from_fn(
+            request_limit_middleware::body_limit_middleware,
+        ))
+        .layer(middleware::from_fn(
+            request_limit_middleware::timeout_middleware,
+        ))
+        .layer(middleware::from_fn(
+            request_limit_middleware::load_shed_middleware,
+        ))
         .layer(middleware::from_fn(common_middleware::request_middleware))
+        // セキュリティ関連のレスポンスヘッダー（共通ミドルウェアで返すエラーのレスポンスにも設定する）
+        .layer(middleware::from_fn_with_state(
+            CspProfile::Api,
+            security_headers_middleware,
+        ))
         .layer(TraceLayer::new_for_http().on_response(
             |res: &axum::response::Response,
              latency: std::time::Duration,
              _span: &tracing::Span| {
-                // レスポンスヘッダーからX-Request-Idを取得
-                let request_id = match res.headers().get("X-Request-Id") {
-                    Some(value) => value.to_str().unwrap_or("-").to_string(),
-                    None => "-".to_string(),
-                };
-
-                // ログ出力
-                log::info!(
-                    "[request_id={} status=({}) latency={}μs] finish request !!",
-                    request_id,
-                    res.status(),
-                    latency.as_micros()
-                )
+                // 共通コンテキストの情報を含めてアクセスログを出力
+                logger::access_log(res.extensions().get::<Context>(), res.status(), latency)
             },
         ))
-        .layer(cors);
+        // ヘルスチェックはログやメトリクスの対象外にするため共通ミドルウェアの後に追加
+        .merge(health_router());
 
     // 本番環境でない場合にOpenAPIを設定
-    if config.env != "production" {
+    if !config.env.is_production() {
         let openapi = SwaggerUi::new("/swagger-ui").url("/openapi.json", ApiDoc::openapi());
+        // Swagger UIはスクリプト・スタイル・画像を読み込むため緩和したContent-Security-Policyを設定
+        let openapi = Router::new()
+            .merge(openapi)
+            .layer(middleware::from_fn_with_state(
+                CspProfile::SwaggerUi,
+                security_headers_middleware,
+            ));
         return router.merge(openapi);
     }
 
     router
 }
+
+// ヘルスチェック用のルーター（Kubernetesのプローブ用）
+pub fn health_router() -> Router {
+    Router::new()
+        .route("/healthz", get(health_handler::healthz))
+        .route("/readyz", get(health_handler::readyz))
+        .route("/startupz", get(health_handler::startupz))
+}
+
+// メトリクス用のルーター（APIとは別のポートで待ち受ける）
+pub fn metrics_router() -> Router {
+    Router::new().route("/metrics", get(metrics_handler::metrics))
+}
@@ -1,2 +1,3 @@
 pub mod sample;
 pub mod users;
+pub mod webhooks;
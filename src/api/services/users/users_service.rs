@@ -5,10 +5,11 @@ use crate::api::contexts::context::Context;
 use crate::api::errors::error::CommonError;
 
 // リポジトリ用のモジュール
+use crate::api::repositories::audit_logs::audit_logs_repository::AuditLogsRepositoryTrait;
 use crate::api::repositories::users::users_repository::UsersRepositoryTrait;
 
 // Usersモデル
-use crate::api::entities::prelude::UsersModel;
+use crate::api::entities::prelude::{AuditLogsModel, UsersModel};
 
 // 使用するリポジトリーをまとめる構造体
 pub struct UsersCommonRepository {
@@ -17,6 +18,7 @@ pub struct UsersCommonRepository {
     // Sync: オブジェクトが複数のスレッドから同時にアクセスできることを保証
     // 'static: オブジェクトのライフタイムがプログラムが終了するまで破棄されない
     pub users_repo: Box<dyn UsersRepositoryTrait + Send + Sync + 'static>,
+    pub audit_logs_repo: Box<dyn AuditLogsRepositoryTrait + Send + Sync + 'static>,
 }
 
 // サンプルサービス
@@ -57,6 +59,13 @@ pub trait UsersServiceTrait {
         email: String,
     ) -> Result<UsersModel, CommonError>;
     async fn delete_user(&self, ctx: &Context, uid: String) -> Result<UsersModel, CommonError>;
+    async fn get_user_history(
+        &self,
+        ctx: &Context,
+        uid: String,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<AuditLogsModel>, u64), CommonError>;
 }
 
 #[async_trait::async_trait]
@@ -149,4 +158,27 @@ impl UsersServiceTrait for UsersService {
 
         Ok(user)
     }
+
+    // 対象ユーザーの変更履歴取得
+    async fn get_user_history(
+        &self,
+        ctx: &Context,
+        uid: String,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<AuditLogsModel>, u64), CommonError> {
+        let result = match self
+            .repo
+            .audit_logs_repo
+            .get_audit_logs_from_uid(ctx, uid, page, per_page)
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => {
+                return Err(err);
+            }
+        };
+
+        Ok(result)
+    }
 }
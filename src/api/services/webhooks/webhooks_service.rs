@@ -0,0 +1,244 @@
+// json変換用マクロ
+use serde_json::json;
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// 共通エラー用モジュール
+use crate::api::errors::error::CommonError;
+
+// configsモジュール
+use crate::api::configs::config;
+
+// イベント用のモジュール
+use crate::api::events::cloud_event::CloudEvent;
+use crate::api::events::webhook_sender::{send_webhook, webhook_client};
+
+// リポジトリ用のモジュール
+use crate::api::repositories::webhooks::webhooks_repository::WebhooksRepositoryTrait;
+
+// Webhookモデル
+use crate::api::entities::prelude::{WebhookDeliveriesModel, WebhookSubscriptionsModel};
+
+// テストイベントの種別と発生元
+pub const EVENT_TYPE_WEBHOOK_TEST: &str = "rust_api.webhook.test";
+pub const EVENT_SOURCE_WEBHOOKS: &str = "/rust_api/webhooks";
+
+// 使用するリポジトリーをまとめる構造体
+pub struct WebhooksCommonRepository {
+    // Box<T>型で動的にメモリ領域確保
+    // Send: オブジェクトが異なるスレッド間で安全に送信できることを保証
+    // Sync: オブジェクトが複数のスレッドから同時にアクセスできることを保証
+    // 'static: オブジェクトのライフタイムがプログラムが終了するまで破棄されない
+    pub webhooks_repo: Box<dyn WebhooksRepositoryTrait + Send + Sync + 'static>,
+}
+
+// Webhookサービス
+pub struct WebhooksService {
+    repo: WebhooksCommonRepository,
+}
+
+impl WebhooksService {
+    pub fn new(repo: WebhooksCommonRepository) -> Self {
+        WebhooksService { repo }
+    }
+}
+
+// Webhookサービス用のトレイト（モック化もできるように定義）
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait WebhooksServiceTrait {
+    async fn create_webhook(
+        &self,
+        ctx: &Context,
+        uid: String,
+        url: String,
+        event_types: Vec<String>,
+        secret: String,
+    ) -> Result<WebhookSubscriptionsModel, CommonError>;
+    async fn get_webhooks(
+        &self,
+        ctx: &Context,
+    ) -> Result<Vec<WebhookSubscriptionsModel>, CommonError>;
+    async fn get_webhook_from_uid(
+        &self,
+        ctx: &Context,
+        uid: String,
+    ) -> Result<Option<WebhookSubscriptionsModel>, CommonError>;
+    async fn delete_webhook(
+        &self,
+        ctx: &Context,
+        uid: String,
+    ) -> Result<Option<WebhookSubscriptionsModel>, CommonError>;
+    async fn get_webhook_deliveries(
+        &self,
+        ctx: &Context,
+        uid: String,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<WebhookDeliveriesModel>, u64), CommonError>;
+    async fn send_test_webhook(
+        &self,
+        ctx: &Context,
+        uid: String,
+    ) -> Result<Option<WebhookDeliveriesModel>, CommonError>;
+}
+
+#[async_trait::async_trait]
+impl WebhooksServiceTrait for WebhooksService {
+    // Webhookの購読作成
+    async fn create_webhook(
+        &self,
+        ctx: &Context,
+        uid: String,
+        url: String,
+        event_types: Vec<String>,
+        secret: String,
+    ) -> Result<WebhookSubscriptionsModel, CommonError> {
+        let webhook = match self
+            .repo
+            .webhooks_repo
+            .create_webhook(ctx, uid, url, event_types, secret)
+            .await
+        {
+            Ok(webhook) => webhook,
+            Err(err) => {
+                return Err(err);
+            }
+        };
+
+        Ok(webhook)
+    }
+
+    // 全ての有効なWebhookの購読取得
+    async fn get_webhooks(
+        &self,
+        ctx: &Context,
+    ) -> Result<Vec<WebhookSubscriptionsModel>, CommonError> {
+        let webhooks = match self.repo.webhooks_repo.get_webhooks(ctx).await {
+            Ok(webhooks) => webhooks,
+            Err(err) => {
+                return Err(err);
+            }
+        };
+
+        Ok(webhooks)
+    }
+
+    // Uidから有効なWebhookの購読取得
+    async fn get_webhook_from_uid(
+        &self,
+        ctx: &Context,
+        uid: String,
+    ) -> Result<Option<WebhookSubscriptionsModel>, CommonError> {
+        let webhook = match self.repo.webhooks_repo.get_webhook_from_uid(ctx, uid).await {
+            Ok(webhook) => webhook,
+            Err(err) => {
+                return Err(err);
+            }
+        };
+
+        Ok(webhook)
+    }
+
+    // Webhookの購読削除
+    async fn delete_webhook(
+        &self,
+        ctx: &Context,
+        uid: String,
+    ) -> Result<Option<WebhookSubscriptionsModel>, CommonError> {
+        let webhook = match self.repo.webhooks_repo.delete_webhook(ctx, uid).await {
+            Ok(webhook) => webhook,
+            Err(err) => {
+                return Err(err);
+            }
+        };
+
+        Ok(webhook)
+    }
+
+    // Webhookの配信ログ取得
+    async fn get_webhook_deliveries(
+        &self,
+        ctx: &Context,
+        uid: String,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<WebhookDeliveriesModel>, u64), CommonError> {
+        let result = match self
+            .repo
+            .webhooks_repo
+            .get_webhook_deliveries(ctx, uid, page, per_page)
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => {
+                return Err(err);
+            }
+        };
+
+        Ok(result)
+    }
+
+    // テストイベントを即時送信し、配信結果を返す
+    async fn send_test_webhook(
+        &self,
+        ctx: &Context,
+        uid: String,
+    ) -> Result<Option<WebhookDeliveriesModel>, CommonError> {
+        // 対象の購読取得
+        let webhook = match self
+            .repo
+            .webhooks_repo
+            .get_webhook_from_uid(ctx, uid.clone())
+            .await
+        {
+            Ok(Some(webhook)) => webhook,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                return Err(err);
+            }
+        };
+
+        // テストイベントの作成
+        let event = CloudEvent::new(
+            EVENT_SOURCE_WEBHOOKS,
+            EVENT_TYPE_WEBHOOK_TEST,
+            Some(uid.clone()),
+            json!({ "message": "This is a test event." }),
+        );
+
+        // 配信ログの登録
+        let delivery = match self
+            .repo
+            .webhooks_repo
+            .create_webhook_delivery(ctx, uid, event.clone())
+            .await
+        {
+            Ok(delivery) => delivery,
+            Err(err) => {
+                return Err(err);
+            }
+        };
+
+        // 署名付きで送信
+        let config = config::get_config();
+        let client = webhook_client(config.webhook_timeout);
+        let result = send_webhook(&client, &webhook.url, &webhook.secret, &event).await;
+
+        // 配信結果の記録
+        let delivery = match self
+            .repo
+            .webhooks_repo
+            .update_webhook_delivery_result(ctx, delivery, result)
+            .await
+        {
+            Ok(delivery) => delivery,
+            Err(err) => {
+                return Err(err);
+            }
+        };
+
+        Ok(Some(delivery))
+    }
+}
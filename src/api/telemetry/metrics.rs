@@ -0,0 +1,181 @@
+// Prometheus
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+
+// axum
+use axum::http::StatusCode;
+
+// SeaORM
+use sea_orm::DatabaseConnection;
+
+// 標準ライブラリ
+use std::sync::OnceLock;
+use std::time::Duration;
+
+// ルートに一致しなかったリクエストのラベル（生のURIはラベルに使用しない）
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+// 処理時間のヒストグラムの区切り（秒）
+const HTTP_DURATION_BUCKETS: [f64; 12] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+// アプリケーションのメトリクス
+pub struct AppMetrics {
+    registry: Registry,
+    http_requests_total: IntCounterVec,
+    http_request_duration_seconds: HistogramVec,
+    http_requests_in_flight: IntGauge,
+    db_pool_connections: IntGaugeVec,
+    db_pool_max_connections: IntGauge,
+    users_created_total: IntCounter,
+    users_deleted_total: IntCounter,
+}
+
+impl AppMetrics {
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let registry = Registry::new();
+
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "Total number of HTTP requests."),
+            &["method", "route", "status"],
+        )?;
+        let http_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency in seconds.",
+            )
+            .buckets(HTTP_DURATION_BUCKETS.to_vec()),
+            &["method", "route", "status"],
+        )?;
+        let http_requests_in_flight = IntGauge::new(
+            "http_requests_in_flight",
+            "Number of HTTP requests currently being processed.",
+        )?;
+        let db_pool_connections = IntGaugeVec::new(
+            Opts::new(
+                "db_pool_connections",
+                "Number of connections in the DB pool by state.",
+            ),
+            &["state"],
+        )?;
+        let db_pool_max_connections = IntGauge::new(
+            "db_pool_max_connections",
+            "Maximum number of connections in the DB pool.",
+        )?;
+        let users_created_total =
+            IntCounter::new("users_created_total", "Total number of created users.")?;
+        let users_deleted_total =
+            IntCounter::new("users_deleted_total", "Total number of deleted users.")?;
+
+        registry.register(Box::new(http_requests_total.clone()))?;
+        registry.register(Box::new(http_request_duration_seconds.clone()))?;
+        registry.register(Box::new(http_requests_in_flight.clone()))?;
+        registry.register(Box::new(db_pool_connections.clone()))?;
+        registry.register(Box::new(db_pool_max_connections.clone()))?;
+        registry.register(Box::new(users_created_total.clone()))?;
+        registry.register(Box::new(users_deleted_total.clone()))?;
+
+        Ok(AppMetrics {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            http_requests_in_flight,
+            db_pool_connections,
+            db_pool_max_connections,
+            users_created_total,
+            users_deleted_total,
+        })
+    }
+
+    // HTTPリクエストの処理結果を記録
+    pub fn observe_http_request(
+        &self,
+        method: &str,
+        route: &str,
+        status: StatusCode,
+        elapsed: Duration,
+    ) {
+        let route = if route.is_empty() {
+            UNMATCHED_ROUTE
+        } else {
+            route
+        };
+        let labels = [method, route, status_class(status)];
+        self.http_requests_total.with_label_values(&labels).inc();
+        self.http_request_duration_seconds
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    // 処理中のリクエスト数を増やす（戻り値の破棄時に減らす）
+    pub fn track_in_flight(&self) -> InFlightGuard {
+        self.http_requests_in_flight.inc();
+        InFlightGuard(self.http_requests_in_flight.clone())
+    }
+
+    // 処理中のリクエスト数
+    pub fn in_flight_requests(&self) -> i64 {
+        self.http_requests_in_flight.get()
+    }
+
+    // DB接続プールの状態を記録
+    pub fn observe_db_pool(&self, db: &DatabaseConnection) {
+        let pool = db.get_postgres_connection_pool();
+        let size = i64::from(pool.size());
+        let idle = pool.num_idle() as i64;
+        self.db_pool_connections
+            .with_label_values(&["idle"])
+            .set(idle);
+        self.db_pool_connections
+            .with_label_values(&["in_use"])
+            .set((size - idle).max(0));
+        self.db_pool_max_connections
+            .set(i64::from(pool.options().get_max_connections()));
+    }
+
+    pub fn inc_users_created(&self) {
+        self.users_created_total.inc();
+    }
+
+    pub fn inc_users_deleted(&self) {
+        self.users_deleted_total.inc();
+    }
+
+    // Prometheusのテキスト形式で出力
+    pub fn encode(&self) -> Result<String, prometheus::Error> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+}
+
+// 処理中のリクエスト数を減らすガード（リクエストが中断された場合も減らす）
+pub struct InFlightGuard(IntGauge);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+// ステータスコードの分類を返す関数（例：404 -> 4xx）
+pub fn status_class(status: StatusCode) -> &'static str {
+    match status.as_u16() {
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        _ => "5xx",
+    }
+}
+
+// メトリクスのインスタンス（プロセス内で共有）
+static APP_METRICS: OnceLock<AppMetrics> = OnceLock::new();
+
+// メトリクスを取得する関数
+pub fn app_metrics() -> &'static AppMetrics {
+    APP_METRICS.get_or_init(|| AppMetrics::new().expect("メトリクスの登録エラー"))
+}
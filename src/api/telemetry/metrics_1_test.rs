@@ -0,0 +1,86 @@
+#[cfg(test)]
+// AppMetricsのテスト
+mod metrics_test {
+    use crate::api::telemetry::metrics::{AppMetrics, status_class};
+    use axum::http::StatusCode;
+    use std::time::Duration;
+
+    #[test]
+    fn test_status_class() {
+        assert_eq!(status_class(StatusCode::SWITCHING_PROTOCOLS), "1xx");
+        assert_eq!(status_class(StatusCode::CREATED), "2xx");
+        assert_eq!(status_class(StatusCode::NOT_MODIFIED), "3xx");
+        assert_eq!(status_class(StatusCode::NOT_FOUND), "4xx");
+        assert_eq!(status_class(StatusCode::SERVICE_UNAVAILABLE), "5xx");
+    }
+
+    #[test]
+    fn test_observe_http_request() {
+        let metrics = AppMetrics::new().unwrap();
+        metrics.observe_http_request(
+            "GET",
+            "/api/v1/user/{uid}",
+            StatusCode::OK,
+            Duration::from_millis(20),
+        );
+        metrics.observe_http_request(
+            "GET",
+            "/api/v1/user/{uid}",
+            StatusCode::NOT_FOUND,
+            Duration::from_millis(5),
+        );
+        // ルートに一致しない場合は生のURIを使用しない
+        metrics.observe_http_request("GET", "", StatusCode::NOT_FOUND, Duration::from_millis(1));
+
+        let body = metrics.encode().unwrap();
+        assert!(body.contains(
+            "http_requests_total{method=\"GET\",route=\"/api/v1/user/{uid}\",status=\"2xx\"} 1"
+        ));
+        assert!(body.contains(
+            "http_requests_total{method=\"GET\",route=\"/api/v1/user/{uid}\",status=\"4xx\"} 1"
+        ));
+        assert!(
+            body.contains(
+                "http_requests_total{method=\"GET\",route=\"unmatched\",status=\"4xx\"} 1"
+            )
+        );
+        assert!(body.contains(
+            "http_request_duration_seconds_bucket{method=\"GET\",route=\"/api/v1/user/{uid}\",status=\"2xx\",le=\"0.025\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_track_in_flight() {
+        let metrics = AppMetrics::new().unwrap();
+
+        let first = metrics.track_in_flight();
+        let second = metrics.track_in_flight();
+        assert!(
+            metrics
+                .encode()
+                .unwrap()
+                .contains("http_requests_in_flight 2")
+        );
+
+        drop(first);
+        drop(second);
+        assert!(
+            metrics
+                .encode()
+                .unwrap()
+                .contains("http_requests_in_flight 0")
+        );
+    }
+
+    #[test]
+    fn test_users_counters() {
+        let metrics = AppMetrics::new().unwrap();
+        metrics.inc_users_created();
+        metrics.inc_users_created();
+        metrics.inc_users_deleted();
+
+        let body = metrics.encode().unwrap();
+        assert!(body.contains("users_created_total 2"));
+        assert!(body.contains("users_deleted_total 1"));
+    }
+}
@@ -0,0 +1,6 @@
+pub mod metrics;
+pub mod otel;
+
+// テストコード用のモジュール
+mod metrics_1_test;
+mod otel_1_test;
@@ -0,0 +1,362 @@
+// OpenTelemetry
+use opentelemetry::{
+    KeyValue, global,
+    metrics::Histogram,
+    trace::{Span as _, SpanKind, Status, TraceContextExt, Tracer as _, TracerProvider as _},
+};
+use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+use opentelemetry_otlp::{MetricExporter, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    Resource,
+    metrics::{PeriodicReader, SdkMeterProvider},
+    propagation::TraceContextPropagator,
+    runtime,
+    trace::{Sampler, Tracer, TracerProvider},
+};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+
+// tracing
+use tracing::{Instrument, Subscriber};
+use tracing_subscriber::{Layer, filter::FilterFn, registry::LookupSpan};
+
+// axum
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+
+// 標準ライブラリ
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+// configsモジュール
+use crate::api::configs::config::Config;
+
+// リクエストID用のモジュール
+use crate::api::contexts::request_id::{current_request_id, request_id_setting};
+
+// OTLPのプロトコル
+pub const OTLP_PROTOCOL_GRPC: &str = "grpc";
+pub const OTLP_PROTOCOL_HTTP: &str = "http/protobuf";
+
+// プロトコルごとのエンドポイントの既定値
+const DEFAULT_GRPC_ENDPOINT: &str = "http://localhost:4317";
+const DEFAULT_HTTP_ENDPOINT: &str = "http://localhost:4318";
+
+// 計装ライブラリ名
+const INSTRUMENTATION_NAME: &str = "rust_api";
+
+// エクスポーター自身の通信をトレースしないよう除外するターゲット
+const EXCLUDED_TARGETS: [&str; 6] = ["h2", "hyper", "tonic", "tower", "reqwest", "opentelemetry"];
+
+// OpenTelemetryの設定
+#[derive(Clone, Debug)]
+pub struct OtelSetting {
+    pub service_name: String,
+    pub protocol: String,
+    pub endpoint: String,
+    pub sampler: String,
+    pub sampler_arg: f64,
+    pub metric_export_interval: Duration,
+}
+
+impl OtelSetting {
+    // 環境変数から設定を作成（無効な場合はNone）
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if !config.otel_enabled {
+            return None;
+        }
+        Some(OtelSetting {
+            service_name: config.otel_service_name.clone(),
+            protocol: config.otel_exporter_otlp_protocol.clone(),
+            endpoint: config
+                .otel_exporter_otlp_endpoint
+                .as_ref()
+                .map(|url| url.to_string())
+                .unwrap_or_default(),
+            sampler: config.otel_traces_sampler.clone(),
+            sampler_arg: config.otel_traces_sampler_arg,
+            metric_export_interval: config.otel_metric_export_interval,
+        })
+    }
+
+    // エンドポイント（未設定の場合はプロトコルごとの既定値）
+    fn base_endpoint(&self) -> &str {
+        match (self.endpoint.is_empty(), self.protocol.as_str()) {
+            (false, _) => self.endpoint.trim_end_matches('/'),
+            (true, OTLP_PROTOCOL_HTTP) => DEFAULT_HTTP_ENDPOINT,
+            (true, _) => DEFAULT_GRPC_ENDPOINT,
+        }
+    }
+
+    fn resource(&self) -> Resource {
+        Resource::new_with_defaults([KeyValue::new("service.name", self.service_name.clone())])
+    }
+}
+
+// サンプラーを作成する関数（OTEL_TRACES_SAMPLERと同じ名前を指定）
+pub fn build_sampler(name: &str, arg: f64) -> Result<Sampler, String> {
+    if !(0.0..=1.0).contains(&arg) {
+        return Err(format!(
+            "サンプリング率は0.0〜1.0で指定してください: {}",
+            arg
+        ));
+    }
+    let sampler = match name {
+        "always_on" => Sampler::AlwaysOn,
+        "always_off" => Sampler::AlwaysOff,
+        "traceidratio" => Sampler::TraceIdRatioBased(arg),
+        "parentbased_always_on" => Sampler::ParentBased(Box::new(Sampler::AlwaysOn)),
+        "parentbased_always_off" => Sampler::ParentBased(Box::new(Sampler::AlwaysOff)),
+        "parentbased_traceidratio" => {
+            Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(arg)))
+        }
+        _ => return Err(format!("不正なサンプラーです: {}", name)),
+    };
+    Ok(sampler)
+}
+
+// トレースのプロバイダーを作成する関数
+pub fn build_tracer_provider(setting: &OtelSetting) -> Result<TracerProvider, String> {
+    let sampler = build_sampler(&setting.sampler, setting.sampler_arg)?;
+    let exporter = match setting.protocol.as_str() {
+        OTLP_PROTOCOL_GRPC => SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(setting.base_endpoint())
+            .build(),
+        OTLP_PROTOCOL_HTTP => SpanExporter::builder()
+            .with_http()
+            .with_endpoint(format!("{}/v1/traces", setting.base_endpoint()))
+            .build(),
+        protocol => return Err(format!("不正なOTLPのプロトコルです: {}", protocol)),
+    }
+    .map_err(|err| format!("トレースのエクスポーター作成エラー: {}", err))?;
+
+    Ok(TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_sampler(sampler)
+        .with_resource(setting.resource())
+        .build())
+}
+
+// メトリクスのプロバイダーを作成する関数
+pub fn build_meter_provider(setting: &OtelSetting) -> Result<SdkMeterProvider, String> {
+    let exporter = match setting.protocol.as_str() {
+        OTLP_PROTOCOL_GRPC => MetricExporter::builder()
+            .with_tonic()
+            .with_endpoint(setting.base_endpoint())
+            .build(),
+        OTLP_PROTOCOL_HTTP => MetricExporter::builder()
+            .with_http()
+            .with_endpoint(format!("{}/v1/metrics", setting.base_endpoint()))
+            .build(),
+        protocol => return Err(format!("不正なOTLPのプロトコルです: {}", protocol)),
+    }
+    .map_err(|err| format!("メトリクスのエクスポーター作成エラー: {}", err))?;
+
+    let reader = PeriodicReader::builder(exporter, runtime::Tokio)
+        .with_interval(setting.metric_export_interval)
+        .build();
+    Ok(SdkMeterProvider::builder()
+        .with_reader(reader)
+        .with_resource(setting.resource())
+        .build())
+}
+
+// 初期化したプロバイダー（終了時に未送信のデータを送信する）
+pub struct Telemetry {
+    tracer_provider: TracerProvider,
+    meter_provider: SdkMeterProvider,
+}
+
+impl Telemetry {
+    pub fn shutdown(&self) {
+        if let Err(err) = self.tracer_provider.shutdown() {
+            println!("トレースのプロバイダー終了エラー: {}", err);
+        }
+        if let Err(err) = self.meter_provider.shutdown() {
+            println!("メトリクスのプロバイダー終了エラー: {}", err);
+        }
+    }
+}
+
+// トレーサーとメトリクスの計測器（プロセス内で共有、無効な場合は未設定）
+static TRACER: OnceLock<Tracer> = OnceLock::new();
+static HTTP_SERVER_DURATION: OnceLock<Histogram<f64>> = OnceLock::new();
+
+// OpenTelemetryの初期化用関数（ロガーより先に呼び出す）
+pub fn init_telemetry(config: &Config) -> Option<Telemetry> {
+    let setting = OtelSetting::from_config(config)?;
+
+    let providers = build_tracer_provider(&setting)
+        .and_then(|tracer| build_meter_provider(&setting).map(|meter| (tracer, meter)));
+    let (tracer_provider, meter_provider) = match providers {
+        Ok(providers) => providers,
+        Err(err) => {
+            println!("OpenTelemetryの初期化エラー: {}", err);
+            return None;
+        }
+    };
+
+    // W3C Trace Context（traceparent/tracestate）で伝播する
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(tracer_provider.clone());
+    global::set_meter_provider(meter_provider.clone());
+
+    let _ = TRACER.set(tracer_provider.tracer(INSTRUMENTATION_NAME));
+    let _ = HTTP_SERVER_DURATION.set(
+        global::meter(INSTRUMENTATION_NAME)
+            .f64_histogram("http.server.request.duration")
+            .with_unit("s")
+            .with_description("Duration of HTTP server requests.")
+            .build(),
+    );
+
+    Some(Telemetry {
+        tracer_provider,
+        meter_provider,
+    })
+}
+
+// tracingのspanをOpenTelemetryのspanとして送信するレイヤーを作成する関数
+pub fn layer_with_tracer<S>(tracer: Tracer) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let layer: OpenTelemetryLayer<S, Tracer> = tracing_opentelemetry::layer().with_tracer(tracer);
+    layer.with_filter(FilterFn::new(|metadata| {
+        !EXCLUDED_TARGETS
+            .iter()
+            .any(|target| metadata.target().starts_with(target))
+    }))
+}
+
+// ロガーに追加するレイヤー（無効な場合はNone）
+pub fn otel_layer<S>() -> Option<impl Layer<S>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    TRACER.get().map(|tracer| layer_with_tracer(tracer.clone()))
+}
+
+// リクエストヘッダーのtraceparent/tracestateをspanの親に設定する関数
+pub fn set_parent_from_headers(span: &tracing::Span, headers: &HeaderMap) {
+    let parent =
+        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    span.set_parent(parent);
+}
+
+// spanのトレースIDを返す関数（OpenTelemetryが無効な場合はNone）
+pub fn trace_id(span: &tracing::Span) -> Option<String> {
+    let context = span.context();
+    let span_context = context.span().span_context().clone();
+    if span_context.is_valid() {
+        Some(span_context.trace_id().to_string())
+    } else {
+        None
+    }
+}
+
+// spanのコンテキストをリクエストヘッダーのtraceparent/tracestateに設定する関数
+pub fn inject_headers(span: &tracing::Span, headers: &mut HeaderMap) {
+    let context = span.context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(headers))
+    });
+}
+
+// 外部へのHTTPリクエストをクライアントのspanで計測して送信する関数（処理中のリクエストIDも転送する）
+// URLのユーザー情報やクエリにトークンを含む場合があるため、spanにはスキーム・ホスト・ポート・パスのみ記録する
+pub async fn send_traced(
+    builder: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let (client, request) = builder.build_split();
+    let mut request = request?;
+
+    let span = tracing::info_span!(
+        "http.client",
+        otel.name = request.method().as_str(),
+        otel.kind = "client",
+        otel.status_code = tracing::field::Empty,
+        http.request.method = request.method().as_str(),
+        url.scheme = request.url().scheme(),
+        server.address = request.url().host_str().unwrap_or_default(),
+        server.port = request.url().port_or_known_default(),
+        url.path = request.url().path(),
+        http.response.status_code = tracing::field::Empty,
+    );
+    inject_headers(&span, request.headers_mut());
+    if let Some(request_id) = current_request_id() {
+        let setting = request_id_setting();
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(setting.header_name.as_bytes()),
+            HeaderValue::from_str(&request_id),
+        ) {
+            request.headers_mut().insert(name, value);
+        }
+    }
+
+    let result = client.execute(request).instrument(span.clone()).await;
+    match &result {
+        Ok(res) => {
+            span.record("http.response.status_code", res.status().as_u16());
+            if res.status().is_client_error() || res.status().is_server_error() {
+                span.record("otel.status_code", "ERROR");
+            }
+        }
+        Err(_) => {
+            span.record("otel.status_code", "ERROR");
+        }
+    }
+    result
+}
+
+// SeaORMのクエリ実行後に呼び出され、DBのspanを作成する関数
+pub fn record_db_query(info: &sea_orm::metric::Info<'_>) {
+    let Some(tracer) = TRACER.get() else {
+        return;
+    };
+
+    let end_time = SystemTime::now();
+    let start_time = end_time.checked_sub(info.elapsed).unwrap_or(end_time);
+    let sql = info.statement.sql.clone();
+    let operation = sql
+        .split_whitespace()
+        .next()
+        .unwrap_or("query")
+        .to_uppercase();
+
+    // 実行中の処理（リポジトリのspan）を親にする
+    let parent = tracing::Span::current().context();
+    let mut span = tracer
+        .span_builder(operation.clone())
+        .with_kind(SpanKind::Client)
+        .with_start_time(start_time)
+        .with_attributes([
+            KeyValue::new("db.system", "postgresql"),
+            KeyValue::new("db.operation.name", operation),
+            // パラメーターはバインド変数のため値は含まれない
+            KeyValue::new("db.query.text", sql),
+        ])
+        .start_with_context(tracer, &parent);
+    if info.failed {
+        span.set_status(Status::error("query failed"));
+    }
+    span.end_with_timestamp(end_time);
+}
+
+// HTTPリクエストの処理時間を記録する関数
+pub fn record_http_server_duration(
+    method: &str,
+    route: &str,
+    status: StatusCode,
+    elapsed: Duration,
+) {
+    if let Some(histogram) = HTTP_SERVER_DURATION.get() {
+        histogram.record(
+            elapsed.as_secs_f64(),
+            &[
+                KeyValue::new("http.request.method", method.to_string()),
+                KeyValue::new("http.route", route.to_string()),
+                KeyValue::new("http.response.status_code", i64::from(status.as_u16())),
+            ],
+        );
+    }
+}
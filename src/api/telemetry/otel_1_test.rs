@@ -0,0 +1,363 @@
+#[cfg(test)]
+// OpenTelemetryのテスト（プロセス内の疑似コレクターで受信内容を確認）
+mod otel_test {
+    use crate::api::telemetry::otel::{
+        OTLP_PROTOCOL_GRPC, OTLP_PROTOCOL_HTTP, OtelSetting, build_meter_provider, build_sampler,
+        build_tracer_provider, layer_with_tracer, send_traced, set_parent_from_headers, trace_id,
+    };
+    use axum::{
+        Router,
+        body::Bytes,
+        http::{HeaderMap, StatusCode},
+        routing::{get, post},
+    };
+    use opentelemetry::{global, metrics::MeterProvider as _, trace::TracerProvider as _};
+    use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+    use opentelemetry_proto::tonic::collector::trace::v1::{
+        ExportTraceServiceRequest, ExportTraceServiceResponse,
+        trace_service_server::{TraceService, TraceServiceServer},
+    };
+    use opentelemetry_proto::tonic::trace::v1::span::SpanKind;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::{Sampler, TracerProvider};
+    use prost::Message;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tracing::Instrument;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    // 呼び出し元から渡されるtraceparent
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const PARENT_SPAN_ID: &str = "00f067aa0ba902b7";
+
+    // 疑似コレクターが受信した内容
+    #[derive(Clone, Default)]
+    struct Collected {
+        traces: Arc<Mutex<Vec<ExportTraceServiceRequest>>>,
+        metrics: Arc<Mutex<Vec<ExportMetricsServiceRequest>>>,
+        headers: Arc<Mutex<Vec<HeaderMap>>>,
+    }
+
+    // 受信したspan（名前、トレースID、親のspanID、種類）
+    struct CollectedSpan {
+        name: String,
+        trace_id: String,
+        parent_span_id: String,
+        kind: i32,
+        // 属性（値の確認用にDebug形式の文字列にする）
+        attributes: String,
+    }
+
+    impl Collected {
+        fn spans(&self) -> Vec<CollectedSpan> {
+            let traces = self.traces.lock().unwrap();
+            traces
+                .iter()
+                .flat_map(|request| request.resource_spans.iter())
+                .flat_map(|resource_spans| resource_spans.scope_spans.iter())
+                .flat_map(|scope_spans| scope_spans.spans.iter())
+                .map(|span| CollectedSpan {
+                    name: span.name.clone(),
+                    trace_id: hex::encode(&span.trace_id),
+                    parent_span_id: hex::encode(&span.parent_span_id),
+                    kind: span.kind,
+                    attributes: format!("{:?}", span.attributes),
+                })
+                .collect()
+        }
+
+        fn metric_names(&self) -> Vec<String> {
+            let metrics = self.metrics.lock().unwrap();
+            metrics
+                .iter()
+                .flat_map(|request| request.resource_metrics.iter())
+                .flat_map(|resource_metrics| resource_metrics.scope_metrics.iter())
+                .flat_map(|scope_metrics| scope_metrics.metrics.iter())
+                .map(|metric| metric.name.clone())
+                .collect()
+        }
+    }
+
+    // OTLP/HTTPの疑似コレクター（外部APIの呼び出し先も兼ねる）を起動
+    async fn start_http_collector(collected: Collected) -> String {
+        let traces = collected.clone();
+        let metrics = collected.clone();
+        let headers = collected.clone();
+        let app = Router::new()
+            .route(
+                "/v1/traces",
+                post(move |body: Bytes| async move {
+                    let request = ExportTraceServiceRequest::decode(body).unwrap();
+                    traces.traces.lock().unwrap().push(request);
+                    StatusCode::OK
+                }),
+            )
+            .route(
+                "/v1/metrics",
+                post(move |body: Bytes| async move {
+                    let request = ExportMetricsServiceRequest::decode(body).unwrap();
+                    metrics.metrics.lock().unwrap().push(request);
+                    StatusCode::OK
+                }),
+            )
+            .route(
+                "/echo",
+                get(move |header_map: HeaderMap| async move {
+                    headers.headers.lock().unwrap().push(header_map);
+                    StatusCode::OK
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    // OTLP/gRPCの疑似コレクター
+    struct FakeTraceService(Collected);
+
+    #[tonic::async_trait]
+    impl TraceService for FakeTraceService {
+        async fn export(
+            &self,
+            request: tonic::Request<ExportTraceServiceRequest>,
+        ) -> Result<tonic::Response<ExportTraceServiceResponse>, tonic::Status> {
+            self.0.traces.lock().unwrap().push(request.into_inner());
+            Ok(tonic::Response::new(ExportTraceServiceResponse {
+                partial_success: None,
+            }))
+        }
+    }
+
+    async fn start_grpc_collector(collected: Collected) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming =
+            tonic::transport::server::TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(TraceServiceServer::new(FakeTraceService(collected)))
+                .serve_with_incoming(incoming)
+                .await
+                .unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    fn setting(protocol: &str, endpoint: &str) -> OtelSetting {
+        OtelSetting {
+            service_name: "rust_api_test".to_string(),
+            protocol: protocol.to_string(),
+            endpoint: endpoint.to_string(),
+            sampler: "parentbased_always_on".to_string(),
+            sampler_arg: 1.0,
+            metric_export_interval: Duration::from_secs(60),
+        }
+    }
+
+    // 未送信のspanを送信（バッチ処理の完了を待つためブロッキングで実行）
+    async fn flush(provider: &TracerProvider) {
+        let provider = provider.clone();
+        tokio::task::spawn_blocking(move || provider.force_flush())
+            .await
+            .unwrap();
+    }
+
+    // 疑似コレクターがspanを受信するまで待機
+    async fn wait_for_spans(collected: &Collected, count: usize) -> Vec<CollectedSpan> {
+        for _ in 0..100 {
+            let spans = collected.spans();
+            if spans.len() >= count {
+                return spans;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        collected.spans()
+    }
+
+    // traceparentヘッダーの作成
+    fn traceparent_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            format!("00-{}-{}-01", TRACE_ID, PARENT_SPAN_ID)
+                .parse()
+                .unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_build_sampler() {
+        assert!(matches!(
+            build_sampler("always_on", 1.0),
+            Ok(Sampler::AlwaysOn)
+        ));
+        assert!(matches!(
+            build_sampler("always_off", 1.0),
+            Ok(Sampler::AlwaysOff)
+        ));
+        assert!(matches!(
+            build_sampler("traceidratio", 0.25),
+            Ok(Sampler::TraceIdRatioBased(arg)) if arg == 0.25
+        ));
+        assert!(matches!(
+            build_sampler("parentbased_traceidratio", 0.5),
+            Ok(Sampler::ParentBased(_))
+        ));
+        assert!(build_sampler("unknown", 1.0).is_err());
+        assert!(build_sampler("traceidratio", 1.5).is_err());
+    }
+
+    #[test]
+    fn test_build_tracer_provider_invalid_protocol() {
+        let result = build_tracer_provider(&setting("udp", ""));
+        assert!(result.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_export_http_with_propagation() {
+        let collected = Collected::default();
+        let endpoint = start_http_collector(collected.clone()).await;
+
+        let provider = build_tracer_provider(&setting(OTLP_PROTOCOL_HTTP, &endpoint)).unwrap();
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let subscriber =
+            tracing_subscriber::registry().with(layer_with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        // 受信したリクエストのtraceparentを引き継ぐ
+        let span = tracing::info_span!(
+            "request",
+            otel.name = "GET /api/v1/users",
+            otel.kind = "server"
+        );
+        set_parent_from_headers(&span, &traceparent_headers());
+        assert_eq!(trace_id(&span), Some(TRACE_ID.to_string()));
+
+        // 外部APIの呼び出し時にtraceparentが伝播される
+        let url = format!(
+            "{}/echo?token=query-secret",
+            endpoint.replace("://", "://user:userinfo-secret@")
+        );
+        let res = send_traced(reqwest::Client::new().get(url))
+            .instrument(span.clone())
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        drop(span);
+
+        let headers = collected.headers.lock().unwrap().clone();
+        let traceparent = headers[0].get("traceparent").unwrap().to_str().unwrap();
+        assert!(traceparent.starts_with(&format!("00-{}-", TRACE_ID)));
+        assert!(!traceparent.contains(PARENT_SPAN_ID));
+
+        flush(&provider).await;
+        let spans = wait_for_spans(&collected, 2).await;
+
+        let server = spans
+            .iter()
+            .find(|span| span.name == "GET /api/v1/users")
+            .unwrap();
+        assert_eq!(server.trace_id, TRACE_ID);
+        assert_eq!(server.parent_span_id, PARENT_SPAN_ID);
+        assert_eq!(server.kind, SpanKind::Server as i32);
+
+        let client = spans.iter().find(|span| span.name == "GET").unwrap();
+        assert_eq!(client.trace_id, TRACE_ID);
+        assert_eq!(client.kind, SpanKind::Client as i32);
+
+        // URLのユーザー情報やクエリはspanに記録しない
+        assert!(client.attributes.contains("/echo"));
+        assert!(!client.attributes.contains("query-secret"));
+        assert!(!client.attributes.contains("userinfo-secret"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_export_grpc() {
+        let collected = Collected::default();
+        let endpoint = start_grpc_collector(collected.clone()).await;
+
+        let provider = build_tracer_provider(&setting(OTLP_PROTOCOL_GRPC, &endpoint)).unwrap();
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let subscriber =
+            tracing_subscriber::registry().with(layer_with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let span = tracing::info_span!("request", otel.name = "POST /api/v1/user");
+        set_parent_from_headers(&span, &traceparent_headers());
+        span.in_scope(|| {
+            let _child = tracing::info_span!("UsersRepository.create_user").entered();
+        });
+        drop(span);
+
+        flush(&provider).await;
+        let spans = wait_for_spans(&collected, 2).await;
+
+        assert!(
+            spans
+                .iter()
+                .any(|span| span.name == "POST /api/v1/user" && span.trace_id == TRACE_ID)
+        );
+        assert!(
+            spans
+                .iter()
+                .any(|span| span.name == "UsersRepository.create_user"
+                    && span.trace_id == TRACE_ID)
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_export_sampler_always_off() {
+        let collected = Collected::default();
+        let endpoint = start_http_collector(collected.clone()).await;
+
+        let mut setting = setting(OTLP_PROTOCOL_HTTP, &endpoint);
+        setting.sampler = "always_off".to_string();
+        let provider = build_tracer_provider(&setting).unwrap();
+        let subscriber =
+            tracing_subscriber::registry().with(layer_with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        drop(tracing::info_span!(
+            "request",
+            otel.name = "GET /api/v1/users"
+        ));
+
+        flush(&provider).await;
+        assert!(wait_for_spans(&collected, 1).await.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_export_metrics_http() {
+        let collected = Collected::default();
+        let endpoint = start_http_collector(collected.clone()).await;
+
+        let provider = build_meter_provider(&setting(OTLP_PROTOCOL_HTTP, &endpoint)).unwrap();
+        let histogram = provider
+            .meter("test")
+            .f64_histogram("http.server.request.duration")
+            .build();
+        histogram.record(0.01, &[]);
+
+        let flush_provider = provider.clone();
+        tokio::task::spawn_blocking(move || flush_provider.force_flush())
+            .await
+            .unwrap()
+            .unwrap();
+
+        for _ in 0..100 {
+            if !collected.metric_names().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(
+            collected
+                .metric_names()
+                .contains(&"http.server.request.duration".to_string())
+        );
+    }
+}
@@ -1,2 +1,4 @@
 pub mod sample;
 pub mod users;
+pub mod webhooks;
+pub mod ws;
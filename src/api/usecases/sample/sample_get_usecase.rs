@@ -27,6 +27,7 @@ pub struct SampleGetUsecase {
 }
 
 impl SampleGetUsecase {
+    #[tracing::instrument(name = "SampleGetUsecase.exec", skip_all)]
     pub async fn exec(&self, ctx: Context) -> Response {
         // サンプルテキストを取得するサービスを実行
         let text = match self
@@ -56,11 +57,7 @@ impl SampleGetUsecase {
         // json形式のメッセージを設定
         let msg = Json(json!({ "message": text}));
 
-        // レスポンスヘッダーに付与する値の設定
-        let x_request_id = ctx.header.get("X-Request-Id");
-        let request_id = x_request_id.expect("-").to_str().unwrap();
-
         // レスポンス結果を設定して戻り値として返す
-        (StatusCode::OK, [("X-Request-Id", request_id)], msg).into_response()
+        (StatusCode::OK, msg).into_response()
     }
 }
@@ -22,6 +22,9 @@ use crate::api::handlers::users::users_handler::CreateUserRequestBody;
 // サービスのモジュール
 use crate::api::services::users::users_service::{UsersService, UsersServiceTrait};
 
+// メトリクス用のモジュール
+use crate::api::telemetry::metrics::app_metrics;
+
 // 使用するサービスをまとめる構造体
 pub struct CreateUserCommonService {
     pub users_service: UsersService,
@@ -33,15 +36,11 @@ pub struct CreateUserUsecase {
 }
 
 impl CreateUserUsecase {
+    #[tracing::instrument(name = "CreateUserUsecase.exec", skip_all)]
     pub async fn exec(&self, ctx: Context, body: CreateUserRequestBody) -> Response {
         // Uidの設定
         let uid = Uuid::new_v4().to_string();
 
-        // レスポンスヘッダーに付与する値の設定
-        let x_request_id = ctx.header.get("X-Request-Id");
-        let request_id = x_request_id.expect("-").to_str().unwrap();
-        let res_header = [("X-Request-Id", request_id)];
-
         // ユーザー作成処理
         let user = match self
             .service
@@ -61,17 +60,20 @@ impl CreateUserUsecase {
                 };
 
                 // レスポンス結果の設定
-                let res = (status_code, res_header, msg).into_response();
+                let res = (status_code, msg).into_response();
 
                 // 戻り値としてレスポンス結果を返す
                 return res;
             }
         };
 
+        // 作成したユーザー数を記録
+        app_metrics().inc_users_created();
+
         // レスポンスボディの設定
         let res_body = Json(json!(user));
 
         // レスポンス結果を設定して戻り値として返す
-        (StatusCode::CREATED, res_header, res_body).into_response()
+        (StatusCode::CREATED, res_body).into_response()
     }
 }
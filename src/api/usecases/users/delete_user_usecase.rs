@@ -16,6 +16,9 @@ use crate::api::errors::error::CommonError;
 // サービスのモジュール
 use crate::api::services::users::users_service::{UsersService, UsersServiceTrait};
 
+// メトリクス用のモジュール
+use crate::api::telemetry::metrics::app_metrics;
+
 // 使用するサービスをまとめる構造体
 pub struct DeleteUserCommonService {
     pub users_service: UsersService,
@@ -27,20 +30,19 @@ pub struct DeleteUserUsecase {
 }
 
 impl DeleteUserUsecase {
+    #[tracing::instrument(name = "DeleteUserUsecase.exec", skip_all, fields(uid = %uid))]
     pub async fn exec(&self, ctx: Context, uid: String) -> Response {
-        // レスポンスヘッダーに付与する値の設定
-        let x_request_id = ctx.header.get("X-Request-Id");
-        let request_id = x_request_id.expect("-").to_str().unwrap();
-        let res_header = [("X-Request-Id", request_id)];
-
         // 対象ユーザー削除処理
         match self.service.users_service.delete_user(&ctx, uid).await {
             Ok(_user) => {
+                // 削除したユーザー数を記録
+                app_metrics().inc_users_deleted();
+
                 // json形式のメッセージを設定
                 let msg = Json(json!({ "message": "OK".to_string()}));
 
                 // レスポンス結果を設定して戻り値として返す
-                (StatusCode::OK, res_header, msg).into_response()
+                (StatusCode::OK, msg).into_response()
             }
             Err(err) => {
                 // json形式のメッセージを設定
@@ -53,7 +55,7 @@ impl DeleteUserUsecase {
                 };
 
                 // レスポンス結果を設定して戻り値として返す
-                (status_code, res_header, msg).into_response()
+                (status_code, msg).into_response()
             }
         }
     }
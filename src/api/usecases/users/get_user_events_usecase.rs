@@ -0,0 +1,125 @@
+// axum
+use axum::{
+    http::StatusCode,
+    response::{
+        IntoResponse, Json, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+
+// 非同期ストリーム作成用のマクロ
+use async_stream::stream;
+
+// json変換用マクロ
+use serde_json::json;
+
+// 配信用のチャンネル
+use tokio::sync::broadcast::error::RecvError;
+
+// 標準ライブラリ
+use std::convert::Infallible;
+use std::time::Duration;
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// ロガー用のモジュール
+use crate::api::loggers::logger::{error, info};
+
+// ユーザーイベント配信用のモジュール
+use crate::api::events::user_event_stream::{UserEventHub, UserStreamEvent};
+
+// シャットダウン用のモジュール
+use crate::api::lifecycle::shutdown::ShutdownListener;
+
+// 再接続時のイベントID受け取り用ヘッダー
+pub const HEADER_LAST_EVENT_ID: &str = "Last-Event-ID";
+
+// リプレイできない場合に送信するイベント名（クライアントは一覧を再取得する）
+pub const SSE_EVENT_RESET: &str = "reset";
+
+// 実行するユースケースの構造体
+pub struct GetUserEventsUsecase {
+    pub hub: &'static UserEventHub,
+    pub heartbeat_interval: Duration,
+    // シャットダウン時に配信を終了する
+    pub shutdown: ShutdownListener,
+}
+
+// SSEのイベントに変換する関数
+fn to_sse_event(event: &UserStreamEvent) -> Event {
+    Event::default()
+        .id(event.id.to_string())
+        .event(&event.event.event_type)
+        .data(serde_json::to_string(&event.event).unwrap_or_default())
+}
+
+impl GetUserEventsUsecase {
+    #[tracing::instrument(name = "GetUserEventsUsecase.exec", skip_all)]
+    pub async fn exec(&self, ctx: Context) -> Response {
+        // Last-Event-IDの取得
+        let last_event_id = match ctx.header.get(HEADER_LAST_EVENT_ID) {
+            Some(value) => match value.to_str().ok().and_then(|v| v.parse::<i64>().ok()) {
+                Some(id) => Some(id),
+                None => {
+                    let msg = Json(json!({ "message": "Bad Request"}));
+                    return (StatusCode::BAD_REQUEST, msg).into_response();
+                }
+            },
+            None => None,
+        };
+
+        // 購読開始
+        let subscription = self.hub.subscribe(last_event_id);
+        info(
+            &ctx,
+            &format!(
+                "start user events stream (last_event_id={:?} replay={} expired={})",
+                last_event_id,
+                subscription.replay.len(),
+                subscription.expired
+            ),
+        );
+
+        let stream_ctx = ctx.clone();
+        let mut shutdown = self.shutdown.clone();
+        let stream = stream! {
+            let mut receiver = subscription.receiver;
+
+            // 再送できないイベントがある場合はリセットを通知
+            if subscription.expired {
+                yield Ok::<Event, Infallible>(Event::default().event(SSE_EVENT_RESET).data("{}"));
+            }
+
+            // 切断中に発生したイベントを再送
+            for event in subscription.replay.iter() {
+                yield Ok(to_sse_event(event));
+            }
+
+            // ライブ配信
+            loop {
+                let received = tokio::select! {
+                    received = receiver.recv() => received,
+                    // 接続を終了し、クライアントにはLast-Event-IDで再接続させる
+                    _ = shutdown.wait() => break,
+                };
+                match received {
+                    Ok(event) => yield Ok(to_sse_event(&event)),
+                    Err(RecvError::Lagged(skipped)) => {
+                        // 受信が遅いクライアントはイベントを取りこぼすためリセットを通知
+                        error(
+                            &stream_ctx,
+                            &format!("[GetUserEventsUsecase.exec] 配信遅延により{}件のイベントを破棄しました", skipped),
+                        );
+                        yield Ok(Event::default().event(SSE_EVENT_RESET).data("{}"));
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        };
+
+        // ハートビートを設定してレスポンスを返す
+        let sse = Sse::new(stream).keep_alive(KeepAlive::new().interval(self.heartbeat_interval));
+        (StatusCode::OK, sse).into_response()
+    }
+}
@@ -27,12 +27,8 @@ pub struct GetUserFromUidUsecase {
 }
 
 impl GetUserFromUidUsecase {
+    #[tracing::instrument(name = "GetUserFromUidUsecase.exec", skip_all, fields(uid = %uid))]
     pub async fn exec(&self, ctx: Context, uid: String) -> Response {
-        // レスポンスヘッダーに付与する値の設定
-        let x_request_id = ctx.header.get("X-Request-Id");
-        let request_id = x_request_id.expect("-").to_str().unwrap();
-        let res_header = [("X-Request-Id", request_id)];
-
         // Uidから有効な対象ユーザー取得処理
         let user = match self
             .service
@@ -48,7 +44,7 @@ impl GetUserFromUidUsecase {
                         let msg = Json(json!({}));
 
                         // レスポンス結果の設定
-                        let res = (StatusCode::OK, res_header, msg).into_response();
+                        let res = (StatusCode::OK, msg).into_response();
 
                         // 戻り値としてレスポンス結果を返す
                         return res;
@@ -66,7 +62,7 @@ impl GetUserFromUidUsecase {
                 };
 
                 // レスポンス結果の設定
-                let res = (status_code, res_header, msg).into_response();
+                let res = (status_code, msg).into_response();
 
                 // 戻り値としてレスポンス結果を返す
                 return res;
@@ -77,6 +73,6 @@ impl GetUserFromUidUsecase {
         let res_body = Json(json!(user));
 
         // レスポンス結果を設定して戻り値として返す
-        (StatusCode::OK, res_header, res_body).into_response()
+        (StatusCode::OK, res_body).into_response()
     }
 }
@@ -0,0 +1,80 @@
+// axum
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+
+// json変換用マクロ
+use serde_json::json;
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// 共通エラー用モジュール
+use crate::api::errors::error::CommonError;
+
+// クエリパラメータ用の構造体
+use crate::api::handlers::users::users_handler::GetUserHistoryQuery;
+
+// サービスのモジュール
+use crate::api::services::users::users_service::{UsersService, UsersServiceTrait};
+
+// ページネーションのデフォルト値
+const DEFAULT_PAGE: u64 = 1;
+const DEFAULT_PER_PAGE: u64 = 20;
+
+// 使用するサービスをまとめる構造体
+pub struct GetUserHistoryCommonService {
+    pub users_service: UsersService,
+}
+
+// 実行するユースケースの構造体
+pub struct GetUserHistoryUsecase {
+    pub service: GetUserHistoryCommonService,
+}
+
+impl GetUserHistoryUsecase {
+    #[tracing::instrument(name = "GetUserHistoryUsecase.exec", skip_all, fields(uid = %uid))]
+    pub async fn exec(&self, ctx: Context, uid: String, query: GetUserHistoryQuery) -> Response {
+        // クエリパラメータからページネーションの値を取得
+        let page = query.page.unwrap_or(DEFAULT_PAGE);
+        let per_page = query.per_page.unwrap_or(DEFAULT_PER_PAGE);
+
+        // 対象ユーザーの変更履歴取得処理
+        let (audit_logs, total) = match self
+            .service
+            .users_service
+            .get_user_history(&ctx, uid, page, per_page)
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => {
+                // json形式のメッセージを設定
+                let msg = Json(json!({ "message": err.to_string()}));
+
+                // ステータスコードの設定
+                let status_code = match err {
+                    CommonError::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
+                    CommonError::CustomError { status_code, .. } => status_code,
+                };
+
+                // レスポンス結果の設定
+                let res = (status_code, msg).into_response();
+
+                // 戻り値としてレスポンス結果を返す
+                return res;
+            }
+        };
+
+        // レスポンスボディの設定
+        let res_body = Json(json!({
+            "audit_logs": audit_logs,
+            "page": page,
+            "per_page": per_page,
+            "total": total,
+        }));
+
+        // レスポンス結果を設定して戻り値として返す
+        (StatusCode::OK, res_body).into_response()
+    }
+}
@@ -27,12 +27,8 @@ pub struct GetUsersUsecase {
 }
 
 impl GetUsersUsecase {
+    #[tracing::instrument(name = "GetUsersUsecase.exec", skip_all)]
     pub async fn exec(&self, ctx: Context) -> Response {
-        // レスポンスヘッダーに付与する値の設定
-        let x_request_id = ctx.header.get("X-Request-Id");
-        let request_id = x_request_id.expect("-").to_str().unwrap();
-        let res_header = [("X-Request-Id", request_id)];
-
         // 全ての有効なユーザー取得処理
         let users = match self.service.users_service.get_users(&ctx).await {
             Ok(users) => users,
@@ -47,7 +43,7 @@ impl GetUsersUsecase {
                 };
 
                 // レスポンス結果の設定
-                let res = (status_code, res_header, msg).into_response();
+                let res = (status_code, msg).into_response();
 
                 // 戻り値としてレスポンス結果を返す
                 return res;
@@ -58,6 +54,6 @@ impl GetUsersUsecase {
         let res_body = Json(json!(users));
 
         // レスポンス結果を設定して戻り値として返す
-        (StatusCode::OK, res_header, res_body).into_response()
+        (StatusCode::OK, res_body).into_response()
     }
 }
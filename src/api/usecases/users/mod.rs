@@ -1,5 +1,7 @@
 pub mod create_user_usecase;
 pub mod delete_user_usecase;
+pub mod get_user_events_usecase;
 pub mod get_user_from_uid_usecase;
+pub mod get_user_history_usecase;
 pub mod get_users_usecase;
 pub mod update_user_usecase;
@@ -30,12 +30,8 @@ pub struct UpdateUserUsecase {
 }
 
 impl UpdateUserUsecase {
+    #[tracing::instrument(name = "UpdateUserUsecase.exec", skip_all, fields(uid = %uid))]
     pub async fn exec(&self, ctx: Context, uid: String, body: UpdateUserRequestBody) -> Response {
-        // レスポンスヘッダーに付与する値の設定
-        let x_request_id = ctx.header.get("X-Request-Id");
-        let request_id = x_request_id.expect("-").to_str().unwrap();
-        let res_header = [("X-Request-Id", request_id)];
-
         // リクエストボディから更新する値を取得
         let last_name = match body.last_name {
             Some(last_name) => last_name,
@@ -71,7 +67,7 @@ impl UpdateUserUsecase {
                 };
 
                 // レスポンス結果の設定
-                let res = (status_code, res_header, msg).into_response();
+                let res = (status_code, msg).into_response();
 
                 // 戻り値としてレスポンス結果を返す
                 return res;
@@ -82,6 +78,6 @@ impl UpdateUserUsecase {
         let res_body = Json(json!(user));
 
         // レスポンス結果を設定して戻り値として返す
-        (StatusCode::OK, res_header, res_body).into_response()
+        (StatusCode::OK, res_body).into_response()
     }
 }
@@ -0,0 +1,85 @@
+// axum
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+
+// json変換用マクロ
+use serde_json::json;
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// 共通エラー用モジュール
+use crate::api::errors::error::CommonError;
+
+// UUID
+use uuid::Uuid;
+
+// リクエストボディ用の構造体
+use crate::api::handlers::webhooks::webhooks_handler::CreateWebhookRequestBody;
+
+// サービスのモジュール
+use crate::api::services::webhooks::webhooks_service::{WebhooksService, WebhooksServiceTrait};
+
+// 使用するサービスをまとめる構造体
+pub struct CreateWebhookCommonService {
+    pub webhooks_service: WebhooksService,
+}
+
+// 実行するユースケースの構造体
+pub struct CreateWebhookUsecase {
+    pub service: CreateWebhookCommonService,
+}
+
+impl CreateWebhookUsecase {
+    #[tracing::instrument(name = "CreateWebhookUsecase.exec", skip_all)]
+    pub async fn exec(&self, ctx: Context, body: CreateWebhookRequestBody) -> Response {
+        // Uidの設定
+        let uid = Uuid::new_v4().to_string();
+
+        // 署名用のシークレットの設定（未指定の場合は生成する）
+        let secret = match body.secret {
+            Some(secret) => secret,
+            None => format!(
+                "whsec_{}{}",
+                Uuid::new_v4().simple(),
+                Uuid::new_v4().simple()
+            ),
+        };
+
+        // Webhookの購読作成処理
+        let webhook = match self
+            .service
+            .webhooks_service
+            .create_webhook(&ctx, uid, body.url, body.event_types, secret.clone())
+            .await
+        {
+            Ok(webhook) => webhook,
+            Err(err) => {
+                // json形式のメッセージを設定
+                let msg = Json(json!({ "message": err.to_string()}));
+
+                // ステータスコードの設定
+                let status_code = match err {
+                    CommonError::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
+                    CommonError::CustomError { status_code, .. } => status_code,
+                };
+
+                // レスポンス結果の設定
+                let res = (status_code, msg).into_response();
+
+                // 戻り値としてレスポンス結果を返す
+                return res;
+            }
+        };
+
+        // レスポンスボディの設定（シークレットは作成時のみ返す）
+        let mut res_body = json!(webhook);
+        res_body["secret"] = json!(secret);
+        let res_body = Json(res_body);
+
+        // レスポンス結果を設定して戻り値として返す
+        (StatusCode::CREATED, res_body).into_response()
+    }
+}
@@ -0,0 +1,85 @@
+// axum
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+
+// json変換用マクロ
+use serde_json::json;
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// 共通エラー用モジュール
+use crate::api::errors::error::CommonError;
+
+// クエリパラメータ用の構造体
+use crate::api::handlers::webhooks::webhooks_handler::GetWebhookDeliveriesQuery;
+
+// ページネーションのデフォルト値
+const DEFAULT_PAGE: u64 = 1;
+const DEFAULT_PER_PAGE: u64 = 20;
+
+// サービスのモジュール
+use crate::api::services::webhooks::webhooks_service::{WebhooksService, WebhooksServiceTrait};
+
+// 使用するサービスをまとめる構造体
+pub struct GetWebhookDeliveriesCommonService {
+    pub webhooks_service: WebhooksService,
+}
+
+// 実行するユースケースの構造体
+pub struct GetWebhookDeliveriesUsecase {
+    pub service: GetWebhookDeliveriesCommonService,
+}
+
+impl GetWebhookDeliveriesUsecase {
+    #[tracing::instrument(name = "GetWebhookDeliveriesUsecase.exec", skip_all, fields(uid = %uid))]
+    pub async fn exec(
+        &self,
+        ctx: Context,
+        uid: String,
+        query: GetWebhookDeliveriesQuery,
+    ) -> Response {
+        // クエリパラメータからページネーションの値を取得
+        let page = query.page.unwrap_or(DEFAULT_PAGE);
+        let per_page = query.per_page.unwrap_or(DEFAULT_PER_PAGE);
+
+        // Webhookの配信ログ取得処理
+        let (deliveries, total) = match self
+            .service
+            .webhooks_service
+            .get_webhook_deliveries(&ctx, uid, page, per_page)
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => {
+                // json形式のメッセージを設定
+                let msg = Json(json!({ "message": err.to_string()}));
+
+                // ステータスコードの設定
+                let status_code = match err {
+                    CommonError::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
+                    CommonError::CustomError { status_code, .. } => status_code,
+                };
+
+                // レスポンス結果の設定
+                let res = (status_code, msg).into_response();
+
+                // 戻り値としてレスポンス結果を返す
+                return res;
+            }
+        };
+
+        // レスポンスボディの設定
+        let res_body = Json(json!({
+            "deliveries": deliveries,
+            "page": page,
+            "per_page": per_page,
+            "total": total,
+        }));
+
+        // レスポンス結果を設定して戻り値として返す
+        (StatusCode::OK, res_body).into_response()
+    }
+}
@@ -0,0 +1,76 @@
+// axum
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+
+// json変換用マクロ
+use serde_json::json;
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// 共通エラー用モジュール
+use crate::api::errors::error::CommonError;
+
+// サービスのモジュール
+use crate::api::services::webhooks::webhooks_service::{WebhooksService, WebhooksServiceTrait};
+
+// 使用するサービスをまとめる構造体
+pub struct GetWebhookFromUidCommonService {
+    pub webhooks_service: WebhooksService,
+}
+
+// 実行するユースケースの構造体
+pub struct GetWebhookFromUidUsecase {
+    pub service: GetWebhookFromUidCommonService,
+}
+
+impl GetWebhookFromUidUsecase {
+    #[tracing::instrument(name = "GetWebhookFromUidUsecase.exec", skip_all, fields(uid = %uid))]
+    pub async fn exec(&self, ctx: Context, uid: String) -> Response {
+        // Uidから有効なWebhookの購読取得処理
+        let webhook = match self
+            .service
+            .webhooks_service
+            .get_webhook_from_uid(&ctx, uid)
+            .await
+        {
+            Ok(webhook) => match webhook {
+                Some(webhook) => webhook,
+                None => {
+                    // json形式のメッセージを設定
+                    let msg = Json(json!({ "message": "Not Found"}));
+
+                    // レスポンス結果の設定
+                    let res = (StatusCode::NOT_FOUND, msg).into_response();
+
+                    // 戻り値としてレスポンス結果を返す
+                    return res;
+                }
+            },
+            Err(err) => {
+                // json形式のメッセージを設定
+                let msg = Json(json!({ "message": err.to_string()}));
+
+                // ステータスコードの設定
+                let status_code = match err {
+                    CommonError::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
+                    CommonError::CustomError { status_code, .. } => status_code,
+                };
+
+                // レスポンス結果の設定
+                let res = (status_code, msg).into_response();
+
+                // 戻り値としてレスポンス結果を返す
+                return res;
+            }
+        };
+
+        // レスポンスボディの設定
+        let res_body = Json(json!(webhook));
+
+        // レスポンス結果を設定して戻り値として返す
+        (StatusCode::OK, res_body).into_response()
+    }
+}
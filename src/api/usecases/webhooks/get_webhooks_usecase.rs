@@ -0,0 +1,59 @@
+// axum
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+
+// json変換用マクロ
+use serde_json::json;
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// 共通エラー用モジュール
+use crate::api::errors::error::CommonError;
+
+// サービスのモジュール
+use crate::api::services::webhooks::webhooks_service::{WebhooksService, WebhooksServiceTrait};
+
+// 使用するサービスをまとめる構造体
+pub struct GetWebhooksCommonService {
+    pub webhooks_service: WebhooksService,
+}
+
+// 実行するユースケースの構造体
+pub struct GetWebhooksUsecase {
+    pub service: GetWebhooksCommonService,
+}
+
+impl GetWebhooksUsecase {
+    #[tracing::instrument(name = "GetWebhooksUsecase.exec", skip_all)]
+    pub async fn exec(&self, ctx: Context) -> Response {
+        // 全ての有効なWebhookの購読取得処理
+        let webhooks = match self.service.webhooks_service.get_webhooks(&ctx).await {
+            Ok(webhooks) => webhooks,
+            Err(err) => {
+                // json形式のメッセージを設定
+                let msg = Json(json!({ "message": err.to_string()}));
+
+                // ステータスコードの設定
+                let status_code = match err {
+                    CommonError::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
+                    CommonError::CustomError { status_code, .. } => status_code,
+                };
+
+                // レスポンス結果の設定
+                let res = (status_code, msg).into_response();
+
+                // 戻り値としてレスポンス結果を返す
+                return res;
+            }
+        };
+
+        // レスポンスボディの設定
+        let res_body = Json(json!(webhooks));
+
+        // レスポンス結果を設定して戻り値として返す
+        (StatusCode::OK, res_body).into_response()
+    }
+}
@@ -0,0 +1,6 @@
+pub mod create_webhook_usecase;
+pub mod delete_webhook_usecase;
+pub mod get_webhook_deliveries_usecase;
+pub mod get_webhook_from_uid_usecase;
+pub mod get_webhooks_usecase;
+pub mod send_test_webhook_usecase;
@@ -0,0 +1,4 @@
+pub mod ws_session_usecase;
+
+// テストコード用のモジュール
+mod ws_session_usecase_1_test;
@@ -0,0 +1,399 @@
+// axum
+use axum::extract::ws::{CloseFrame, Message, WebSocket, close_code};
+
+// ストリーム操作用のトレイト
+use futures_util::{SinkExt, StreamExt};
+
+// 変換用のクレート
+use serde::{Deserialize, Serialize};
+
+// 配信用のチャンネル
+use tokio::sync::{broadcast::error::RecvError, mpsc, oneshot};
+
+// 標準ライブラリ
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+// configsモジュール
+use crate::api::configs::config::Config;
+
+// 共通コンテキストの構造体
+use crate::api::contexts::context::Context;
+
+// ロガー用のモジュール
+use crate::api::loggers::logger::{error, info, warn};
+
+// CloudEvents用のモジュール
+use crate::api::events::cloud_event::CloudEvent;
+
+// ユーザーイベント配信用のモジュール
+use crate::api::events::user_event_stream::{UserEventHub, UserStreamEvent};
+
+// シャットダウン用のモジュール
+use crate::api::lifecycle::shutdown::ShutdownListener;
+
+// 全ユーザーのイベントを購読するトピック
+pub const WS_TOPIC_USERS: &str = "users";
+
+// 対象ユーザーのイベントを購読するトピックの接頭辞（user:{uid}）
+pub const WS_TOPIC_USER_PREFIX: &str = "user:";
+
+// エラーコード
+pub const WS_ERROR_INVALID_MESSAGE: &str = "invalid_message";
+pub const WS_ERROR_INVALID_TOPIC: &str = "invalid_topic";
+pub const WS_ERROR_TOO_MANY_SUBSCRIPTIONS: &str = "too_many_subscriptions";
+pub const WS_ERROR_RATE_LIMITED: &str = "rate_limited";
+
+// 連続で流量制限を超えた場合に切断するまでの回数
+const MAX_RATE_LIMIT_VIOLATIONS: u32 = 10;
+
+// クライアントから受信するメッセージ
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsClientMessage {
+    Subscribe { topic: String },
+    Unsubscribe { topic: String },
+    Ping,
+}
+
+// クライアントに送信するメッセージ
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsServerMessage {
+    Subscribed {
+        topic: String,
+    },
+    Unsubscribed {
+        topic: String,
+    },
+    Pong,
+    Event {
+        topic: String,
+        id: i64,
+        event: CloudEvent,
+    },
+    Error {
+        code: String,
+        message: String,
+    },
+}
+
+impl WsServerMessage {
+    fn error(code: &str, message: &str) -> Self {
+        WsServerMessage::Error {
+            code: code.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+// トピックの形式チェック
+pub fn is_valid_topic(topic: &str) -> bool {
+    if topic == WS_TOPIC_USERS {
+        return true;
+    }
+    match topic.strip_prefix(WS_TOPIC_USER_PREFIX) {
+        Some(uid) => !uid.is_empty() && uid.len() <= 64,
+        None => false,
+    }
+}
+
+// イベントがトピックの購読対象かどうか
+pub fn is_topic_matched(topic: &str, event: &UserStreamEvent) -> bool {
+    if topic == WS_TOPIC_USERS {
+        return true;
+    }
+    match (
+        topic.strip_prefix(WS_TOPIC_USER_PREFIX),
+        &event.event.subject,
+    ) {
+        (Some(uid), Some(subject)) => uid == subject,
+        _ => false,
+    }
+}
+
+// 接続単位の流量制限（トークンバケット）
+pub struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(refill_per_sec: u32, capacity: u32, now: Instant) -> Self {
+        TokenBucket {
+            capacity: capacity as f64,
+            tokens: capacity as f64,
+            refill_per_sec: refill_per_sec as f64,
+            last_refill: now,
+        }
+    }
+
+    // トークンを1つ消費できた場合はtrue
+    pub fn try_acquire(&mut self, now: Instant) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+// WebSocketの接続設定
+#[derive(Clone, Debug)]
+pub struct WsSessionSetting {
+    pub ping_interval: Duration,
+    pub idle_timeout: Duration,
+    pub send_buffer_size: usize,
+    pub max_subscriptions: usize,
+    pub rate_limit_per_sec: u32,
+    pub rate_limit_burst: u32,
+}
+
+impl WsSessionSetting {
+    pub fn from_config(config: &Config) -> Self {
+        WsSessionSetting {
+            ping_interval: config.ws_ping_interval,
+            idle_timeout: config.ws_idle_timeout,
+            send_buffer_size: config.ws_send_buffer_size.max(1),
+            max_subscriptions: config.ws_max_subscriptions,
+            rate_limit_per_sec: config.ws_rate_limit_per_sec,
+            rate_limit_burst: config.ws_rate_limit_burst,
+        }
+    }
+}
+
+// 送信キューが溢れた（クライアントの受信が追いつかない）
+struct SlowConsumer;
+
+// 送信キューにメッセージを追加する関数（ブロックせず、溢れた場合はエラー）
+fn enqueue(tx: &mpsc::Sender<Message>, msg: &WsServerMessage) -> Result<(), SlowConsumer> {
+    let text = serde_json::to_string(msg).unwrap_or_default();
+    tx.try_send(Message::Text(text.into()))
+        .map_err(|_| SlowConsumer)
+}
+
+// 実行するユースケースの構造体
+pub struct WsSessionUsecase {
+    pub hub: &'static UserEventHub,
+    pub setting: WsSessionSetting,
+    // シャットダウン時に切断する
+    pub shutdown: ShutdownListener,
+}
+
+impl WsSessionUsecase {
+    #[tracing::instrument(name = "WsSessionUsecase.exec", skip_all)]
+    pub async fn exec(&self, ctx: Context, socket: WebSocket) {
+        info(&ctx, "[ws] connected");
+
+        let (mut sender, mut receiver) = socket.split();
+
+        // 送信専用タスク（受信が遅いクライアントで他の処理が止まらないよう、送信キューを介して送る）
+        let (tx, mut rx) = mpsc::channel::<Message>(self.setting.send_buffer_size);
+        let (close_tx, mut close_rx) = oneshot::channel::<CloseFrame>();
+        let writer = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    biased;
+                    frame = &mut close_rx => {
+                        if let Ok(frame) = frame {
+                            let _ = sender.send(Message::Close(Some(frame))).await;
+                        }
+                        break;
+                    }
+                    msg = rx.recv() => match msg {
+                        Some(msg) => {
+                            if sender.send(msg).await.is_err() {
+                                break;
+                            }
+                        }
+                        None => break,
+                    },
+                }
+            }
+        });
+
+        let mut events = self.hub.subscribe(None).receiver;
+        let mut subscriptions: HashSet<String> = HashSet::new();
+        let mut bucket = TokenBucket::new(
+            self.setting.rate_limit_per_sec,
+            self.setting.rate_limit_burst,
+            Instant::now(),
+        );
+        let mut violations: u32 = 0;
+        let mut last_seen = Instant::now();
+        let mut ping = tokio::time::interval(self.setting.ping_interval);
+        ping.reset();
+        let mut shutdown = self.shutdown.clone();
+
+        // 切断理由（Noneはクライアントからの切断）
+        let close: Option<CloseFrame> = loop {
+            tokio::select! {
+                msg = receiver.next() => {
+                    let text = match msg {
+                        Some(Ok(Message::Text(text))) => text,
+                        Some(Ok(Message::Binary(_))) => {
+                            last_seen = Instant::now();
+                            let msg = WsServerMessage::error(WS_ERROR_INVALID_MESSAGE, "テキストメッセージのみ受け付けます。");
+                            if enqueue(&tx, &msg).is_err() {
+                                break Some(slow_consumer_frame());
+                            }
+                            continue;
+                        }
+                        Some(Ok(Message::Ping(_))) | Some(Ok(Message::Pong(_))) => {
+                            last_seen = Instant::now();
+                            continue;
+                        }
+                        Some(Ok(Message::Close(_))) | None => break None,
+                        Some(Err(err)) => {
+                            warn(&ctx, &format!("[ws] 受信エラー: {}", err));
+                            break None;
+                        }
+                    };
+                    last_seen = Instant::now();
+                    // メッセージの内容は個人情報を含む可能性があるためサイズのみ出力する
+                    tracing::debug!(bytes = text.len(), "[ws] recv");
+
+                    // 流量制限
+                    if !bucket.try_acquire(Instant::now()) {
+                        violations += 1;
+                        if violations >= MAX_RATE_LIMIT_VIOLATIONS {
+                            warn(&ctx, "[ws] 流量制限を繰り返し超えたため切断します");
+                            break Some(CloseFrame {
+                                code: close_code::POLICY,
+                                reason: "rate limit exceeded".into(),
+                            });
+                        }
+                        let msg = WsServerMessage::error(WS_ERROR_RATE_LIMITED, "メッセージの送信が多すぎます。");
+                        if enqueue(&tx, &msg).is_err() {
+                            break Some(slow_consumer_frame());
+                        }
+                        continue;
+                    }
+                    violations = 0;
+
+                    let res = self.handle_message(text.as_str(), &mut subscriptions);
+                    if enqueue(&tx, &res).is_err() {
+                        break Some(slow_consumer_frame());
+                    }
+                }
+                event = events.recv() => match event {
+                    Ok(event) => {
+                        let mut is_slow = false;
+                        for topic in subscriptions.iter().filter(|t| is_topic_matched(t, &event)) {
+                            info(&ctx, &format!("[ws] send event id={} topic={}", event.id, topic));
+                            let msg = WsServerMessage::Event {
+                                topic: topic.clone(),
+                                id: event.id,
+                                event: event.event.clone(),
+                            };
+                            if enqueue(&tx, &msg).is_err() {
+                                is_slow = true;
+                                break;
+                            }
+                        }
+                        if is_slow {
+                            break Some(slow_consumer_frame());
+                        }
+                    }
+                    // ハブからの配信に追いつけない場合も受信が遅いクライアントとして切断する
+                    Err(RecvError::Lagged(_)) => break Some(slow_consumer_frame()),
+                    Err(RecvError::Closed) => break Some(CloseFrame {
+                        code: close_code::AWAY,
+                        reason: "server shutdown".into(),
+                    }),
+                },
+                _ = shutdown.wait() => break Some(CloseFrame {
+                    code: close_code::AWAY,
+                    reason: "server shutdown".into(),
+                }),
+                _ = ping.tick() => {
+                    // 一定時間応答がない接続は切断
+                    if last_seen.elapsed() > self.setting.idle_timeout {
+                        break Some(CloseFrame {
+                            code: close_code::AWAY,
+                            reason: "idle timeout".into(),
+                        });
+                    }
+                    if tx.try_send(Message::Ping(Default::default())).is_err() {
+                        break Some(slow_consumer_frame());
+                    }
+                }
+            }
+        };
+
+        // 切断処理
+        match close {
+            Some(frame) => {
+                if frame.code == close_code::AGAIN {
+                    error(&ctx, "[ws] 受信が遅いクライアントのため切断します");
+                }
+                info(
+                    &ctx,
+                    &format!("[ws] disconnected by server (code={})", frame.code),
+                );
+                let _ = close_tx.send(frame);
+            }
+            None => info(&ctx, "[ws] disconnected by client"),
+        }
+        drop(tx);
+        if tokio::time::timeout(Duration::from_secs(5), writer)
+            .await
+            .is_err()
+        {
+            warn(&ctx, "[ws] 送信処理の終了待ちがタイムアウトしました");
+        }
+    }
+
+    // クライアントのメッセージを処理して返信するメッセージを返す
+    fn handle_message(&self, text: &str, subscriptions: &mut HashSet<String>) -> WsServerMessage {
+        let msg: WsClientMessage = match serde_json::from_str(text) {
+            Ok(msg) => msg,
+            Err(err) => {
+                return WsServerMessage::error(WS_ERROR_INVALID_MESSAGE, &err.to_string());
+            }
+        };
+
+        match msg {
+            WsClientMessage::Subscribe { topic } => {
+                if !is_valid_topic(&topic) {
+                    return WsServerMessage::error(
+                        WS_ERROR_INVALID_TOPIC,
+                        "「users」または「user:{uid}」を指定して下さい。",
+                    );
+                }
+                if !subscriptions.contains(&topic)
+                    && subscriptions.len() >= self.setting.max_subscriptions
+                {
+                    return WsServerMessage::error(
+                        WS_ERROR_TOO_MANY_SUBSCRIPTIONS,
+                        "購読数の上限を超えています。",
+                    );
+                }
+                subscriptions.insert(topic.clone());
+                WsServerMessage::Subscribed { topic }
+            }
+            WsClientMessage::Unsubscribe { topic } => {
+                subscriptions.remove(&topic);
+                WsServerMessage::Unsubscribed { topic }
+            }
+            WsClientMessage::Ping => WsServerMessage::Pong,
+        }
+    }
+}
+
+// 受信が遅いクライアントの切断理由
+fn slow_consumer_frame() -> CloseFrame {
+    CloseFrame {
+        code: close_code::AGAIN,
+        reason: "slow consumer".into(),
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(test)]
+// WebSocketのプロトコル処理のテスト
+mod ws_session_test {
+    use crate::api::events::cloud_event::{
+        CloudEvent, EVENT_SOURCE_USERS, EVENT_TYPE_USER_CREATED,
+    };
+    use crate::api::events::user_event_stream::UserStreamEvent;
+    use crate::api::usecases::ws::ws_session_usecase::{
+        TokenBucket, WsClientMessage, is_topic_matched, is_valid_topic,
+    };
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn it_parses_client_messages() {
+        let msg: WsClientMessage =
+            serde_json::from_str(r#"{"type":"subscribe","topic":"user:abc"}"#).unwrap();
+        assert_eq!(
+            msg,
+            WsClientMessage::Subscribe {
+                topic: "user:abc".to_string()
+            }
+        );
+        let msg: WsClientMessage = serde_json::from_str(r#"{"type":"ping"}"#).unwrap();
+        assert_eq!(msg, WsClientMessage::Ping);
+        assert!(serde_json::from_str::<WsClientMessage>(r#"{"type":"publish"}"#).is_err());
+    }
+
+    #[test]
+    fn it_matches_topics() {
+        assert!(is_valid_topic("users"));
+        assert!(is_valid_topic("user:abc"));
+        assert!(!is_valid_topic("user:"));
+        assert!(!is_valid_topic("admins"));
+
+        let event = UserStreamEvent {
+            id: 1,
+            event: CloudEvent::new(
+                EVENT_SOURCE_USERS,
+                EVENT_TYPE_USER_CREATED,
+                Some("abc".to_string()),
+                serde_json::json!({}),
+            ),
+        };
+        assert!(is_topic_matched("users", &event));
+        assert!(is_topic_matched("user:abc", &event));
+        assert!(!is_topic_matched("user:xyz", &event));
+    }
+
+    #[test]
+    fn it_limits_rate_with_token_bucket() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2, 3, now);
+
+        // バースト分は即時に消費できる
+        assert!(bucket.try_acquire(now));
+        assert!(bucket.try_acquire(now));
+        assert!(bucket.try_acquire(now));
+        assert!(!bucket.try_acquire(now));
+
+        // 経過時間に応じて補充される
+        assert!(bucket.try_acquire(now + Duration::from_millis(500)));
+        assert!(!bucket.try_acquire(now + Duration::from_millis(500)));
+
+        // 上限を超えて補充されない
+        let later = now + Duration::from_secs(60);
+        for _ in 0..3 {
+            assert!(bucket.try_acquire(later));
+        }
+        assert!(!bucket.try_acquire(later));
+    }
+}
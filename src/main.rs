@@ -1,32 +1,201 @@
 // axum
-use axum::serve;
+use axum::serve::{ListenerExt, serve};
+
+// 標準ライブラリ
+use std::net::SocketAddr;
+use std::time::Instant;
 
 // apiモジュール
 mod api;
 
 // routerモジュール
-use api::router::router;
+use api::router::{metrics_router, router};
+
+// DB接続用のモジュール
+use api::databases::database::{init_db_pool, shared_db_pool};
 
 // configsモジュール
-use api::configs::config;
+use api::configs::config::init_config;
+use api::configs::config_loader::{self, parse_args, render_config};
+use api::configs::config_reload::ConfigReloader;
 
 // loggerモジュール
 use api::loggers::logger::init_logger;
 
+// OpenTelemetry用のモジュール
+use api::telemetry::otel::init_telemetry;
+
+// ヘルスチェック用のモジュール
+use api::health::health_check::health_state;
+
+// シャットダウン用のモジュール
+use api::lifecycle::shutdown::{BackgroundWorkers, ShutdownSetting, shutdown, wait_for_signal};
+
+// メトリクス用のモジュール
+use api::telemetry::metrics::app_metrics;
+
+// アウトボックスのリレー、Webhook配信、ユーザーイベント受信用モジュール
+use api::events::outbox_relay::OutboxRelay;
+use api::events::user_event_stream::run_user_event_listener;
+use api::events::webhook_dispatcher::WebhookDispatcher;
+
+// レート制限用のモジュール
+use api::middleware::rate_limit_store::run_rate_limit_purger;
+
+// PROXYプロトコル用のモジュール
+use api::listeners::proxy_protocol::{ProxyProtocolListener, ProxyProtocolSetting};
+
 #[tokio::main]
 async fn main() {
-    // 環境変数取得
-    let config = config::get_config();
+    // 設定の読み込み（不正な値がある場合は全てのエラーを出力して起動を中止する）
+    let args = parse_args(std::env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    });
+    let config = config_loader::load(std::env::vars(), &args.overrides).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    });
+
+    // 有効な設定を出力して終了（秘密情報はマスクする）
+    if args.print_config {
+        print!("{}", render_config(&config));
+        return;
+    }
+    let config = init_config(config);
+
+    // OpenTelemetryの初期化（無効な場合はNone）
+    let telemetry = init_telemetry(config);
 
     // ロガーの初期化
     init_logger();
 
     // サーバー起動のログ出力
-    log::info!("Start rust_api (ENV:{}) !!", config.env);
+    tracing::info!("Start rust_api (ENV:{}) !!", config.env);
+
+    // DB接続プールの初期化（失敗した場合はリクエストごとに接続する）
+    if let Err(err) = init_db_pool().await {
+        tracing::error!("DB接続プールの初期化エラー: {}", err);
+    }
+
+    // メトリクス用のサーバーを別ポートで起動
+    if config.metrics_enabled {
+        let addr = format!("0.0.0.0:{}", config.metrics_port);
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        tokio::spawn(async move {
+            if let Err(err) = serve(listener, metrics_router()).await {
+                tracing::error!("メトリクス用サーバーのエラー: {}", err);
+            }
+        });
+    }
+
+    // アウトボックスのリレーをバックグラウンドで起動（シャットダウン時に終了を待つ）
+    let mut workers = BackgroundWorkers::new();
+    if let Some(relay) = OutboxRelay::from_config(config) {
+        workers.spawn("outbox_relay", relay.run(shutdown().subscribe()));
+    }
+
+    // Webhookの配信処理をバックグラウンドで起動
+    if let Some(dispatcher) = WebhookDispatcher::from_config(config) {
+        workers.spawn("webhook_dispatcher", dispatcher.run(shutdown().subscribe()));
+    }
+
+    // 設定ファイルの変更、SIGHUPの受信時に設定を再読み込み
+    workers.spawn(
+        "config_reloader",
+        ConfigReloader::new(args.overrides.clone()).run(shutdown().subscribe()),
+    );
+
+    // レート制限の期限切れの状態を定期的に削除
+    workers.spawn(
+        "rate_limit_purger",
+        run_rate_limit_purger(shutdown().subscribe()),
+    );
+
+    // ユーザーイベントの受信をバックグラウンドで起動（SSE配信用）
+    workers.spawn(
+        "user_event_listener",
+        run_user_event_listener(shutdown().subscribe()),
+    );
 
     // サーバー起動
     let app = router();
     let addr = format!("0.0.0.0:{}", config.port);
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    serve(listener, app).await.unwrap();
+
+    // PROXYプロトコルが有効な場合は信頼するプロキシから受信した送信元を接続元のアドレスにする
+    let listener =
+        ProxyProtocolListener::new(listener, ProxyProtocolSetting::from_config(config)).unwrap();
+
+    // 起動処理の完了を記録（スタートアップチェックが成功する）
+    health_state().mark_started();
+
+    // シグナル受信時はレディネスチェックを失敗させてから新規の受付を停止し、SSE・WebSocket・バックグラウンド処理に通知する
+    let setting = ShutdownSetting::from_config(config);
+    let readiness_delay = setting.readiness_delay;
+    let shutdown_signal = async move {
+        let signal = wait_for_signal().await;
+        tracing::info!("シャットダウンを開始します（signal:{}）", signal);
+        health_state().begin_shutdown();
+        tokio::time::sleep(readiness_delay).await;
+        shutdown().trigger();
+    };
+
+    // リクエストIDを引き継ぐか、クライアントのIPアドレスを判定するため接続元のアドレスを取得できるようにする
+    // 独自のリスナーでもConnectInfo<SocketAddr>で取得できるようにTapIoで包む
+    let server = serve(
+        listener.tap_io(|_| {}),
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal);
+    let mut server = tokio::spawn(async move { server.await });
+
+    // シャットダウンの通知を待つ（サーバーがエラーで停止した場合はそのまま終了処理に進む）
+    let mut listener = shutdown().subscribe();
+    tokio::select! {
+        result = &mut server => {
+            if let Ok(Err(err)) = result {
+                tracing::error!("サーバーのエラー: {}", err);
+            }
+            shutdown().trigger();
+        }
+        _ = listener.wait() => {}
+    }
+
+    // 処理中のリクエストの完了を待ち、時間内に完了しなかった場合は中断する
+    let start = Instant::now();
+    let deadline = tokio::time::Instant::now() + setting.drain_timeout;
+    let mut aborted_requests = 0;
+    if !server.is_finished()
+        && tokio::time::timeout_at(deadline, &mut server)
+            .await
+            .is_err()
+    {
+        aborted_requests = app_metrics().in_flight_requests().max(0);
+        server.abort();
+    }
+
+    // バックグラウンド処理の終了を待つ
+    let aborted_workers = workers
+        .join(deadline.saturating_duration_since(tokio::time::Instant::now()))
+        .await;
+
+    // DB接続プールを閉じる（未完了のトランザクションはロールバックされる）
+    if let Some(db) = shared_db_pool()
+        && let Err(err) = db.clone().close().await
+    {
+        tracing::error!("DB接続プールの切断エラー: {}", err);
+    }
+
+    tracing::info!(
+        aborted_requests = aborted_requests,
+        aborted_workers = aborted_workers,
+        elapsed_ms = start.elapsed().as_millis() as u64,
+        "Stop rust_api !!"
+    );
+
+    // 未送信のトレースとメトリクスを送信
+    if let Some(telemetry) = telemetry {
+        telemetry.shutdown();
+    }
 }
@@ -2,24 +2,42 @@
 
 //! ```cargo
 //! [dependencies]
+//! async-stream = "0.3.6"
 //! async-trait = "0.1.88"
-//! axum = "0.8.3"
+//! axum = { version = "0.8.3", features = ["ws"] }
 //! chrono = "0.4.40"
-//! env_logger = "0.11.8"
+//! chrono-tz = "0.10.4"
 //! envy = "0.4.2"
-//! log = "0.4.27"
+//! futures-util = "0.3.31"
+//! hex = "0.4.3"
+//! hmac = "0.12.1"
+//! http-body = "1.0.1"
+//! humantime = "2.2.0"
+//! ipnet = "2.11.0"
 //! mockall = "0.13.1"
+//! opentelemetry = "0.27.1"
+//! opentelemetry-http = "0.27.0"
+//! opentelemetry-otlp = { version = "0.27.0", features = ["grpc-tonic", "http-proto", "reqwest-client", "metrics", "trace"] }
+//! opentelemetry_sdk = { version = "0.27.1", features = ["rt-tokio"] }
+//! prometheus = { version = "0.14.0", default-features = false }
+//! regex = "1.11.1"
 //! reqwest = { version = "0.12.15", features = ["json"] }
 //! sea-orm = { version = "1.1.11", features = [ "sqlx-postgres", "runtime-tokio-rustls", "macros" ] }
 //! serde = { version = "1.0.219", features = ["derive"] }
 //! serde_json = "1.0.140"
+//! sha2 = "0.10.8"
 //! thiserror = "2.0.12"
 //! tokio = { version = "1.44.2", features = ["full"] }
-//! tower-http = { version = "0.6.2", features = ["trace"] }
+//! toml = "0.8.23"
+//! tower-http = { version = "0.6.2", features = ["trace", "cors", "compression-br", "compression-gzip", "compression-zstd", "decompression-gzip"] }
 //! tracing = "0.1.41"
+//! tracing-log = "0.2.0"
+//! tracing-opentelemetry = "0.28.0"
+//! tracing-subscriber = { version = "0.3.19", features = ["env-filter"] }
+//! url = { version = "2.5.4", features = ["serde"] }
 //! utoipa = { version = "5.3.1", features = ["axum_extras"] }
 //! utoipa-swagger-ui = { version = "9.0.0", features = ["axum"] }
-//! uuid = { version = "1.16.0", features = ["v4"] }
+//! uuid = { version = "1.16.0", features = ["v4", "v7"] }
 //! validator = { version = "0.20.0", features = ["derive"] }
 // //! ```
 